@@ -0,0 +1,80 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use runtime_injector::{constant, Injector, InjectorBuilder};
+use std::marker::PhantomData;
+
+// Each provider needs its own service type, so the types are generated by
+// nesting markers: each level doubles the number of distinct services
+struct Marker<T>(PhantomData<fn() -> T>);
+struct Left;
+struct Right;
+
+fn level0<T: 'static>(builder: &mut InjectorBuilder) {
+    builder.provide(constant(Marker::<T>(PhantomData)));
+}
+
+macro_rules! levels {
+    ($($level:ident => $next:ident),* $(,)?) => {
+        $(
+            fn $level<T: 'static>(builder: &mut InjectorBuilder) {
+                $next::<(T, Left)>(builder);
+                $next::<(T, Right)>(builder);
+            }
+        )*
+    };
+}
+
+levels! {
+    level1 => level0,
+    level2 => level1,
+    level3 => level2,
+    level4 => level3,
+    level5 => level4,
+    level6 => level5,
+    level7 => level6,
+    level8 => level7,
+    level9 => level8,
+    level10 => level9,
+    level11 => level10,
+    level12 => level11,
+}
+
+const PROVIDERS: usize = 5000;
+
+/// Registers 5000 providers, each for a different service.
+fn provide_all(builder: &mut InjectorBuilder) {
+    struct A;
+    struct B;
+    struct C;
+    struct D;
+    struct E;
+
+    // 4096 + 512 + 256 + 128 + 8
+    level12::<A>(builder);
+    level9::<B>(builder);
+    level8::<C>(builder);
+    level7::<D>(builder);
+    level3::<E>(builder);
+}
+
+fn build(c: &mut Criterion) {
+    c.bench_function("build 5000 providers", |b| {
+        b.iter(|| {
+            let mut builder = Injector::builder();
+            provide_all(&mut builder);
+            black_box(builder.build())
+        });
+    });
+}
+
+fn build_with_capacity(c: &mut Criterion) {
+    c.bench_function("build 5000 providers with capacity", |b| {
+        b.iter(|| {
+            let mut builder = InjectorBuilder::with_capacity(PROVIDERS);
+            provide_all(&mut builder);
+            black_box(builder.build())
+        });
+    });
+}
+
+criterion_group!(benches, build, build_with_capacity);
+criterion_main!(benches);
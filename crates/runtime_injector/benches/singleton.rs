@@ -0,0 +1,62 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use runtime_injector::{Injector, IntoSingleton, Svc};
+
+#[derive(Default)]
+struct Cheap(u32);
+
+fn build_injector() -> Injector {
+    let mut builder = Injector::builder();
+    builder.provide(Cheap::default.singleton());
+    builder.build()
+}
+
+fn first_request(c: &mut Criterion) {
+    c.bench_function("singleton first request", |b| {
+        b.iter_with_setup(build_injector, |injector| {
+            let service: Svc<Cheap> = injector.get().unwrap();
+            black_box(service.0)
+        });
+    });
+}
+
+fn cached_request(c: &mut Criterion) {
+    let injector = build_injector();
+    let _: Svc<Cheap> = injector.get().unwrap();
+    c.bench_function("singleton cached request", |b| {
+        b.iter(|| {
+            let service: Svc<Cheap> = injector.get().unwrap();
+            black_box(service.0)
+        });
+    });
+}
+
+#[cfg(feature = "arc")]
+fn concurrent_requests(c: &mut Criterion) {
+    const THREADS: usize = 4;
+    const REQUESTS: usize = 100;
+
+    c.bench_function("singleton concurrent first requests", |b| {
+        b.iter_with_setup(build_injector, |injector| {
+            let injector = &injector;
+            std::thread::scope(|scope| {
+                for _ in 0..THREADS {
+                    scope.spawn(move || {
+                        for _ in 0..REQUESTS {
+                            // Requests made while another thread is activating
+                            // the singleton fail, so only successes are used
+                            if let Ok(service) = injector.get::<Svc<Cheap>>() {
+                                black_box(service.0);
+                            }
+                        }
+                    });
+                }
+            });
+        });
+    });
+}
+
+#[cfg(feature = "arc")]
+criterion_group!(benches, first_request, cached_request, concurrent_requests);
+#[cfg(not(feature = "arc"))]
+criterion_group!(benches, first_request, cached_request);
+criterion_main!(benches);
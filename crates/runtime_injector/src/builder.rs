@@ -1,31 +1,501 @@
 use crate::{
-    Injector, Module, Provider, ProviderMap, RequestInfo, ServiceInfo,
+    constant, parameterize, AsAny, BoxedFallibleServiceFactory, BuildHook,
+    DefaultSingletonProvider, DefaultTransientProvider, DisposalQueue,
+    DynFactoryProvider, ErrorProvider, InjectError, InjectResult, Injector,
+    Interface, InterfaceFor, IntoSingleton, IntoTransient, ManyProvider,
+    MiddlewareEntry, Module, NextMiddleware, Provider, ProviderDecorator,
+    ProviderDisposer, ProviderMap, ProviderRegistry, Registration, RequestInfo,
+    RequiredArg, ResolveHook, Service, ServiceFactory, ServiceInfo, Svc,
+    TransientProvider, TypedProvider, UsageTracker,
 };
+use std::{cmp::Reverse, collections::HashMap, error::Error, time::Duration};
 
 /// A builder for an [`Injector`].
 #[derive(Default)]
 pub struct InjectorBuilder {
     providers: ProviderMap,
+    named_providers: HashMap<String, ProviderMap>,
     root_info: RequestInfo,
+    required_args: Vec<RequiredArg>,
+    layers: Vec<Layer>,
+    middleware: Vec<MiddlewareEntry>,
+    decorators: Vec<ProviderDecorator>,
+    disposers: Vec<ProviderDisposer>,
+    resolve_hooks: Vec<ResolveHook>,
+    build_hooks: Vec<BuildHook>,
+    max_depth: Option<usize>,
+    track_usage: bool,
+    time_singletons: bool,
+    parent: Option<Injector>,
+}
+
+struct Layer {
+    name: String,
+    module: Module,
+    enabled: bool,
 }
 
 impl InjectorBuilder {
+    /// Creates a builder with space for providers of at least `capacity`
+    /// different services. This is only a hint, and more providers can still
+    /// be registered, but it avoids repeatedly growing the builder's storage
+    /// when a large number of services are registered.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{constant, InjectorBuilder, Svc};
+    ///
+    /// let mut builder = InjectorBuilder::with_capacity(2);
+    /// builder.provide(constant(1i32));
+    /// builder.provide(constant(2u32));
+    ///
+    /// let injector = builder.build();
+    /// assert_eq!(1, *injector.get::<Svc<i32>>().unwrap());
+    /// ```
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        InjectorBuilder {
+            providers: HashMap::with_capacity(capacity),
+            ..InjectorBuilder::default()
+        }
+    }
+
+    /// Creates a builder for a child injector. See [`Injector::child()`] for
+    /// more information.
+    pub(crate) fn child_of(
+        parent: Injector,
+        root_info: RequestInfo,
+        middleware: Vec<MiddlewareEntry>,
+        max_depth: Option<usize>,
+    ) -> Self {
+        InjectorBuilder {
+            root_info,
+            middleware,
+            max_depth,
+            parent: Some(parent),
+            ..InjectorBuilder::default()
+        }
+    }
+
     /// Assigns the provider for a service type. Multiple providers can be
     /// registered for a service.
     pub fn provide<P: Provider>(&mut self, provider: P) {
-        self.add_provider(Box::new(provider))
+        self.add_provider(Box::new(provider));
+    }
+
+    /// Registers a singleton service which is created with its [`Default`]
+    /// implementation. This is shorthand for providing `T::default` as a
+    /// singleton. The provider is registered once the returned
+    /// [`Registration`] is dropped, or it can be registered as an
+    /// implementation of an interface instead.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{interface, Injector, Service, Svc};
+    ///
+    /// trait Clock: Service {}
+    /// interface!(dyn Clock = [SystemClock]);
+    ///
+    /// #[derive(Default)]
+    /// struct SystemClock;
+    /// impl Clock for SystemClock {}
+    ///
+    /// #[derive(Default)]
+    /// struct Settings;
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide_default::<Settings>();
+    /// builder.provide_default::<SystemClock>().with_interface::<dyn Clock>();
+    ///
+    /// let injector = builder.build();
+    /// let _settings: Svc<Settings> = injector.get().unwrap();
+    /// let _clock: Svc<dyn Clock> = injector.get().unwrap();
+    /// ```
+    pub fn provide_default<T: Service + Default>(
+        &mut self,
+    ) -> Registration<'_, DefaultSingletonProvider<T>> {
+        let factory: fn() -> T = T::default;
+        Registration::new(&mut self.providers, factory.singleton())
+    }
+
+    /// Registers a transient service which is created with its [`Default`]
+    /// implementation. See [`InjectorBuilder::provide_default()`] for more
+    /// information.
+    pub fn provide_default_transient<T: Service + Default>(
+        &mut self,
+    ) -> Registration<'_, DefaultTransientProvider<T>> {
+        let factory: fn() -> T = T::default;
+        Registration::new(&mut self.providers, factory.transient())
+    }
+
+    /// Registers a do-nothing implementation of an interface which is only
+    /// used if no other implementation is registered for it. The
+    /// implementation is provided as a constant with the lowest possible
+    /// priority, so any other provider for the interface is preferred when a
+    /// single implementation is requested. It is still included when all the
+    /// implementations of the interface are requested, like with
+    /// [`Services<I>`](crate::Services).
+    ///
+    /// This is useful for optional collaborators, like metrics sinks, where
+    /// services can depend on the interface without needing to check whether
+    /// an implementation was registered.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     interface, Injector, IntoSingleton, Service, Svc, TypedProvider,
+    /// };
+    ///
+    /// trait MetricsSink: Service {
+    ///     fn name(&self) -> &'static str;
+    /// }
+    /// interface!(dyn MetricsSink = [NullMetrics, StatsdMetrics]);
+    ///
+    /// struct NullMetrics;
+    /// impl MetricsSink for NullMetrics {
+    ///     fn name(&self) -> &'static str {
+    ///         "null"
+    ///     }
+    /// }
+    ///
+    /// #[derive(Default)]
+    /// struct StatsdMetrics;
+    /// impl MetricsSink for StatsdMetrics {
+    ///     fn name(&self) -> &'static str {
+    ///         "statsd"
+    ///     }
+    /// }
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide_null::<dyn MetricsSink, _>(NullMetrics);
+    /// let injector = builder.build();
+    /// let metrics: Svc<dyn MetricsSink> = injector.get().unwrap();
+    /// assert_eq!("null", metrics.name());
+    ///
+    /// // Any real implementation wins, regardless of registration order
+    /// let mut builder = Injector::builder();
+    /// builder.provide_null::<dyn MetricsSink, _>(NullMetrics);
+    /// builder.provide(
+    ///     StatsdMetrics::default
+    ///         .singleton()
+    ///         .with_interface::<dyn MetricsSink>(),
+    /// );
+    /// let injector = builder.build();
+    /// let metrics: Svc<dyn MetricsSink> = injector.get().unwrap();
+    /// assert_eq!("statsd", metrics.name());
+    /// ```
+    pub fn provide_null<I, T>(&mut self, service: T)
+    where
+        I: ?Sized + InterfaceFor<T>,
+        T: Service,
+    {
+        self.provide(
+            constant(service)
+                .with_priority(i32::MIN)
+                .with_interface::<I>(),
+        );
+    }
+
+    /// Registers a factory which creates several instances of a service at
+    /// once. A new batch of instances is created each time the service is
+    /// requested. See [`ManyProvider`] for more information, including how to
+    /// create the batch only once.
+    pub fn provide_many<R, F>(&mut self, factory: F)
+    where
+        R: Service,
+        F: Service + Fn(&Injector, &RequestInfo) -> InjectResult<Vec<R>>,
+    {
+        self.provide(ManyProvider::transient(factory));
+    }
+
+    /// Registers a provider for a service which always fails with the error
+    /// returned by the given factory. This can be used to explain why a
+    /// service is unavailable rather than leaving it without a provider. See
+    /// [`ErrorProvider`] for more information.
+    pub fn provide_error<I, F>(&mut self, factory: F)
+    where
+        I: ?Sized + Interface,
+        F: Service + Fn() -> Box<dyn Error>,
+    {
+        self.provide(ErrorProvider::<I, F>::new(factory));
+    }
+
+    /// Registers a transient service factory which may fail. Unlike
+    /// [`IntoFallible`], the error type only needs to be convertible into a
+    /// boxed error, so boxed errors and error types from other libraries can
+    /// be returned directly. If the factory fails, then the request fails with
+    /// [`InjectError::ActivationFailed`] containing the original error.
+    ///
+    /// [`IntoFallible`]: crate::IntoFallible
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{InjectError, Injector, Svc};
+    /// use std::error::Error;
+    ///
+    /// struct Foo;
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide_fallible(|| -> Result<Foo, Box<dyn Error + Send + Sync>> {
+    ///     Err("no foo today".into())
+    /// });
+    ///
+    /// let injector = builder.build();
+    /// match injector.get::<Svc<Foo>>() {
+    ///     Err(InjectError::ActivationFailed { inner, .. }) => {
+    ///         assert_eq!("no foo today", inner.to_string());
+    ///     }
+    ///     Err(error) => Err(error).unwrap(),
+    ///     Ok(_) => unreachable!("activation should have failed"),
+    /// }
+    /// ```
+    pub fn provide_fallible<D, R, E, F>(&mut self, factory: F)
+    where
+        D: 'static,
+        R: Service,
+        E: Service + Into<Box<dyn Error + Send + Sync>>,
+        F: ServiceFactory<D, Result = Result<R, E>>,
+    {
+        let factory = BoxedFallibleServiceFactory::new(factory);
+        self.provide(TransientProvider::new(factory));
+    }
+
+    /// Registers a transient factory for an interface which returns the
+    /// interface's trait object directly. This lets a single factory choose
+    /// which implementation to use at runtime, for example based on a
+    /// configuration service, rather than registering a conditional provider
+    /// for each implementation.
+    ///
+    /// The implementations returned by the factory don't need to be declared
+    /// with [`interface!`](crate::interface). The service can be requested as
+    /// either a [`Svc<I>`] or a [`Box<I>`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     interface, Injector, IntoSingleton, Service, Svc,
+    /// };
+    ///
+    /// trait Writer: Service {
+    ///     fn target(&self) -> &'static str;
+    /// }
+    /// interface!(dyn Writer = []);
+    ///
+    /// struct FileWriter;
+    /// impl Writer for FileWriter {
+    ///     fn target(&self) -> &'static str {
+    ///         "file"
+    ///     }
+    /// }
+    ///
+    /// struct ConsoleWriter;
+    /// impl Writer for ConsoleWriter {
+    ///     fn target(&self) -> &'static str {
+    ///         "console"
+    ///     }
+    /// }
+    ///
+    /// #[derive(Default)]
+    /// struct Config {
+    ///     log_to_file: bool,
+    /// }
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Config::default.singleton());
+    /// builder.provide_dyn(|config: Svc<Config>| -> Box<dyn Writer> {
+    ///     if config.log_to_file {
+    ///         Box::new(FileWriter)
+    ///     } else {
+    ///         Box::new(ConsoleWriter)
+    ///     }
+    /// });
+    ///
+    /// let injector = builder.build();
+    /// let writer: Svc<dyn Writer> = injector.get().unwrap();
+    /// assert_eq!("console", writer.target());
+    /// ```
+    pub fn provide_dyn<I, D, F>(&mut self, factory: F)
+    where
+        I: ?Sized + Interface,
+        D: 'static,
+        F: ServiceFactory<D, Result = Box<I>>,
+    {
+        self.provide(DynFactoryProvider::new(factory));
     }
 
     /// Adds a provider to the injector.
-    #[allow(clippy::missing_panics_doc)]
     pub fn add_provider(&mut self, provider: Box<dyn Provider>) {
-        // Should never panic
-        self.providers
-            .entry(provider.result())
-            .or_insert_with(|| Some(Vec::new()))
-            .as_mut()
-            .unwrap()
-            .push(provider)
+        insert_provider(&mut self.providers, provider);
+    }
+
+    /// Adds a batch of providers to the injector. This is useful when the
+    /// providers are computed at runtime, for example from a list of plugins,
+    /// rather than known ahead of time. Each provider is registered for the
+    /// service it provides, so concrete services and implementations of
+    /// interfaces can be mixed in the same batch.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     interface, Injector, IntoSingleton, Provider, Service, Svc,
+    ///     TypedProvider,
+    /// };
+    ///
+    /// trait Plugin: Service {
+    ///     fn name(&self) -> &'static str;
+    /// }
+    /// interface!(dyn Plugin = [Greeter, Logger]);
+    ///
+    /// #[derive(Default)]
+    /// struct Greeter;
+    /// impl Plugin for Greeter {
+    ///     fn name(&self) -> &'static str {
+    ///         "greeter"
+    ///     }
+    /// }
+    ///
+    /// #[derive(Default)]
+    /// struct Logger;
+    /// impl Plugin for Logger {
+    ///     fn name(&self) -> &'static str {
+    ///         "logger"
+    ///     }
+    /// }
+    ///
+    /// let discovered = ["greeter", "logger"];
+    /// let plugins = discovered.iter().map(|&name| -> Box<dyn Provider> {
+    ///     match name {
+    ///         "greeter" => Box::new(
+    ///             Greeter::default.singleton().with_interface::<dyn Plugin>(),
+    ///         ),
+    ///         _ => Box::new(
+    ///             Logger::default.singleton().with_interface::<dyn Plugin>(),
+    ///         ),
+    ///     }
+    /// });
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide_iter(plugins);
+    ///
+    /// let injector = builder.build();
+    /// let plugins: Vec<Svc<dyn Plugin>> = injector.get().unwrap();
+    /// let names: Vec<_> = plugins.iter().map(|plugin| plugin.name()).collect();
+    /// assert_eq!(vec!["greeter", "logger"], names);
+    /// ```
+    pub fn provide_iter<T>(&mut self, providers: T)
+    where
+        T: IntoIterator<Item = Box<dyn Provider>>,
+    {
+        for provider in providers {
+            self.add_provider(provider);
+        }
+    }
+
+    /// Assigns a provider for a service type under a name. Named providers
+    /// can be requested by name through [`Injector::get_named()`], or
+    /// together with their names by requesting a map like
+    /// [`HashMap<String, Svc<T>>`], and are ignored by all other requests.
+    /// This allows several implementations of an interface to be registered
+    /// and requested individually without causing unnamed requests for a
+    /// single implementation to fail. Multiple providers can be registered
+    /// for a service under the same name.
+    ///
+    /// [`HashMap<String, Svc<T>>`]: std::collections::HashMap
+    pub fn provide_named<P: Provider>(
+        &mut self,
+        name: impl Into<String>,
+        provider: P,
+    ) {
+        self.add_named_provider(name, Box::new(provider));
+    }
+
+    /// Adds a provider to the injector under a name. See
+    /// [`InjectorBuilder::provide_named()`] for more information.
+    pub fn add_named_provider(
+        &mut self,
+        name: impl Into<String>,
+        provider: Box<dyn Provider>,
+    ) {
+        let providers = self.named_providers.entry(name.into()).or_default();
+        insert_provider(providers, provider);
+    }
+
+    /// Replaces all the providers for a service type with a new provider,
+    /// returning the number of providers which were replaced. If the
+    /// provider provides an interface, then the providers for that interface
+    /// are replaced. Any additional interfaces the provider is registered as
+    /// (see [`TypedProvider::also_as()`]) have their providers replaced as
+    /// well.
+    ///
+    /// This is useful for swapping out a single service after a module has
+    /// been added, like replacing a real service with a mock in tests. Note
+    /// that providers in layers are only added when the injector is built,
+    /// so they are not replaced.
+    ///
+    /// [`TypedProvider::also_as()`]: crate::TypedProvider::also_as
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     constant, interface, Injector, IntoSingleton, Module, Service, Svc,
+    ///     TypedProvider,
+    /// };
+    ///
+    /// trait Clock: Service {
+    ///     fn now(&self) -> u64;
+    /// }
+    /// interface!(dyn Clock = [SystemClock, FixedClock]);
+    ///
+    /// #[derive(Default)]
+    /// struct SystemClock;
+    /// impl Clock for SystemClock {
+    ///     fn now(&self) -> u64 {
+    ///         1_000
+    ///     }
+    /// }
+    ///
+    /// struct FixedClock(u64);
+    /// impl Clock for FixedClock {
+    ///     fn now(&self) -> u64 {
+    ///         self.0
+    ///     }
+    /// }
+    ///
+    /// let mut module = Module::default();
+    /// module.provide(SystemClock::default.singleton().with_interface::<dyn Clock>());
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.add_module(module);
+    /// let replaced = builder
+    ///     .override_provider(constant(FixedClock(5)).with_interface::<dyn Clock>());
+    /// assert_eq!(1, replaced);
+    ///
+    /// let injector = builder.build();
+    /// let clock: Svc<dyn Clock> = injector.get().unwrap();
+    /// assert_eq!(5, clock.now());
+    /// ```
+    pub fn override_provider<P: Provider>(&mut self, provider: P) -> usize {
+        let mut provider: Box<dyn Provider> = Box::new(provider);
+        let aliases = provider.take_aliases();
+        let replaced = std::iter::once(provider.result())
+            .chain(aliases.iter().map(|alias| alias.result()))
+            .filter_map(|service_info| self.remove_providers(service_info))
+            .map(|providers| providers.len())
+            .sum();
+
+        insert_provider(&mut self.providers, provider);
+        for alias in aliases {
+            insert_provider(&mut self.providers, alias);
+        }
+
+        replaced
     }
 
     /// Removes all providers for a service type.
@@ -36,6 +506,37 @@ impl InjectorBuilder {
         self.providers.remove(&service_info).flatten()
     }
 
+    /// Checks whether any providers have been registered for a service or
+    /// interface, including providers in enabled layers. Providers registered
+    /// under a name are not included.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{Injector, IntoSingleton};
+    ///
+    /// #[derive(Default)]
+    /// struct Foo;
+    /// struct Bar;
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Foo::default.singleton());
+    /// assert!(builder.contains::<Foo>());
+    /// assert!(!builder.contains::<Bar>());
+    /// ```
+    #[must_use]
+    pub fn contains<I: ?Sized + Interface>(&self) -> bool {
+        let service_info = ServiceInfo::of::<I>();
+        std::iter::once(&self.providers)
+            .chain(self.enabled_layers().map(|layer| &layer.providers))
+            .any(|providers| {
+                providers
+                    .get(&service_info)
+                    .and_then(Option::as_ref)
+                    .is_some_and(|providers| !providers.is_empty())
+            })
+    }
+
     /// Borrows the root [`RequestInfo`] that will be used by calls to
     /// [`Injector::get()`].
     #[must_use]
@@ -50,33 +551,1109 @@ impl InjectorBuilder {
         &mut self.root_info
     }
 
+    /// Sets the maximum depth that services can be requested at. Requests
+    /// made directly to the injector have a depth of zero, and each
+    /// dependency is requested at a depth one greater than the service that
+    /// depends on it (see [`RequestInfo::depth()`]). Requests which are deeper
+    /// than the maximum depth fail with [`InjectError::MaxDepthExceeded`].
+    ///
+    /// Cycle detection only catches services which depend on themselves, so
+    /// this can be used to fail cleanly on pathologically deep dependency
+    /// chains instead of overflowing the stack. By default, there is no limit.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{InjectError, Injector, IntoTransient, Svc};
+    ///
+    /// #[derive(Default)]
+    /// struct Foo;
+    /// struct Bar(Svc<Foo>);
+    /// struct Baz(Svc<Bar>);
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Foo::default.transient());
+    /// builder.provide(Bar.transient());
+    /// builder.provide(Baz.transient());
+    /// builder.set_max_depth(1);
+    ///
+    /// let injector = builder.build();
+    /// assert!(injector.get::<Svc<Bar>>().is_ok());
+    /// assert!(matches!(
+    ///     injector.get::<Svc<Baz>>(),
+    ///     Err(InjectError::MaxDepthExceeded { depth: 2, .. })
+    /// ));
+    /// ```
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = Some(max_depth);
+    }
+
+    /// Tracks which providers are used by the injector. Unused services can
+    /// be checked at any time with [`Injector::unused_providers()`], which is
+    /// useful for finding dead configuration.
+    ///
+    /// With the "tracing" feature enabled, a warning is also emitted through
+    /// [`tracing`](https://docs.rs/tracing) for each service with a provider
+    /// that never activated it once the last injector sharing these
+    /// providers is dropped.
+    pub fn track_unused(&mut self) {
+        self.track_usage = true;
+    }
+
+    /// Measures how long each singleton takes to be created the first time
+    /// it is requested. The measured time includes the time spent resolving
+    /// the singleton's dependencies. This is useful for finding which
+    /// services dominate the startup time of an application. The times can
+    /// be read with [`Injector::singleton_build_times()`].
+    ///
+    /// Child injectors only time their singletons if this is enabled on
+    /// their own builder.
+    pub fn time_singletons(&mut self) {
+        self.time_singletons = true;
+    }
+
     /// Adds all the providers registered in a module. This may cause multiple
     /// providers to be registered for the same service.
     ///
     /// If any conflicting request parameters have been set before adding this
-    /// module, they are overridden.
-    #[allow(clippy::missing_panics_doc)]
+    /// module, they are overridden. Regardless of any parameters set later,
+    /// the providers in this module always use the parameters set by this
+    /// module, including any [`Arg<T>`](crate::Arg) values. This means two
+    /// modules can provide the same service with different arguments.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     interface, Arg, Injector, IntoSingleton, Module, Service, Svc,
+    ///     TypedProvider, WithArg,
+    /// };
+    ///
+    /// trait Db: Service {
+    ///     fn url(&self) -> &str;
+    /// }
+    ///
+    /// struct SqlDb(Arg<String>);
+    /// impl Db for SqlDb {
+    ///     fn url(&self) -> &str {
+    ///         &self.0
+    ///     }
+    /// }
+    ///
+    /// interface!(dyn Db = [SqlDb]);
+    ///
+    /// let mut tenant_a = Module::default();
+    /// tenant_a.provide(SqlDb.singleton().with_interface::<dyn Db>());
+    /// tenant_a.with_arg::<SqlDb, String>("postgres://a".to_owned());
+    ///
+    /// let mut tenant_b = Module::default();
+    /// tenant_b.provide(SqlDb.singleton().with_interface::<dyn Db>());
+    /// tenant_b.with_arg::<SqlDb, String>("postgres://b".to_owned());
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.add_module(tenant_a);
+    /// builder.add_module(tenant_b);
+    ///
+    /// let injector = builder.build();
+    /// let dbs: Vec<Svc<dyn Db>> = injector.get().unwrap();
+    /// let urls: Vec<_> = dbs.iter().map(|db| db.url()).collect();
+    /// assert_eq!(vec!["postgres://a", "postgres://b"], urls);
+    /// ```
     pub fn add_module(&mut self, module: Module) {
-        for (result, module_providers) in module.providers {
-            // Should never panic
-            let mut module_providers = module_providers.unwrap();
-            self.providers
-                .entry(result)
-                .and_modify(|providers| {
-                    // Should never panic
-                    providers.as_mut().unwrap().append(&mut module_providers)
-                })
-                .or_insert_with(|| Some(module_providers));
+        self.add_module_with(module, merge_providers);
+    }
+
+    /// Adds all the providers registered in a module, replacing any existing
+    /// providers for the services the module provides. Services which are
+    /// only provided by this builder keep their providers, and services which
+    /// are only provided by the module are added as usual. Providers
+    /// registered under a name only replace providers registered under the
+    /// same name.
+    ///
+    /// If any conflicting request parameters have been set before adding this
+    /// module, they are overridden. The providers in this module always use
+    /// the parameters set by this module, like with
+    /// [`InjectorBuilder::add_module()`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     constant, define_module, interface, Injector, Service, Svc,
+    /// };
+    ///
+    /// trait Config: Service {
+    ///     fn url(&self) -> &str;
+    /// }
+    ///
+    /// struct BaseConfig;
+    /// impl Config for BaseConfig {
+    ///     fn url(&self) -> &str {
+    ///         "https://example.com"
+    ///     }
+    /// }
+    ///
+    /// struct LocalConfig;
+    /// impl Config for LocalConfig {
+    ///     fn url(&self) -> &str {
+    ///         "http://localhost"
+    ///     }
+    /// }
+    ///
+    /// interface!(dyn Config = [BaseConfig, LocalConfig]);
+    ///
+    /// let base = define_module! {
+    ///     services = [constant(1u32)],
+    ///     interfaces = {
+    ///         dyn Config = [constant(BaseConfig)],
+    ///     },
+    /// };
+    /// let local = define_module! {
+    ///     interfaces = {
+    ///         dyn Config = [constant(LocalConfig)],
+    ///     },
+    /// };
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.add_module(base);
+    /// builder.add_module_override(local);
+    ///
+    /// let injector = builder.build();
+    /// let configs: Vec<Svc<dyn Config>> = injector.get().unwrap();
+    /// assert_eq!(1, configs.len());
+    /// assert_eq!("http://localhost", configs[0].url());
+    /// let value: Svc<u32> = injector.get().unwrap();
+    /// assert_eq!(1, *value);
+    /// ```
+    pub fn add_module_override(&mut self, module: Module) {
+        self.add_module_with(module, replace_providers);
+    }
+
+    /// Merges another builder into this one. Providers registered in the
+    /// other builder are added after the providers registered in this
+    /// builder, the same way [`InjectorBuilder::add_module()`] adds a
+    /// module's providers. This is useful for libraries which expose a
+    /// builder that has already been configured.
+    ///
+    /// Unlike a module, a builder may also carry root request parameters,
+    /// layers, middleware, decorators, disposers, and hooks, which are all
+    /// added to this builder after its own. If both builders set the same
+    /// request parameter, then the other builder's value is used, like when
+    /// adding a module. Layers with the same name are combined into one
+    /// layer, which is enabled if the other builder's layer is enabled. The
+    /// other builder's maximum depth is used if it has one, and usage and
+    /// singleton build times are tracked if either builder tracks them. If
+    /// this builder is for a child injector, then it keeps its parent,
+    /// otherwise it uses the other builder's parent, if any.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     constant, Arg, Injector, IntoSingleton, Svc, WithArg,
+    /// };
+    ///
+    /// struct Server(u16);
+    ///
+    /// fn library_builder() -> runtime_injector::InjectorBuilder {
+    ///     let mut builder = Injector::builder();
+    ///     builder.provide((|port: Arg<u16>| Server(*port)).singleton());
+    ///     builder.with_arg::<Server, u16>(80);
+    ///     builder
+    /// }
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(constant(5i32));
+    /// builder.extend(library_builder());
+    ///
+    /// let injector = builder.build();
+    /// let server: Svc<Server> = injector.get().unwrap();
+    /// assert_eq!(80, server.0);
+    /// assert_eq!(5, *injector.get::<Svc<i32>>().unwrap());
+    /// ```
+    pub fn extend(&mut self, other: InjectorBuilder) {
+        let InjectorBuilder {
+            providers,
+            named_providers,
+            root_info,
+            required_args,
+            layers,
+            middleware,
+            decorators,
+            disposers,
+            resolve_hooks,
+            build_hooks,
+            max_depth,
+            track_usage,
+            time_singletons,
+            parent,
+        } = other;
+
+        merge_providers(&mut self.providers, providers);
+        for (name, other_providers) in named_providers {
+            let providers = self.named_providers.entry(name).or_default();
+            merge_providers(providers, other_providers);
         }
 
-        for (key, value) in module.parameters {
+        for (key, value) in root_info.parameters() {
+            drop(self.root_info.insert_parameter_boxed(key, value.clone()));
+        }
+
+        for required_arg in required_args {
+            if !self.required_args.contains(&required_arg) {
+                self.required_args.push(required_arg);
+            }
+        }
+
+        for layer in layers {
+            match self.layers.iter_mut().find(|l| l.name == layer.name) {
+                Some(existing) => {
+                    merge_modules(&mut existing.module, layer.module);
+                    existing.enabled = layer.enabled;
+                }
+                None => self.layers.push(layer),
+            }
+        }
+
+        self.middleware.extend(middleware);
+        self.decorators.extend(decorators);
+        self.disposers.extend(disposers);
+        self.resolve_hooks.extend(resolve_hooks);
+        self.build_hooks.extend(build_hooks);
+        self.max_depth = max_depth.or(self.max_depth);
+        self.track_usage |= track_usage;
+        self.time_singletons |= time_singletons;
+        if self.parent.is_none() {
+            self.parent = parent;
+        }
+    }
+
+    fn add_module_with(
+        &mut self,
+        module: Module,
+        combine: fn(&mut ProviderMap, ProviderMap),
+    ) {
+        let Module {
+            mut providers,
+            mut named_providers,
+            parameters,
+            required_args,
+        } = module;
+
+        // The module's own providers always see the module's parameters, even
+        // if another module sets different values for them
+        if !parameters.is_empty() {
+            let parameters = Svc::new(parameters.clone());
+            parameterize(&mut providers, &parameters);
+            for providers in named_providers.values_mut() {
+                parameterize(providers, &parameters);
+            }
+        }
+
+        combine(&mut self.providers, providers);
+        for (name, module_providers) in named_providers {
+            let providers = self.named_providers.entry(name).or_default();
+            combine(providers, module_providers);
+        }
+
+        for (key, value) in parameters {
             drop(self.root_info_mut().insert_parameter_boxed(&key, value));
         }
+
+        for required_arg in required_args {
+            if !self.required_args.contains(&required_arg) {
+                self.required_args.push(required_arg);
+            }
+        }
+    }
+
+    /// Declares that an [`Arg<T>`](crate::Arg) must be provided for the
+    /// service `S`. See [`Module::require_arg()`] for more information.
+    pub fn require_arg<S: Service, T: Service + AsAny + Clone>(&mut self) {
+        let required_arg = RequiredArg::of::<S, T>();
+        if !self.required_args.contains(&required_arg) {
+            self.required_args.push(required_arg);
+        }
+    }
+
+    /// Gets the arguments which are required to be provided before the
+    /// injector is built.
+    #[must_use]
+    pub fn required_args(&self) -> &[RequiredArg] {
+        &self.required_args
+    }
+
+    /// Logs each provider registered in this builder, along with the
+    /// concrete type it provides, its lifetime, name, and priority, and the
+    /// arguments required by its service. Services which only have required
+    /// arguments are logged as well. This is useful for printing the
+    /// injector's configuration at startup.
+    ///
+    /// An event is emitted through [`tracing`](https://docs.rs/tracing) for
+    /// each provider, so this is only available with the "tracing" feature.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{Arg, Injector, IntoSingleton};
+    ///
+    /// struct Database(Arg<String>);
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Database.singleton());
+    /// builder.require_arg::<Database, String>();
+    ///
+    /// #[cfg(feature = "tracing")]
+    /// builder.log_registry();
+    /// let _injector = builder.build();
+    /// ```
+    #[cfg(feature = "tracing")]
+    pub fn log_registry(&self) {
+        let provider_maps = || {
+            std::iter::once(&self.providers)
+                .chain(self.enabled_layers().map(|layer| &layer.providers))
+        };
+        let mut services: Vec<ServiceInfo> = provider_maps()
+            .flat_map(ProviderMap::keys)
+            .copied()
+            .chain(self.all_required_args().map(RequiredArg::service_info))
+            .collect();
+        services.sort_by_key(ServiceInfo::name);
+        services.dedup();
+
+        for service_info in services {
+            let required_args: Vec<_> = self
+                .all_required_args()
+                .filter(|required_arg| {
+                    required_arg.service_info() == service_info
+                })
+                .map(|required_arg| required_arg.arg_info().name())
+                .collect();
+
+            let mut providers = provider_maps()
+                .filter_map(|providers| providers.get(&service_info))
+                .filter_map(Option::as_ref)
+                .flatten()
+                .peekable();
+            if providers.peek().is_none() {
+                tracing::info!(
+                    service = service_info.name(),
+                    required_args = ?required_args,
+                    "required arguments declared for unregistered service"
+                );
+            }
+
+            for provider in providers {
+                tracing::info!(
+                    service = service_info.name(),
+                    implementation = provider.implementation().name(),
+                    lifetime = %provider.lifetime(),
+                    name = ?provider.name(),
+                    priority = provider.priority(),
+                    required_args = ?required_args,
+                    "registered provider"
+                );
+            }
+        }
+    }
+
+    /// Checks the configuration of the injector without building it. All
+    /// detected errors are returned at once rather than just the first one.
+    /// Enabled layers are included in the validation. This verifies that:
+    ///
+    /// - each required argument has been given a value or a default value
+    ///   (see [`Module::require_arg()`] and [`WithArg::with_arg_or()`]), and
+    /// - each dependency of each provider has a provider registered for it,
+    ///   either in this builder or, for child injectors, in the parent
+    ///   injector. A [`MissingDependency`](InjectError::MissingDependency)
+    ///   error is returned for each unsatisfied dependency.
+    ///
+    /// Dependencies are reported by the providers themselves (see
+    /// [`Provider::dependencies()`]). Providers created from service
+    /// factories report each dependency they request as a [`Svc<T>`] or
+    /// [`Box<T>`], while requests which may succeed without a provider, like
+    /// [`Option<Svc<T>>`], are not checked. Conditional providers and custom
+    /// providers don't report any dependencies by default.
+    ///
+    /// [`Svc<T>`]: crate::Svc
+    /// [`WithArg::with_arg_or()`]: crate::WithArg::with_arg_or
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{InjectError, Injector, IntoSingleton, Svc};
+    ///
+    /// struct Database;
+    /// struct UserRepository(Svc<Database>);
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(UserRepository.singleton());
+    ///
+    /// match builder.validate() {
+    ///     Err(errors) => assert!(matches!(
+    ///         errors.as_slice(),
+    ///         [InjectError::MissingDependency { .. }]
+    ///     )),
+    ///     Ok(_) => unreachable!("the database was never provided"),
+    /// }
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<InjectError>> {
+        let mut errors: Vec<_> = self
+            .all_required_args()
+            .filter(|required_arg| {
+                [required_arg.param_name(), required_arg.default_param_name()]
+                    .iter()
+                    .all(|param_name| {
+                        self.root_info.get_parameter(param_name).is_none()
+                            && self.enabled_layers().all(|layer| {
+                                !layer.parameters.contains_key(param_name)
+                            })
+                    })
+            })
+            .map(|required_arg| InjectError::MissingArgument {
+                service_info: required_arg.service_info(),
+                arg_info: required_arg.arg_info(),
+            })
+            .collect();
+
+        let provider_maps = || {
+            std::iter::once(&self.providers)
+                .chain(self.enabled_layers().map(|layer| &layer.providers))
+        };
+        let named_provider_maps = self.named_providers.values().chain(
+            self.enabled_layers()
+                .flat_map(|layer| layer.named_providers.values()),
+        );
+        let providers = provider_maps()
+            .chain(named_provider_maps)
+            .flat_map(ProviderMap::values)
+            .flatten()
+            .flatten();
+        let inherited = self
+            .parent
+            .as_ref()
+            .map(Injector::registered_services)
+            .unwrap_or_default();
+        for provider in providers {
+            for dependency_info in provider.dependencies() {
+                let registered = inherited.contains(&dependency_info)
+                    || provider_maps().any(|providers| {
+                        providers.contains_key(&dependency_info)
+                    });
+                if !registered {
+                    errors.push(InjectError::MissingDependency {
+                        service_info: provider.result(),
+                        dependency_info,
+                        inner: None,
+                    });
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Adds a middleware which intercepts each request made directly to the
+    /// injector, like through [`Injector::get()`]. Middleware can be used to
+    /// add parameters to requests, enforce limits, or log requests. Requests
+    /// made on behalf of another request, like requests for a service's
+    /// dependencies, do not pass through this middleware. To intercept those
+    /// as well, use [`InjectorBuilder::add_nested_request_middleware()`].
+    ///
+    /// Middleware runs in the order it was added, with each middleware
+    /// wrapping the ones added after it. Each middleware receives the
+    /// [`RequestInfo`] for the request and the next step in the chain, which
+    /// it should call to continue the request. It can fail the request by
+    /// returning an error instead of calling the next step. If it returns
+    /// `Ok(())` without calling the next step, then the request is still
+    /// performed after the middleware returns. If it calls the next step and
+    /// the request fails, then it should return that error. Returning
+    /// `Ok(())` instead causes the request to fail with
+    /// [`InjectError::SuppressedByMiddleware`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{Injector, IntoTransient, RequestInfo, Svc};
+    ///
+    /// struct Foo(String);
+    /// impl Foo {
+    ///     fn new(request_info: RequestInfo) -> Self {
+    ///         let value = request_info
+    ///             .get_parameter("tenant")
+    ///             .and_then(|value| value.downcast_ref::<String>())
+    ///             .cloned()
+    ///             .unwrap_or_default();
+    ///         Foo(value)
+    ///     }
+    /// }
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Foo::new.transient());
+    /// builder.add_request_middleware(|request_info, next| {
+    ///     request_info.insert_parameter("tenant", "acme".to_owned());
+    ///     next(request_info)
+    /// });
+    ///
+    /// let injector = builder.build();
+    /// let foo: Svc<Foo> = injector.get().unwrap();
+    /// assert_eq!("acme", foo.0);
+    /// ```
+    pub fn add_request_middleware<F>(&mut self, middleware: F)
+    where
+        F: Service
+            + Fn(&mut RequestInfo, NextMiddleware<'_>) -> InjectResult<()>,
+    {
+        self.middleware
+            .push(MiddlewareEntry::new(middleware, false));
+    }
+
+    /// Adds a middleware which intercepts every request made to the
+    /// injector, including requests made on behalf of other requests. See
+    /// [`InjectorBuilder::add_request_middleware()`] for more information.
+    ///
+    /// This also includes the requests an injector makes internally to
+    /// fulfill another request. For example, a request for [`Svc<T>`]
+    /// requests [`Services<T>`] to find the providers of the service, so the
+    /// middleware runs more than once for that request.
+    ///
+    /// [`Svc<T>`]: crate::Svc
+    /// [`Services<T>`]: crate::Services
+    pub fn add_nested_request_middleware<F>(&mut self, middleware: F)
+    where
+        F: Service
+            + Fn(&mut RequestInfo, NextMiddleware<'_>) -> InjectResult<()>,
+    {
+        self.middleware.push(MiddlewareEntry::new(middleware, true));
+    }
+
+    /// Adds a decorator to each provider of a service or interface. Each time
+    /// a provider creates an instance of the service, the decorator is given
+    /// that instance and can return a different instance to use instead,
+    /// like one which wraps the original instance to add caching or logging.
+    /// Decorators apply to requests for [`Svc<T>`], [`Vec<Svc<T>>`], and
+    /// [`Services<T>`], and providers registered under a name are decorated
+    /// as well. Decorated services can't be requested as owned service
+    /// pointers.
+    ///
+    /// If a provider stores its instance, like a singleton, then the
+    /// decorator only runs once and the decorated instance is reused. For
+    /// other providers, like transient providers, the decorator runs each
+    /// time an instance is created. Multiple decorators can be added for a
+    /// service, and they run in the order they were added.
+    ///
+    /// [`Svc<T>`]: crate::Svc
+    /// [`Services<T>`]: crate::Services
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     interface, Injector, IntoSingleton, Service, Svc, TypedProvider,
+    /// };
+    ///
+    /// trait Repository: Service {
+    ///     fn get(&self) -> String;
+    /// }
+    /// interface!(dyn Repository = [SqlRepository, LoggingRepository]);
+    ///
+    /// #[derive(Default)]
+    /// struct SqlRepository;
+    /// impl Repository for SqlRepository {
+    ///     fn get(&self) -> String {
+    ///         "row".to_owned()
+    ///     }
+    /// }
+    ///
+    /// struct LoggingRepository(Svc<dyn Repository>);
+    /// impl Repository for LoggingRepository {
+    ///     fn get(&self) -> String {
+    ///         format!("logged {}", self.0.get())
+    ///     }
+    /// }
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(
+    ///     SqlRepository::default
+    ///         .singleton()
+    ///         .with_interface::<dyn Repository>(),
+    /// );
+    /// builder.decorate(|repository: Svc<dyn Repository>, _, _| {
+    ///     Ok(Svc::new(LoggingRepository(repository)) as Svc<dyn Repository>)
+    /// });
+    ///
+    /// let injector = builder.build();
+    /// let repository: Svc<dyn Repository> = injector.get().unwrap();
+    /// assert_eq!("logged row", repository.get());
+    /// ```
+    pub fn decorate<I, F>(&mut self, decorator: F)
+    where
+        I: ?Sized + Interface,
+        F: Service
+            + Fn(Svc<I>, &Injector, &RequestInfo) -> InjectResult<Svc<I>>,
+    {
+        self.decorators.push(ProviderDecorator::new(decorator));
+    }
+
+    /// Adds a disposer for a service or interface. When the injector is
+    /// dropped, the disposer is called with each instance of the service
+    /// that was stored by its providers, giving the service a chance to
+    /// clean up resources like file handles or background threads. Disposers
+    /// for all services run in the reverse order that the instances were
+    /// created, so a service is disposed of before the services it depends
+    /// on. Multiple disposers can be added for a service.
+    ///
+    /// Since injectors share their providers with their clones and scopes,
+    /// disposers only run once the last of those injectors is dropped. Only
+    /// instances which are stored by their providers, like singletons, are
+    /// disposed of this way. Transient and scoped instances are never passed
+    /// to disposers, and neither are instances that were never requested.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{Injector, IntoSingleton, Svc};
+    /// use std::sync::{
+    ///     atomic::{AtomicBool, Ordering},
+    ///     Arc,
+    /// };
+    ///
+    /// #[derive(Default)]
+    /// struct Connection;
+    ///
+    /// let closed = Arc::new(AtomicBool::new(false));
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Connection::default.singleton());
+    /// builder.on_dispose({
+    ///     let closed = closed.clone();
+    ///     move |_: Svc<Connection>| closed.store(true, Ordering::Relaxed)
+    /// });
+    ///
+    /// let injector = builder.build();
+    /// let _connection: Svc<Connection> = injector.get().unwrap();
+    /// assert!(!closed.load(Ordering::Relaxed));
+    ///
+    /// drop(injector);
+    /// assert!(closed.load(Ordering::Relaxed));
+    /// ```
+    pub fn on_dispose<I, F>(&mut self, disposer: F)
+    where
+        I: ?Sized + Interface,
+        F: Service + Fn(Svc<I>),
+    {
+        self.disposers.push(ProviderDisposer::new(disposer));
+    }
+
+    /// Adds a callback which is invoked each time a provider activates a
+    /// service, whether it succeeds or fails. The callback is given the
+    /// service that was requested, the time spent in its provider, and
+    /// whether activation succeeded. This is useful for tracing and metrics,
+    /// like recording a span for each service or finding slow constructors.
+    ///
+    /// The time includes activating the service's dependencies, and any
+    /// decorators for the service. Dependencies are reported separately as
+    /// well. Providers which store their instances, like singletons, are
+    /// still reported each time they provide their stored instance.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{Injector, IntoSingleton, ServiceInfo, Svc};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// #[derive(Default)]
+    /// struct Foo;
+    ///
+    /// let resolved = Arc::new(Mutex::new(Vec::new()));
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Foo::default.singleton());
+    /// builder.on_resolve({
+    ///     let resolved = resolved.clone();
+    ///     move |service_info: &ServiceInfo, _elapsed, result| {
+    ///         if result.is_ok() {
+    ///             resolved.lock().unwrap().push(*service_info);
+    ///         }
+    ///     }
+    /// });
+    ///
+    /// let injector = builder.build();
+    /// let _foo: Svc<Foo> = injector.get().unwrap();
+    /// assert_eq!(vec![ServiceInfo::of::<Foo>()], *resolved.lock().unwrap());
+    /// ```
+    pub fn on_resolve<F>(&mut self, callback: F)
+    where
+        F: Service + Fn(&ServiceInfo, Duration, &InjectResult<()>),
+    {
+        self.resolve_hooks.push(ResolveHook::new(callback));
+    }
+
+    /// Adds a callback which can rewrite the registered providers just
+    /// before the injector is built. This is useful for cross-cutting
+    /// changes which need to see every provider, like wrapping all
+    /// singletons or replacing a provider registered by a module.
+    ///
+    /// Build hooks run in the order they were added, after all modules and
+    /// enabled layers have been added, so they see the complete set of
+    /// providers. They run before any decorators, disposers, or resolve
+    /// hooks are applied, and providers inherited from a parent injector are
+    /// not included.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     constant, Injector, IntoSingleton, ProviderRegistry, ServiceInfo,
+    ///     Svc,
+    /// };
+    ///
+    /// #[derive(Default)]
+    /// struct Foo;
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Foo::default.singleton());
+    /// builder.add_build_hook(|registry: &mut ProviderRegistry<'_>| {
+    ///     registry.remove(ServiceInfo::of::<Foo>());
+    ///     registry.provide(constant(4i32));
+    /// });
+    ///
+    /// let injector = builder.build();
+    /// assert!(injector.get::<Svc<Foo>>().is_err());
+    /// assert_eq!(4, *injector.get::<Svc<i32>>().unwrap());
+    /// ```
+    pub fn add_build_hook<F>(&mut self, hook: F)
+    where
+        F: Service + FnOnce(&mut ProviderRegistry<'_>),
+    {
+        self.build_hooks.push(BuildHook::new(hook));
+    }
+
+    /// Configures a named layer of providers. Layers are similar to modules,
+    /// except they remain addressable after they've been configured, meaning
+    /// the providers in a layer can be enabled, disabled, or removed as a
+    /// unit until the injector is built. Layers are enabled by default.
+    ///
+    /// If a layer with the given name already exists, then it is configured
+    /// further rather than replaced. When the injector is built, each enabled
+    /// layer is added to it like a module in the order the layers were first
+    /// configured.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{Injector, IntoSingleton, Services};
+    ///
+    /// #[derive(Default)]
+    /// struct Foo;
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Foo::default.singleton());
+    /// builder.layer("experimental", |layer| {
+    ///     layer.provide(Foo::default.singleton());
+    /// });
+    ///
+    /// // Maybe based on some configuration
+    /// builder.disable_layer("experimental");
+    ///
+    /// let injector = builder.build();
+    /// let foos: Services<Foo> = injector.get().unwrap();
+    /// assert_eq!(1, foos.len());
+    /// ```
+    pub fn layer<F: FnOnce(&mut Module)>(&mut self, name: &str, configure: F) {
+        let index = if let Some(index) =
+            self.layers.iter().position(|layer| layer.name == name)
+        {
+            index
+        } else {
+            self.layers.push(Layer {
+                name: name.to_owned(),
+                module: Module::default(),
+                enabled: true,
+            });
+            self.layers.len() - 1
+        };
+
+        configure(&mut self.layers[index].module);
+    }
+
+    /// Enables a layer. Returns `false` if the layer doesn't exist.
+    pub fn enable_layer(&mut self, name: &str) -> bool {
+        self.set_layer_enabled(name, true)
+    }
+
+    /// Disables a layer, preventing its providers and parameters from being
+    /// added to the injector. Returns `false` if the layer doesn't exist.
+    pub fn disable_layer(&mut self, name: &str) -> bool {
+        self.set_layer_enabled(name, false)
+    }
+
+    /// Removes a layer and returns its contents if it exists.
+    pub fn remove_layer(&mut self, name: &str) -> Option<Module> {
+        let index = self.layers.iter().position(|layer| layer.name == name)?;
+        Some(self.layers.remove(index).module)
+    }
+
+    /// Checks whether a layer is enabled. Returns `None` if the layer
+    /// doesn't exist.
+    #[must_use]
+    pub fn is_layer_enabled(&self, name: &str) -> Option<bool> {
+        self.layers
+            .iter()
+            .find(|layer| layer.name == name)
+            .map(|layer| layer.enabled)
+    }
+
+    fn set_layer_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        match self.layers.iter_mut().find(|layer| layer.name == name) {
+            Some(layer) => {
+                layer.enabled = enabled;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn enabled_layers(&self) -> impl Iterator<Item = &Module> {
+        self.layers
+            .iter()
+            .filter(|layer| layer.enabled)
+            .map(|layer| &layer.module)
+    }
+
+    fn all_required_args(&self) -> impl Iterator<Item = &RequiredArg> {
+        self.required_args.iter().chain(
+            self.enabled_layers()
+                .flat_map(|layer| layer.required_args.iter()),
+        )
     }
 
     /// Builds the injector.
     #[must_use]
-    pub fn build(self) -> Injector {
-        Injector::new_from_parts(self.providers, self.root_info)
+    pub fn build(mut self) -> Injector {
+        for layer in std::mem::take(&mut self.layers) {
+            if layer.enabled {
+                self.add_module(layer.module);
+            }
+        }
+
+        for hook in std::mem::take(&mut self.build_hooks) {
+            hook.run(&mut ProviderRegistry::new(
+                &mut self.providers,
+                &mut self.named_providers,
+            ));
+        }
+
+        sort_by_priority(&mut self.providers);
+        for providers in self.named_providers.values_mut() {
+            sort_by_priority(providers);
+        }
+
+        let disposal = DisposalQueue::new();
+        for disposer in &self.disposers {
+            disposer.apply(&mut self.providers, &disposal);
+            for providers in self.named_providers.values_mut() {
+                disposer.apply(providers, &disposal);
+            }
+        }
+
+        for decorator in &self.decorators {
+            decorator.apply(&mut self.providers);
+            for providers in self.named_providers.values_mut() {
+                decorator.apply(providers);
+            }
+        }
+
+        for hook in &self.resolve_hooks {
+            hook.apply(&mut self.providers);
+            for providers in self.named_providers.values_mut() {
+                hook.apply(providers);
+            }
+        }
+
+        // Usage is tracked last so activations through decorators count
+        let usage = self.track_usage.then(|| {
+            let mut usage = UsageTracker::default();
+            usage.track(&mut self.providers);
+            for providers in self.named_providers.values_mut() {
+                usage.track(providers);
+            }
+            usage
+        });
+
+        // Inherited providers are added last so they aren't wrapped again
+        if let Some(parent) = &self.parent {
+            parent.inherit_providers(
+                &mut self.providers,
+                &mut self.named_providers,
+            );
+        }
+
+        Injector::new_from_parts(
+            self.providers,
+            self.named_providers,
+            self.root_info,
+            self.middleware,
+            disposal,
+            self.max_depth,
+            usage,
+            self.time_singletons,
+        )
+    }
+
+    /// Validates the configuration of the injector, then builds it. See
+    /// [`InjectorBuilder::validate()`] for more information.
+    pub fn build_checked(self) -> Result<Injector, Vec<InjectError>> {
+        self.validate()?;
+        Ok(self.build())
+    }
+}
+
+/// Adds a provider and its aliases to a provider map.
+#[allow(clippy::missing_panics_doc)]
+pub(crate) fn insert_provider(
+    providers: &mut ProviderMap,
+    mut provider: Box<dyn Provider>,
+) {
+    let aliases = provider.take_aliases();
+
+    // Should never panic
+    providers
+        .entry(provider.result())
+        .or_insert_with(|| Some(Vec::new()))
+        .as_mut()
+        .unwrap()
+        .push(provider);
+
+    for alias in aliases {
+        insert_provider(providers, alias);
+    }
+}
+
+/// Sorts the providers for each service by descending priority. Providers
+/// with the same priority stay in the order they were registered in.
+fn sort_by_priority(providers: &mut ProviderMap) {
+    for providers in providers.values_mut().flatten() {
+        providers.sort_by_key(|provider| Reverse(provider.priority()));
+    }
+}
+
+/// Moves all the providers from one provider map into another.
+#[allow(clippy::missing_panics_doc)]
+fn merge_providers(providers: &mut ProviderMap, from: ProviderMap) {
+    for (result, from_providers) in from {
+        // Should never panic
+        let mut from_providers = from_providers.unwrap();
+        providers
+            .entry(result)
+            .and_modify(|providers| {
+                // Should never panic
+                providers.as_mut().unwrap().append(&mut from_providers);
+            })
+            .or_insert_with(|| Some(from_providers));
     }
 }
+
+/// Moves the contents of one module into another, the same way
+/// [`InjectorBuilder::add_module()`] would add them.
+pub(crate) fn merge_modules(module: &mut Module, from: Module) {
+    let Module {
+        providers,
+        named_providers,
+        parameters,
+        required_args,
+    } = from;
+
+    merge_providers(&mut module.providers, providers);
+    for (name, from_providers) in named_providers {
+        let providers = module.named_providers.entry(name).or_default();
+        merge_providers(providers, from_providers);
+    }
+
+    module.parameters.extend(parameters);
+    for required_arg in required_args {
+        if !module.required_args.contains(&required_arg) {
+            module.required_args.push(required_arg);
+        }
+    }
+}
+
+/// Moves all the providers from one provider map into another, replacing the
+/// providers for any services which are in both maps.
+fn replace_providers(providers: &mut ProviderMap, from: ProviderMap) {
+    providers.extend(from);
+}
+
+/// Registers the same provider for several type parameters of a generic
+/// service. The provider expression is registered once for each listed type,
+/// with the type parameter's name referring to that type.
+///
+/// This works with anything that has a `provide` method, like an
+/// [`InjectorBuilder`] or a [`Module`]. Interfaces still need to be declared
+/// with [`interface!`](crate::interface) for each type they're used with.
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{
+///     interface, provide_generic, Injector, IntoSingleton, Service, Svc,
+///     TypedProvider,
+/// };
+/// use std::marker::PhantomData;
+///
+/// struct User;
+/// struct Order;
+///
+/// trait Repo<T: Service>: Service {
+///     fn table(&self) -> &'static str;
+/// }
+///
+/// struct Repository<T>(&'static str, PhantomData<fn() -> T>);
+///
+/// impl<T: Service> Repository<T> {
+///     fn new() -> Self {
+///         Repository(std::any::type_name::<T>(), PhantomData)
+///     }
+/// }
+///
+/// impl<T: Service> Repo<T> for Repository<T> {
+///     fn table(&self) -> &'static str {
+///         self.0
+///     }
+/// }
+///
+/// interface! {
+///     dyn Repo<User> = [Repository<User>],
+///     dyn Repo<Order> = [Repository<Order>],
+/// };
+///
+/// let mut builder = Injector::builder();
+/// provide_generic!(
+///     builder,
+///     T in [User, Order] => Repository::<T>::new
+///         .singleton()
+///         .with_interface::<dyn Repo<T>>(),
+/// );
+///
+/// let injector = builder.build();
+/// let users: Svc<dyn Repo<User>> = injector.get().unwrap();
+/// let orders: Svc<dyn Repo<Order>> = injector.get().unwrap();
+/// assert!(users.table().ends_with("User"));
+/// assert!(orders.table().ends_with("Order"));
+/// ```
+#[macro_export]
+macro_rules! provide_generic {
+    (
+        $builder:expr,
+        $param:ident in [$($type:ty),* $(,)?] => $provider:expr
+        $(,)?
+    ) => {
+        $({
+            #[allow(dead_code)]
+            type $param = $type;
+            $builder.provide($provider);
+        })*
+    };
+}
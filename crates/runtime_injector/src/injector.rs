@@ -1,8 +1,15 @@
 use crate::{
-    InjectResult, InjectorBuilder, Interface, Provider, Request, RequestInfo,
-    ServiceInfo, Services, Svc,
+    DisposalQueue, DisposeOnDrop, DynSvc, Inject, InjectError, InjectResult,
+    InjectorBuilder, Interface, IntoOverrides, Lifetime, MiddlewareEntry,
+    OwnedDynSvc, Provider, ProviderMeta, Request, RequestInfo,
+    RequestParameter, ScopeStorage, ServiceDescription, ServiceInfo, Services,
+    Svc, UsageTracker,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
 };
-use std::collections::HashMap;
 
 pub(crate) type ProviderMap =
     HashMap<ServiceInfo, Option<Vec<Box<dyn Provider>>>>;
@@ -11,6 +18,7 @@ pub(crate) trait MapContainerEx<T> {
     fn new(value: T) -> Self;
     fn with_inner<R, F: FnOnce(&T) -> R>(&self, f: F) -> R;
     fn with_inner_mut<R, F: FnOnce(&mut T) -> R>(&self, f: F) -> R;
+    fn try_with_inner_mut<R, F: FnOnce(&mut T) -> R>(&self, f: F) -> Option<R>;
 }
 
 #[cfg(feature = "rc")]
@@ -32,6 +40,13 @@ mod types {
         fn with_inner_mut<R, F: FnOnce(&mut T) -> R>(&self, f: F) -> R {
             f(&mut *self.borrow_mut())
         }
+
+        fn try_with_inner_mut<R, F: FnOnce(&mut T) -> R>(
+            &self,
+            f: F,
+        ) -> Option<R> {
+            self.try_borrow_mut().ok().map(|mut inner| f(&mut *inner))
+        }
     }
 }
 
@@ -54,6 +69,13 @@ mod types {
         fn with_inner_mut<R, F: FnOnce(&mut T) -> R>(&self, f: F) -> R {
             f(&mut *self.lock().unwrap())
         }
+
+        fn try_with_inner_mut<R, F: FnOnce(&mut T) -> R>(
+            &self,
+            f: F,
+        ) -> Option<R> {
+            self.try_lock().ok().map(|mut inner| f(&mut *inner))
+        }
     }
 }
 
@@ -116,8 +138,22 @@ pub(crate) use types::*;
 /// ```
 #[derive(Clone, Default)]
 pub struct Injector {
+    // Only held to be dropped, and dropped first so that disposers run
+    // before the providers are dropped
+    #[allow(dead_code)]
+    disposal: Svc<DisposeOnDrop>,
     provider_map: MapContainer<ProviderMap>,
+    named_providers: Svc<HashMap<String, MapContainer<ProviderMap>>>,
+    // Providers given a name with `with_name`, which can also be requested
+    // by that name
+    named_aliases: Svc<HashMap<String, MapContainer<ProviderMap>>>,
     root_request_info: Svc<RequestInfo>,
+    middleware: Svc<Vec<MiddlewareEntry>>,
+    max_depth: Option<usize>,
+    usage: Option<Svc<UsageTracker>>,
+    time_singletons: bool,
+    scope: ScopeStorage,
+    sealed: Svc<AtomicBool>,
 }
 
 impl Injector {
@@ -137,21 +173,151 @@ impl Injector {
     )]
     pub fn new(providers: ProviderMap) -> Self {
         Injector {
+            disposal: Svc::default(),
             provider_map: MapContainerEx::new(providers),
+            named_providers: Svc::default(),
+            named_aliases: Svc::default(),
             root_request_info: Svc::new(RequestInfo::default()),
+            middleware: Svc::default(),
+            max_depth: None,
+            usage: None,
+            time_singletons: false,
+            scope: ScopeStorage::new(),
+            sealed: Svc::default(),
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new_from_parts(
         providers: ProviderMap,
+        named_providers: HashMap<String, ProviderMap>,
         request_info: RequestInfo,
+        middleware: Vec<MiddlewareEntry>,
+        disposal: DisposalQueue,
+        max_depth: Option<usize>,
+        usage: Option<UsageTracker>,
+        time_singletons: bool,
     ) -> Self {
+        let named_providers = named_providers
+            .into_iter()
+            .map(|(name, providers)| (name, MapContainerEx::new(providers)))
+            .collect();
+        let provider_map = MapContainerEx::new(providers);
+        let named_aliases = BorrowedProvider::borrow_named(&provider_map)
+            .into_iter()
+            .map(|(name, providers)| (name, MapContainerEx::new(providers)))
+            .collect();
+
         Injector {
-            provider_map: MapContainerEx::new(providers),
+            disposal: Svc::new(DisposeOnDrop::new(disposal)),
+            provider_map,
+            named_providers: Svc::new(named_providers),
+            named_aliases: Svc::new(named_aliases),
             root_request_info: Svc::new(request_info),
+            middleware: Svc::new(middleware),
+            max_depth,
+            usage: usage.map(Svc::new),
+            time_singletons,
+            scope: ScopeStorage::new(),
+            sealed: Svc::default(),
+        }
+    }
+
+    /// Creates a new scope. The returned injector shares its providers with
+    /// this injector, meaning singletons are shared between them, but it
+    /// has its own instances of each scoped service. This is useful for
+    /// creating services which should only live for as long as a unit of
+    /// work, like a web request. See [`IntoScoped`] for more information.
+    ///
+    /// [`IntoScoped`]: crate::IntoScoped
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     Injector, IntoScoped, IntoSingleton, Svc,
+    /// };
+    ///
+    /// #[derive(Default)]
+    /// struct Database;
+    /// struct RequestContext(Svc<Database>);
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Database::default.singleton());
+    /// builder.provide(RequestContext.scoped());
+    ///
+    /// let injector = builder.build();
+    /// let scope1 = injector.create_scope().unwrap();
+    /// let scope2 = injector.create_scope().unwrap();
+    /// let context1: Svc<RequestContext> = scope1.get().unwrap();
+    /// let context2: Svc<RequestContext> = scope2.get().unwrap();
+    ///
+    /// // Each scope has its own context, but they share the same database
+    /// assert!(!Svc::ptr_eq(&context1, &context2));
+    /// assert!(Svc::ptr_eq(&context1.0, &context2.0));
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`InjectError::Sealed`] if the injector has been sealed. See
+    /// [`Injector::seal()`] for more information.
+    pub fn create_scope(&self) -> InjectResult<Injector> {
+        self.check_sealed()?;
+        Ok(Injector {
+            scope: ScopeStorage::new(),
+            ..self.clone()
+        })
+    }
+
+    /// Seals the injector, preventing any new scopes or child injectors from
+    /// being created from it. After an injector is sealed,
+    /// [`Injector::create_scope()`] and [`Injector::child()`] return
+    /// [`InjectError::Sealed`]. This is useful for guaranteeing that no
+    /// more scopes are created after startup, like when handing the
+    /// injector to untrusted plugins.
+    ///
+    /// Sealing applies to every clone and scope of this injector, since they
+    /// share the same providers, and it can't be undone. Existing clones,
+    /// scopes, and child injectors remain valid and can still be used to
+    /// request services.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{InjectError, Injector};
+    ///
+    /// let injector = Injector::builder().build();
+    /// let scope = injector.create_scope().unwrap();
+    /// injector.seal();
+    ///
+    /// assert!(scope.is_sealed());
+    /// assert!(matches!(injector.create_scope(), Err(InjectError::Sealed)));
+    /// assert!(matches!(scope.child(), Err(InjectError::Sealed)));
+    /// ```
+    pub fn seal(&self) {
+        self.sealed.store(true, Ordering::Relaxed);
+    }
+
+    /// Checks whether the injector has been sealed. See [`Injector::seal()`]
+    /// for more information.
+    #[must_use]
+    pub fn is_sealed(&self) -> bool {
+        self.sealed.load(Ordering::Relaxed)
+    }
+
+    fn check_sealed(&self) -> InjectResult<()> {
+        if self.is_sealed() {
+            Err(InjectError::Sealed)
+        } else {
+            Ok(())
         }
     }
 
+    /// Gets the storage for scoped services in this injector's scope.
+    pub(crate) fn scope(&self) -> &ScopeStorage {
+        &self.scope
+    }
+
     /// Performs a request for a service. There are several types of requests
     /// that can be made to the service container by default:
     ///
@@ -266,6 +432,88 @@ impl Injector {
         self.get_with(self.root_request_info.as_ref())
     }
 
+    /// Creates a value which implements [`Inject`]. Every [`Request`]
+    /// implements [`Inject`], so this can be used the same way as
+    /// [`Injector::get()`]. Unlike [`Injector::get()`], this can also create
+    /// types which only implement [`Inject`], like structs which are composed
+    /// of several requests. The value is created directly rather than by a
+    /// provider, so it doesn't need to be registered and is never wrapped in
+    /// a service pointer. See [`Inject`] for more information.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     constant, Inject, InjectResult, Injector, Request, RequestInfo, Svc,
+    /// };
+    ///
+    /// struct Settings {
+    ///     port: Svc<u16>,
+    ///     host: Svc<String>,
+    /// }
+    ///
+    /// impl Inject for Settings {
+    ///     fn inject(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
+    ///         Ok(Settings {
+    ///             port: Request::request(injector, info)?,
+    ///             host: Request::request(injector, info)?,
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(constant(8080u16));
+    /// builder.provide(constant("localhost".to_owned()));
+    ///
+    /// let injector = builder.build();
+    /// let settings: Settings = injector.inject().unwrap();
+    /// assert_eq!(8080, *settings.port);
+    /// assert_eq!("localhost", settings.host.as_str());
+    ///
+    /// // Requests can be injected as well
+    /// let port: Svc<u16> = injector.inject().unwrap();
+    /// assert_eq!(8080, *port);
+    /// ```
+    pub fn inject<T: Inject>(&self) -> InjectResult<T> {
+        let request_info = self.root_request_info.as_ref();
+        self.with_middleware(request_info, |info| T::inject(self, info))
+    }
+
+    /// Performs a request for a service, returning a default value if the
+    /// requested service has no providers. Unlike requesting an
+    /// `Option<Svc<T>>`, any other errors are still returned, including
+    /// [`InjectError::MissingDependency`] if the service's provider exists
+    /// but one of its dependencies doesn't. The default value is only created
+    /// if it's needed.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{Injector, IntoSingleton, Svc};
+    ///
+    /// struct Config(u16);
+    /// struct Server(Svc<Config>);
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Server.singleton());
+    ///
+    /// let injector = builder.build();
+    /// let config = injector.get_or(|| Svc::new(Config(8080))).unwrap();
+    /// assert_eq!(8080, config.0);
+    ///
+    /// // The server's provider exists, so its missing dependency is an error
+    /// assert!(injector.get_or(|| Svc::new(Server(config))).is_err());
+    /// ```
+    pub fn get_or<R: Request>(
+        &self,
+        default: impl FnOnce() -> R,
+    ) -> InjectResult<R> {
+        match self.get() {
+            Err(InjectError::MissingProvider { .. }) => Ok(default()),
+            result => result,
+        }
+    }
+
     /// Performs a request for a service with additional request information.
     ///
     /// ## Example
@@ -302,61 +550,1535 @@ impl Injector {
         &self,
         request_info: &RequestInfo,
     ) -> InjectResult<R> {
-        R::request(self, request_info)
+        self.with_middleware(request_info, |info| R::request(self, info))
     }
 
-    /// Gets implementations of a service from the container. This is
-    /// equivalent to requesting [`Services<T>`] from [`Injector::get()`].
-    pub(crate) fn get_service<I: ?Sized + Interface>(
+    /// Performs a request for a service, and also returns the path of
+    /// services which led to the last service that was requested. When a
+    /// request fails, this is usually the chain of dependencies from the
+    /// requested service to the service which failed, which makes it easier
+    /// to diagnose failures deep in the dependency graph, like a
+    /// [`InjectError::MissingDependency`]. Services which are never
+    /// requested from the injector, like instances stored by a singleton
+    /// that has already been activated, are not part of the path.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{Injector, IntoTransient, ServiceInfo, Svc};
+    ///
+    /// struct Config;
+    /// struct Database(Svc<Config>);
+    /// struct Server(Svc<Database>);
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Database.transient());
+    /// builder.provide(Server.transient());
+    ///
+    /// let injector = builder.build();
+    /// let (result, path) = injector.get_traced::<Svc<Server>>();
+    /// assert!(result.is_err());
+    /// assert_eq!(
+    ///     vec![
+    ///         ServiceInfo::of::<Server>(),
+    ///         ServiceInfo::of::<Database>(),
+    ///         ServiceInfo::of::<Config>(),
+    ///     ],
+    ///     path
+    /// );
+    /// ```
+    pub fn get_traced<R: Request>(
         &self,
-        request_info: &RequestInfo,
-    ) -> InjectResult<Services<I>> {
-        Services::new(
-            self.clone(),
-            self.provider_map.clone(),
-            request_info.clone(),
-        )
+    ) -> (InjectResult<R>, Vec<ServiceInfo>) {
+        let mut request_info = self.root_request_info.as_ref().clone();
+        let trace = request_info.start_trace();
+        let result = self.get_with(&request_info);
+        let path = trace.with_inner_mut(std::mem::take);
+        (result, path)
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::{
-        DynSvc, InjectError, InjectResult, Injector, Provider, RequestInfo,
-        ServiceInfo, Svc,
-    };
-    use core::panic;
 
-    #[test]
-    fn get_exact_returns_error_on_invalid_provider() {
-        struct BadProvider;
-        impl Provider for BadProvider {
-            fn result(&self) -> ServiceInfo {
-                ServiceInfo::of::<i32>()
-            }
+    /// Performs a request for a service, using the given service instances
+    /// instead of the registered providers for those services. This only
+    /// affects this request, including any dependencies which are activated
+    /// as part of this request. This is useful for testing a service in
+    /// isolation while letting the injector provide the rest of its
+    /// dependencies.
+    ///
+    /// Note that if a singleton is activated as part of this request, then
+    /// that singleton will be created using the overridden services and will
+    /// continue to be used by future requests.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{Injector, IntoTransient, Overrides, Svc};
+    ///
+    /// struct Config(i32);
+    /// struct Foo(Svc<Config>);
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide((|| Config(1)).transient());
+    /// builder.provide(Foo.transient());
+    ///
+    /// let injector = builder.build();
+    /// let mut overrides = Overrides::new();
+    /// overrides.insert(Svc::new(Config(2)));
+    ///
+    /// let foo: Svc<Foo> = injector.get_with_overrides(overrides).unwrap();
+    /// assert_eq!(2, foo.0 .0);
+    ///
+    /// let foo: Svc<Foo> = injector.get().unwrap();
+    /// assert_eq!(1, foo.0 .0);
+    /// ```
+    pub fn get_with_overrides<R: Request>(
+        &self,
+        overrides: impl IntoOverrides,
+    ) -> InjectResult<R> {
+        let mut request_info = self.root_request_info.as_ref().clone();
+        request_info.set_overrides(overrides.into_overrides());
+        self.get_with(&request_info)
+    }
 
-            fn provide(
-                &mut self,
-                _injector: &Injector,
-                _request_info: &RequestInfo,
-            ) -> InjectResult<DynSvc> {
-                Ok(Svc::new(1.2_f32))
-            }
+    /// Performs a request with additional request parameters. The parameters
+    /// are added to the injector's root request info for this request only,
+    /// replacing any root parameters with the same keys. This is useful for
+    /// passing a value which is only known at the time of the request, like
+    /// the current tenant, without building a [`RequestInfo`] by hand.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     Injector, IntoTransient, RequestInfo, RequestParameter, Svc,
+    /// };
+    ///
+    /// struct Tenant(u32);
+    /// impl Tenant {
+    ///     fn new(request_info: RequestInfo) -> Self {
+    ///         let id = request_info
+    ///             .get_parameter("tenant_id")
+    ///             .and_then(|id| id.downcast_ref::<u32>())
+    ///             .copied()
+    ///             .unwrap_or_default();
+    ///         Tenant(id)
+    ///     }
+    /// }
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Tenant::new.transient());
+    ///
+    /// let injector = builder.build();
+    /// let tenant: Svc<Tenant> = injector
+    ///     .get_with_params(vec![(
+    ///         "tenant_id".to_owned(),
+    ///         Box::new(5u32) as Box<dyn RequestParameter>,
+    ///     )])
+    ///     .unwrap();
+    /// assert_eq!(5, tenant.0);
+    ///
+    /// // The root request info is unchanged
+    /// let tenant: Svc<Tenant> = injector.get().unwrap();
+    /// assert_eq!(0, tenant.0);
+    /// ```
+    pub fn get_with_params<R: Request>(
+        &self,
+        params: impl IntoIterator<Item = (String, Box<dyn RequestParameter>)>,
+    ) -> InjectResult<R> {
+        let mut request_info = self.root_request_info.as_ref().clone();
+        for (key, value) in params {
+            request_info.insert_parameter_boxed(&key, value);
         }
 
-        let mut builder = Injector::builder();
-        builder.provide(BadProvider);
+        self.get_with(&request_info)
+    }
 
-        let injector = builder.build();
-        let bad: InjectResult<Svc<i32>> = injector.get();
+    /// Performs a request for a service which is only known at runtime,
+    /// returning the type-erased service pointer. This behaves like
+    /// requesting a [`Svc<T>`] of the service, so the service must have
+    /// exactly one provider. Downcasting the service to its concrete type is
+    /// the caller's responsibility. This is useful for plugins and scripting,
+    /// where the services being requested aren't known at compile time.
+    ///
+    /// The returned pointer is always erased down to the concrete service,
+    /// including for services which have been decorated, so it can be
+    /// downcast to the service's type. Requests for the [`ServiceInfo`] of a
+    /// trait interface still resolve through the interface's
+    /// implementations, but services which were registered for a trait
+    /// interface with [`InjectorBuilder::provide_dyn()`] or decorated as a
+    /// trait interface are stored behind an internal wrapper and can't be
+    /// downcast from the returned pointer.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{Injector, IntoSingleton, ServiceInfo};
+    ///
+    /// struct Plugin(&'static str);
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide((|| Plugin("hello")).singleton());
+    ///
+    /// let injector = builder.build();
+    /// let plugin = injector.get_dyn(ServiceInfo::of::<Plugin>()).unwrap();
+    /// let plugin = plugin.downcast::<Plugin>().unwrap();
+    /// assert_eq!("hello", plugin.0);
+    /// ```
+    pub fn get_dyn(&self, service_info: ServiceInfo) -> InjectResult<DynSvc> {
+        self.with_middleware(self.root_request_info.as_ref(), |info| {
+            self.request_dyn(service_info, info)
+        })
+    }
 
-        match bad {
-            Err(InjectError::InvalidProvider { service_info })
-                if service_info == ServiceInfo::of::<i32>() => {}
-            Err(error) => Err(error).unwrap(),
-            Ok(value) => {
-                panic!("Value of {} was provided by an invalid provider", value)
+    /// Performs a request using only the providers registered under the
+    /// given name. Providers registered under a name are never used by
+    /// requests which aren't for that name, including requests made by
+    /// [`Injector::get()`], except for requests for maps of named services
+    /// like [`HashMap<String, Svc<T>>`]. If no providers are registered under
+    /// the given name for the requested service, then the request is treated
+    /// as though the service has no providers. The dependencies of the
+    /// service are resolved normally. See [`InjectorBuilder::provide_named()`]
+    /// for more information.
+    ///
+    /// Providers which were given a name with [`TypedProvider::with_name()`]
+    /// can be requested by that name as well. Unlike providers registered
+    /// with [`InjectorBuilder::provide_named()`], they can still be used by
+    /// unnamed requests. If a service has providers registered under the
+    /// name with both methods, only the providers registered with
+    /// [`InjectorBuilder::provide_named()`] are used.
+    ///
+    /// [`TypedProvider::with_name()`]: crate::TypedProvider::with_name
+    /// [`HashMap<String, Svc<T>>`]: std::collections::HashMap
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     interface, Injector, IntoSingleton, Service, Svc, TypedProvider,
+    /// };
+    ///
+    /// trait Cache: Service {
+    ///     fn backend(&self) -> &'static str;
+    /// }
+    /// interface!(dyn Cache = [RedisCache, MemoryCache]);
+    ///
+    /// #[derive(Default)]
+    /// struct RedisCache;
+    /// impl Cache for RedisCache {
+    ///     fn backend(&self) -> &'static str {
+    ///         "redis"
+    ///     }
+    /// }
+    ///
+    /// #[derive(Default)]
+    /// struct MemoryCache;
+    /// impl Cache for MemoryCache {
+    ///     fn backend(&self) -> &'static str {
+    ///         "memory"
+    ///     }
+    /// }
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide_named(
+    ///     "redis",
+    ///     RedisCache::default.singleton().with_interface::<dyn Cache>(),
+    /// );
+    /// builder.provide_named(
+    ///     "memory",
+    ///     MemoryCache::default.singleton().with_interface::<dyn Cache>(),
+    /// );
+    ///
+    /// let injector = builder.build();
+    /// let cache: Svc<dyn Cache> = injector.get_named("redis").unwrap();
+    /// assert_eq!("redis", cache.backend());
+    /// assert!(injector.get_named::<Svc<dyn Cache>>("disk").is_err());
+    /// assert!(injector.get::<Svc<dyn Cache>>().is_err());
+    /// ```
+    pub fn get_named<R: Request>(&self, name: &str) -> InjectResult<R> {
+        let mut request_info = self.root_request_info.as_ref().clone();
+        request_info.set_provider_name(name);
+        self.get_with(&request_info)
+    }
+
+    /// Gets every implementation of a service or interface which was
+    /// registered with a tag. If no providers have the tag, then an empty
+    /// [`Vec`] is returned. See [`TypedProvider::with_tag()`] for more
+    /// information.
+    ///
+    /// [`TypedProvider::with_tag()`]: crate::TypedProvider::with_tag
+    pub fn get_tagged<I: ?Sized + Interface>(
+        &self,
+        tag: &str,
+    ) -> InjectResult<Vec<Svc<I>>> {
+        let mut services: Services<I> = match self.get() {
+            Ok(services) => services,
+            Err(InjectError::MissingProvider { .. }) => return Ok(Vec::new()),
+            Err(error) => return Err(error),
+        };
+
+        services.get_all_tagged(tag).collect()
+    }
+
+    /// Gets owned service pointers to every implementation of a service or
+    /// interface, skipping implementations whose providers can't provide
+    /// owned service pointers. This is similar to requesting
+    /// [`Vec<Box<I>>`], except that request fails if any provider doesn't
+    /// support owned service pointers, like a singleton.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     interface, Injector, IntoSingleton, IntoTransient, Service,
+    ///     TypedProvider,
+    /// };
+    ///
+    /// trait Plugin: Service {}
+    /// interface!(dyn Plugin = [SharedPlugin, OwnedPlugin]);
+    ///
+    /// #[derive(Default)]
+    /// struct SharedPlugin;
+    /// impl Plugin for SharedPlugin {}
+    ///
+    /// #[derive(Default)]
+    /// struct OwnedPlugin;
+    /// impl Plugin for OwnedPlugin {}
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(
+    ///     SharedPlugin::default
+    ///         .singleton()
+    ///         .with_interface::<dyn Plugin>(),
+    /// );
+    /// builder.provide(
+    ///     OwnedPlugin::default
+    ///         .transient()
+    ///         .with_interface::<dyn Plugin>(),
+    /// );
+    ///
+    /// let injector = builder.build();
+    /// assert!(injector.get::<Vec<Box<dyn Plugin>>>().is_err());
+    ///
+    /// let plugins = injector.get_all_owned_lenient::<dyn Plugin>().unwrap();
+    /// assert_eq!(1, plugins.len());
+    /// ```
+    pub fn get_all_owned_lenient<I: ?Sized + Interface>(
+        &self,
+    ) -> InjectResult<Vec<Box<I>>> {
+        let mut services: Services<I> = self.get()?;
+        services
+            .get_all_owned()
+            .filter(|result| {
+                !matches!(result, Err(InjectError::OwnedNotSupported { .. }))
+            })
+            .collect()
+    }
+
+    /// Creates a new injector which combines the providers of this injector
+    /// and another injector. For each service, if `other` has any providers
+    /// registered for it, then only those providers are used. Otherwise, the
+    /// providers from this injector are used. Named providers and request
+    /// parameters are combined in the same way, with those from `other`
+    /// taking precedence.
+    ///
+    /// This is useful for composing injectors which were built separately,
+    /// like a host application and its plugins. Rather than copying the
+    /// providers, the new injector borrows them from the original injectors
+    /// while they're being used. This means that singletons are shared
+    /// between the original injectors and the new one, and a singleton that
+    /// has already been created in either injector is reused by the overlay.
+    /// However, dependencies of services activated through the new injector
+    /// are always resolved through the new injector.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{constant, Injector, IntoTransient, Svc};
+    ///
+    /// struct Greeting(&'static str);
+    /// struct Greeter(Svc<Greeting>);
+    ///
+    /// let mut host = Injector::builder();
+    /// host.provide(constant(Greeting("hello")));
+    /// host.provide(Greeter.transient());
+    /// let host = host.build();
+    ///
+    /// let mut plugin = Injector::builder();
+    /// plugin.provide(constant(Greeting("howdy")));
+    /// let plugin = plugin.build();
+    ///
+    /// let injector = host.overlay(&plugin);
+    /// let greeter: Svc<Greeter> = injector.get().unwrap();
+    /// assert_eq!("howdy", greeter.0 .0);
+    /// ```
+    #[must_use]
+    pub fn overlay(&self, other: &Injector) -> Injector {
+        let mut providers = ProviderMap::new();
+        let mut named_providers = HashMap::<_, ProviderMap>::new();
+        for source in [self, other] {
+            BorrowedProvider::borrow_all(&source.provider_map, &mut providers);
+            for (name, provider_map) in source.named_providers.iter() {
+                BorrowedProvider::borrow_all(
+                    provider_map,
+                    named_providers.entry(name.clone()).or_default(),
+                );
             }
         }
+
+        let mut request_info = self.root_request_info.as_ref().clone();
+        for (key, value) in other.root_request_info.parameters() {
+            drop(request_info.insert_parameter_boxed(key, value.clone()));
+        }
+
+        let middleware = self
+            .middleware
+            .iter()
+            .chain(other.middleware.iter())
+            .cloned()
+            .collect();
+
+        Injector::new_from_parts(
+            providers,
+            named_providers,
+            request_info,
+            middleware,
+            DisposalQueue::new(),
+            self.max_depth.into_iter().chain(other.max_depth).min(),
+            None,
+            self.time_singletons || other.time_singletons,
+        )
+    }
+
+    /// Creates a builder for a child injector. The child injector uses the
+    /// providers registered in its builder, and falls back to the providers
+    /// of this injector for any services it doesn't register providers for.
+    /// The child also starts with this injector's request parameters and
+    /// middleware, as well as its maximum depth.
+    ///
+    /// Services provided by this injector are still activated by this
+    /// injector. This means that singletons are shared between this injector
+    /// and its children, and their dependencies are always resolved through
+    /// this injector, even if a child registers providers for them. Services
+    /// provided by the child are activated by the child, so their
+    /// dependencies are resolved through the child first. Singletons which
+    /// are only registered in the child are only shared by that child and
+    /// its scopes. Scoped services provided by this injector are stored in
+    /// the scope of the injector requesting them, so each scope of the child
+    /// has its own instances.
+    ///
+    /// Decorators, disposers, and usage tracking which are added to the
+    /// child's builder only apply to the child's own providers.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{constant, Injector, IntoSingleton, Svc};
+    ///
+    /// struct Greeting(&'static str);
+    /// struct Greeter(Svc<Greeting>);
+    /// struct PluginGreeter(Svc<Greeting>);
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(constant(Greeting("hello")));
+    /// builder.provide(Greeter.singleton());
+    /// let parent = builder.build();
+    ///
+    /// let mut builder = parent.child().unwrap();
+    /// builder.provide(constant(Greeting("howdy")));
+    /// builder.provide(PluginGreeter.singleton());
+    /// let child = builder.build();
+    ///
+    /// // The parent's services use the parent's greeting
+    /// let greeter: Svc<Greeter> = child.get().unwrap();
+    /// assert_eq!("hello", greeter.0 .0);
+    /// let parent_greeter: Svc<Greeter> = parent.get().unwrap();
+    /// assert!(Svc::ptr_eq(&greeter, &parent_greeter));
+    ///
+    /// // The child's services use the child's greeting
+    /// let plugin_greeter: Svc<PluginGreeter> = child.get().unwrap();
+    /// assert_eq!("howdy", plugin_greeter.0 .0);
+    /// assert!(parent.get::<Svc<PluginGreeter>>().is_err());
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`InjectError::Sealed`] if the injector has been sealed. See
+    /// [`Injector::seal()`] for more information.
+    pub fn child(&self) -> InjectResult<InjectorBuilder> {
+        self.check_sealed()?;
+        Ok(InjectorBuilder::child_of(
+            self.clone(),
+            self.root_request_info.as_ref().clone(),
+            self.middleware.as_ref().clone(),
+            self.max_depth,
+        ))
+    }
+
+    /// Adds providers to a child injector's providers for each service this
+    /// injector provides that the child doesn't. See [`Injector::child()`]
+    /// for more information.
+    pub(crate) fn inherit_providers(
+        &self,
+        providers: &mut ProviderMap,
+        named_providers: &mut HashMap<String, ProviderMap>,
+    ) {
+        fn inherit(
+            parent: &Injector,
+            provider_map: &MapContainer<ProviderMap>,
+            into: &mut ProviderMap,
+        ) {
+            for (service_info, borrowed) in
+                BorrowedProvider::borrow_each(provider_map, Some(parent))
+            {
+                let shadowed = into
+                    .get(&service_info)
+                    .and_then(Option::as_ref)
+                    .is_some_and(|providers| !providers.is_empty());
+                if !shadowed {
+                    drop(into.insert(service_info, Some(borrowed)));
+                }
+            }
+        }
+
+        inherit(self, &self.provider_map, providers);
+        for (name, provider_map) in self.named_providers.iter() {
+            inherit(
+                self,
+                provider_map,
+                named_providers.entry(name.clone()).or_default(),
+            );
+        }
+    }
+
+    /// Exports the dependency graph of this injector as a directed graph in
+    /// the [DOT language](https://graphviz.org/doc/info/lang.html), which
+    /// can be rendered with tools like Graphviz. This does not activate any
+    /// services.
+    ///
+    /// Each service is a node labeled with the lifetimes of the providers
+    /// registered for it (see [`Provider::lifetime()`]), and has an edge to
+    /// each of its dependencies (see [`Provider::dependencies()`]).
+    /// Interfaces are labeled as interfaces instead, and have an edge to
+    /// each of their implementations. Only providers registered without a
+    /// name are included, and providers which are currently activating a
+    /// service are skipped.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{Injector, IntoSingleton, IntoTransient, Svc};
+    ///
+    /// #[derive(Default)]
+    /// struct Database;
+    /// struct UserRepository(Svc<Database>);
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Database::default.singleton());
+    /// builder.provide(UserRepository.transient());
+    ///
+    /// let injector = builder.build();
+    /// let dot = injector.export_dot();
+    /// assert!(dot.starts_with("digraph {"));
+    /// assert!(dot.contains(r#"Database\n(singleton)""#));
+    /// assert!(dot.contains(r#"UserRepository\n(transient)""#));
+    /// ```
+    #[must_use]
+    pub fn export_dot(&self) -> String {
+        let mut nodes = BTreeMap::<_, BTreeSet<String>>::new();
+        let mut edges = BTreeSet::new();
+        self.provider_map.with_inner(|map| {
+            for provider in map.values().flatten().flatten() {
+                let result = provider.result().name();
+                let implementation = provider.implementation().name();
+                if result != implementation {
+                    let labels = nodes.entry(result).or_default();
+                    labels.insert("interface".to_owned());
+                    edges.insert((result, implementation));
+                }
+
+                let labels = nodes.entry(implementation).or_default();
+                labels.insert(provider.lifetime().to_string());
+                for dependency in provider.dependencies() {
+                    nodes.entry(dependency.name()).or_default();
+                    edges.insert((implementation, dependency.name()));
+                }
+            }
+        });
+
+        let nodes = nodes.into_iter().map(|(name, labels)| {
+            if labels.is_empty() {
+                format!("    {name:?};")
+            } else {
+                let labels: Vec<_> = labels.into_iter().collect();
+                let label = format!("{}\n({})", name, labels.join(", "));
+                format!("    {name:?} [label={label:?}];")
+            }
+        });
+        let edges = edges
+            .into_iter()
+            .map(|(from, to)| format!("    {from:?} -> {to:?};"));
+
+        let mut lines = vec!["digraph {".to_owned()];
+        lines.extend(nodes);
+        lines.extend(edges);
+        lines.push("}\n".to_owned());
+        lines.join("\n")
+    }
+
+    /// Gets each service which has providers registered for it, ordered by
+    /// name. This includes both concrete types and interfaces, which can be
+    /// told apart with [`Injector::is_interface()`]. Providers registered
+    /// under a name are not included. No services are activated.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{Injector, IntoSingleton, ServiceInfo};
+    ///
+    /// #[derive(Default)]
+    /// struct Foo;
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Foo::default.singleton());
+    ///
+    /// let injector = builder.build();
+    /// assert_eq!(vec![ServiceInfo::of::<Foo>()], injector.registered_services());
+    /// assert_eq!(1, injector.provider_count(ServiceInfo::of::<Foo>()));
+    /// ```
+    #[must_use]
+    pub fn registered_services(&self) -> Vec<ServiceInfo> {
+        let mut services: Vec<_> = self.provider_map.with_inner(|map| {
+            map.iter()
+                .filter(|(_, providers)| {
+                    providers
+                        .as_ref()
+                        .is_none_or(|providers| !providers.is_empty())
+                })
+                .map(|(service_info, _)| *service_info)
+                .collect()
+        });
+        services.sort_by_key(ServiceInfo::name);
+        services
+    }
+
+    /// Gets the number of providers registered for a service. Providers
+    /// which are being used by an active request, like providers held by an
+    /// instance of [`Services<T>`], are not counted. No services are
+    /// activated.
+    #[must_use]
+    pub fn provider_count(&self, service_info: ServiceInfo) -> usize {
+        self.provider_map.with_inner(|map| {
+            map.get(&service_info)
+                .and_then(Option::as_ref)
+                .map_or(0, Vec::len)
+        })
+    }
+
+    /// Checks whether a service is registered as an interface, meaning at
+    /// least one of its providers provides a different concrete type. No
+    /// services are activated.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     interface, Injector, IntoSingleton, Service, ServiceInfo,
+    ///     TypedProvider,
+    /// };
+    ///
+    /// trait Foo: Service {}
+    /// interface!(dyn Foo = [Bar]);
+    ///
+    /// #[derive(Default)]
+    /// struct Bar;
+    /// impl Foo for Bar {}
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Bar::default.singleton().with_interface::<dyn Foo>());
+    /// builder.provide(Bar::default.singleton());
+    ///
+    /// let injector = builder.build();
+    /// assert!(injector.is_interface(ServiceInfo::of::<dyn Foo>()));
+    /// assert!(!injector.is_interface(ServiceInfo::of::<Bar>()));
+    /// ```
+    #[must_use]
+    pub fn is_interface(&self, service_info: ServiceInfo) -> bool {
+        self.provider_map.with_inner(|map| {
+            map.get(&service_info).and_then(Option::as_ref).is_some_and(
+                |providers| {
+                    providers.iter().any(|provider| {
+                        provider.implementation() != service_info
+                    })
+                },
+            )
+        })
+    }
+
+    /// Describes each provider registered in the injector, including the
+    /// concrete type of its service, how long its instances live, and the
+    /// interface it is registered as, if any. Descriptions are ordered by the
+    /// name of the service the providers are registered for, then by the
+    /// order the providers were registered in. Providers registered under a
+    /// name are not included. No services are activated.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     interface, Injector, IntoSingleton, IntoTransient, Lifetime,
+    ///     Service, ServiceDescription, ServiceInfo, TypedProvider,
+    /// };
+    ///
+    /// trait Foo: Service {}
+    /// interface!(dyn Foo = [Bar]);
+    ///
+    /// #[derive(Default)]
+    /// struct Bar;
+    /// impl Foo for Bar {}
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Bar::default.singleton().with_interface::<dyn Foo>());
+    ///
+    /// let injector = builder.build();
+    /// assert_eq!(
+    ///     vec![ServiceDescription {
+    ///         service_info: ServiceInfo::of::<Bar>(),
+    ///         lifetime: Lifetime::Singleton,
+    ///         interface: Some(ServiceInfo::of::<dyn Foo>()),
+    ///     }],
+    ///     injector.describe(),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn describe(&self) -> Vec<ServiceDescription> {
+        let mut registrations: Vec<_> = self.provider_map.with_inner(|map| {
+            map.iter()
+                .map(|(&service_info, providers)| {
+                    let descriptions: Vec<_> = providers
+                        .iter()
+                        .flatten()
+                        .map(|provider| {
+                            let implementation = provider.implementation();
+                            ServiceDescription {
+                                service_info: implementation,
+                                lifetime: provider.lifetime(),
+                                interface: Some(service_info)
+                                    .filter(|&info| info != implementation),
+                            }
+                        })
+                        .collect();
+                    (service_info, descriptions)
+                })
+                .collect()
+        });
+
+        registrations.sort_by_key(|(service_info, _)| service_info.name());
+        registrations
+            .into_iter()
+            .flat_map(|(_, descriptions)| descriptions)
+            .collect()
+    }
+
+    /// Gets the concrete type of each implementation registered for a
+    /// service or interface, in descending order of priority and then in the
+    /// order they were registered. None of the providers are activated. This
+    /// can be used to check which implementations an interface is bound to,
+    /// for example by logging them when an application starts.
+    ///
+    /// Providers which are currently activating a service can't be inspected
+    /// and are skipped.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     interface, Injector, IntoSingleton, Service, ServiceInfo,
+    ///     TypedProvider,
+    /// };
+    ///
+    /// trait Cache: Service {}
+    /// interface!(dyn Cache = [MemoryCache, DiskCache]);
+    ///
+    /// #[derive(Default)]
+    /// struct MemoryCache;
+    /// impl Cache for MemoryCache {}
+    ///
+    /// #[derive(Default)]
+    /// struct DiskCache;
+    /// impl Cache for DiskCache {}
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(DiskCache::default.singleton().with_interface::<dyn Cache>());
+    /// builder.provide(MemoryCache::default.singleton().with_interface::<dyn Cache>());
+    ///
+    /// let injector = builder.build();
+    /// assert_eq!(
+    ///     vec![ServiceInfo::of::<DiskCache>(), ServiceInfo::of::<MemoryCache>()],
+    ///     injector.implementations_of::<dyn Cache>(),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn implementations_of<I: ?Sized + Interface>(
+        &self,
+    ) -> Vec<ServiceInfo> {
+        self.provider_map.with_inner(|map| {
+            map.get(&ServiceInfo::of::<I>())
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|provider| provider.implementation())
+                .collect()
+        })
+    }
+
+    /// Gets the concrete type, name, tags, and lifetime of each provider
+    /// registered for a service or interface. Providers registered without a
+    /// name are listed first, in descending order of priority and then in the
+    /// order they were registered, followed by providers registered with
+    /// [`InjectorBuilder::provide_named()`], sorted by name. None of the
+    /// providers are activated.
+    ///
+    /// Providers which are currently activating a service can't be inspected
+    /// and are skipped.
+    ///
+    /// [`InjectorBuilder::provide_named()`]: crate::InjectorBuilder::provide_named
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     interface, Injector, IntoSingleton, IntoTransient, Lifetime,
+    ///     ProviderMeta, Service, ServiceInfo, TypedProvider,
+    /// };
+    ///
+    /// trait Cache: Service {}
+    /// interface!(dyn Cache = [MemoryCache, DiskCache]);
+    ///
+    /// #[derive(Default)]
+    /// struct MemoryCache;
+    /// impl Cache for MemoryCache {}
+    ///
+    /// #[derive(Default)]
+    /// struct DiskCache;
+    /// impl Cache for DiskCache {}
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(
+    ///     MemoryCache::default
+    ///         .singleton()
+    ///         .with_tag("fast")
+    ///         .with_interface::<dyn Cache>(),
+    /// );
+    /// builder.provide_named(
+    ///     "disk",
+    ///     DiskCache::default.transient().with_interface::<dyn Cache>(),
+    /// );
+    ///
+    /// let injector = builder.build();
+    /// assert_eq!(
+    ///     vec![
+    ///         ProviderMeta {
+    ///             service_info: ServiceInfo::of::<MemoryCache>(),
+    ///             name: None,
+    ///             tags: vec!["fast".to_owned()],
+    ///             lifetime: Lifetime::Singleton,
+    ///         },
+    ///         ProviderMeta {
+    ///             service_info: ServiceInfo::of::<DiskCache>(),
+    ///             name: Some("disk".to_owned()),
+    ///             tags: Vec::new(),
+    ///             lifetime: Lifetime::Transient,
+    ///         },
+    ///     ],
+    ///     injector.provider_metadata::<dyn Cache>(),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn provider_metadata<I: ?Sized + Interface>(
+        &self,
+    ) -> Vec<ProviderMeta> {
+        fn describe(
+            provider_map: &ProviderMap,
+            service_info: ServiceInfo,
+            name: Option<&str>,
+        ) -> Vec<ProviderMeta> {
+            provider_map
+                .get(&service_info)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|provider| ProviderMeta {
+                    service_info: provider.implementation(),
+                    name: name.or_else(|| provider.name()).map(Into::into),
+                    tags: provider.tags().into_iter().map(Into::into).collect(),
+                    lifetime: provider.lifetime(),
+                })
+                .collect()
+        }
+
+        let service_info = ServiceInfo::of::<I>();
+        let mut metadata = self
+            .provider_map
+            .with_inner(|map| describe(map, service_info, None));
+
+        let mut names: Vec<_> = self.named_providers.keys().collect();
+        names.sort();
+        for name in names {
+            let provider_map = &self.named_providers[name];
+            metadata.extend(
+                provider_map
+                    .with_inner(|map| describe(map, service_info, Some(name))),
+            );
+        }
+
+        metadata
+    }
+
+    /// Gets the services which have a provider that has never activated an
+    /// instance of them, sorted by name. Services which were activated as a
+    /// dependency of another service count as used. Usage is only tracked if
+    /// it was enabled with [`InjectorBuilder::track_unused()`], so this is
+    /// always empty otherwise.
+    ///
+    /// Scopes share usage with the injector they were created from. Overlays
+    /// don't track usage, but services activated through an overlay count as
+    /// used by the injector which registered them.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{Injector, IntoSingleton, ServiceInfo, Svc};
+    ///
+    /// #[derive(Default)]
+    /// struct Foo;
+    /// struct Bar(Svc<Foo>);
+    /// #[derive(Default)]
+    /// struct Baz;
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Foo::default.singleton());
+    /// builder.provide(Bar.singleton());
+    /// builder.provide(Baz::default.singleton());
+    /// builder.track_unused();
+    ///
+    /// let injector = builder.build();
+    /// let _bar: Svc<Bar> = injector.get().unwrap();
+    /// assert_eq!(vec![ServiceInfo::of::<Baz>()], injector.unused_providers());
+    /// ```
+    #[must_use]
+    pub fn unused_providers(&self) -> Vec<ServiceInfo> {
+        self.usage
+            .as_ref()
+            .map(|usage| usage.unused())
+            .unwrap_or_default()
+    }
+
+    /// Gets how long each singleton took to be created, by the concrete type
+    /// of the singleton. Only singletons which have been activated are
+    /// included, and their creation is only timed if it was enabled with
+    /// [`InjectorBuilder::time_singletons()`], so this is always empty
+    /// otherwise. If a type is provided by several singletons, then their
+    /// build times are added together.
+    ///
+    /// Each build time includes the time spent creating the singleton's
+    /// dependencies, so the build times of a singleton and its dependencies
+    /// may overlap.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{Injector, IntoSingleton, ServiceInfo, Svc};
+    ///
+    /// #[derive(Default)]
+    /// struct Foo;
+    /// #[derive(Default)]
+    /// struct Bar;
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Foo::default.singleton());
+    /// builder.provide(Bar::default.singleton());
+    /// builder.time_singletons();
+    ///
+    /// let injector = builder.build();
+    /// let _foo: Svc<Foo> = injector.get().unwrap();
+    ///
+    /// let build_times = injector.singleton_build_times();
+    /// assert!(build_times.contains_key(&ServiceInfo::of::<Foo>()));
+    /// assert!(!build_times.contains_key(&ServiceInfo::of::<Bar>()));
+    /// ```
+    #[must_use]
+    pub fn singleton_build_times(&self) -> HashMap<ServiceInfo, Duration> {
+        fn collect(
+            provider_map: &ProviderMap,
+            build_times: &mut HashMap<ServiceInfo, Duration>,
+        ) {
+            for provider in provider_map.values().flatten().flatten() {
+                if let Some(build_time) = provider.build_time() {
+                    *build_times
+                        .entry(provider.implementation())
+                        .or_default() += build_time;
+                }
+            }
+        }
+
+        let mut build_times = HashMap::new();
+        self.provider_map
+            .with_inner(|map| collect(map, &mut build_times));
+        for provider_map in self.named_providers.values() {
+            provider_map.with_inner(|map| collect(map, &mut build_times));
+        }
+
+        build_times
+    }
+
+    /// Whether singletons activated by this injector should be timed. See
+    /// [`InjectorBuilder::time_singletons()`] for more information.
+    pub(crate) fn times_singletons(&self) -> bool {
+        self.time_singletons
+    }
+
+    /// Runs the middleware which applies to a request, then performs the
+    /// request.
+    fn with_middleware<R>(
+        &self,
+        request_info: &RequestInfo,
+        mut request: impl FnMut(&RequestInfo) -> InjectResult<R>,
+    ) -> InjectResult<R> {
+        let chain: Vec<_> = self
+            .middleware
+            .iter()
+            .filter(|middleware| middleware.applies_to(request_info))
+            .collect();
+        if chain.is_empty() {
+            return request(request_info);
+        }
+
+        let mut request_info = request_info.clone();
+        request_info.set_nested();
+
+        let mut result = None;
+        let mut continued = false;
+        MiddlewareEntry::run_chain(&chain, &mut request_info, &mut |info| {
+            continued = true;
+            result = Some(request(info)?);
+            Ok(())
+        })?;
+
+        // Middleware which doesn't fail the request must continue it, but a
+        // request which already failed isn't performed again
+        match result {
+            Some(result) => Ok(result),
+            None if continued => Err(InjectError::SuppressedByMiddleware),
+            None => request(&request_info),
+        }
+    }
+
+    /// Requests exactly one instance of a service which is only known at
+    /// runtime. See [`Injector::get_dyn()`] for more information.
+    fn request_dyn(
+        &self,
+        service_info: ServiceInfo,
+        request_info: &RequestInfo,
+    ) -> InjectResult<DynSvc> {
+        // The interface type is never used since the services aren't
+        // downcast
+        struct Untyped;
+
+        request_info.trace_step(service_info);
+        let depth = request_info.depth();
+        if self.max_depth.is_some_and(|max_depth| depth > max_depth) {
+            return Err(InjectError::MaxDepthExceeded {
+                service_info,
+                depth,
+            });
+        }
+
+        if request_info.is_cancelled() {
+            return Err(InjectError::Cancelled { service_info });
+        }
+
+        let provider_map = self.provider_map_for(service_info, request_info)?;
+        let mut services = Services::<Untyped>::new_for(
+            self.clone(),
+            provider_map,
+            request_info.clone(),
+            service_info,
+        )?;
+        let candidates = services.highest_priority_count();
+        if candidates > 1 {
+            return Err(InjectError::MultipleProviders {
+                service_info,
+                providers: candidates,
+            });
+        }
+
+        let mut services = services.get_highest_priority_dyn().into_iter();
+        let service = services
+            .next()
+            .transpose()?
+            .ok_or(InjectError::MissingProvider { service_info })?;
+
+        // A single provider may still provide multiple services
+        let remaining = services.count();
+        if remaining > 0 {
+            return Err(InjectError::MultipleProviders {
+                service_info,
+                providers: remaining + 1,
+            });
+        }
+
+        Ok(service)
+    }
+
+    /// Gets the names which providers have been registered under. See
+    /// [`InjectorBuilder::provide_named()`] for more information.
+    pub(crate) fn provider_names(&self) -> impl Iterator<Item = &str> {
+        self.named_providers.keys().map(String::as_str)
+    }
+
+    /// Gets implementations of a service from the container. This is
+    /// equivalent to requesting [`Services<T>`] from [`Injector::get()`].
+    pub(crate) fn get_service<I: ?Sized + Interface>(
+        &self,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Services<I>> {
+        request_info.trace_step(ServiceInfo::of::<I>());
+        let depth = request_info.depth();
+        if self.max_depth.is_some_and(|max_depth| depth > max_depth) {
+            return Err(InjectError::MaxDepthExceeded {
+                service_info: ServiceInfo::of::<I>(),
+                depth,
+            });
+        }
+
+        if request_info.is_cancelled() {
+            return Err(InjectError::Cancelled {
+                service_info: ServiceInfo::of::<I>(),
+            });
+        }
+
+        let provider_map =
+            self.provider_map_for(ServiceInfo::of::<I>(), request_info)?;
+        Services::new(self.clone(), provider_map, request_info.clone())
+    }
+
+    /// Counts the providers which would be used to request a service without
+    /// activating any of them. See [`Count<T>`](crate::Count) for more
+    /// information.
+    pub(crate) fn count_providers(
+        &self,
+        service_info: ServiceInfo,
+        request_info: &RequestInfo,
+    ) -> InjectResult<usize> {
+        if request_info
+            .overrides()
+            .is_some_and(|overrides| overrides.contains(service_info))
+        {
+            return Ok(1);
+        }
+
+        let provider_map =
+            match self.provider_map_for(service_info, request_info) {
+                Ok(provider_map) => provider_map,
+                Err(InjectError::MissingProvider { .. }) => return Ok(0),
+                Err(error) => return Err(error),
+            };
+
+        provider_map.with_inner(|provider_map| {
+            match provider_map.get(&service_info) {
+                None => Ok(0),
+                Some(Some(providers)) => Ok(providers.len()),
+                Some(None) => Err(InjectError::CycleDetected {
+                    service_info,
+                    cycle: vec![service_info],
+                }),
+            }
+        })
+    }
+
+    /// Gets the providers which should be used to request a service.
+    fn provider_map_for(
+        &self,
+        service_info: ServiceInfo,
+        request_info: &RequestInfo,
+    ) -> InjectResult<MapContainer<ProviderMap>> {
+        match request_info.provider_name() {
+            Some(name) => [&self.named_providers, &self.named_aliases]
+                .iter()
+                .filter_map(|provider_maps| provider_maps.get(name))
+                .find(|provider_map| {
+                    provider_map.with_inner(|provider_map| {
+                        provider_map.contains_key(&service_info)
+                    })
+                })
+                .cloned()
+                .ok_or(InjectError::MissingProvider { service_info }),
+            None => Ok(self.provider_map.clone()),
+        }
+    }
+}
+
+/// A provider which activates a provider owned by another injector. The
+/// borrowed providers are taken out of their injector while in use, the same
+/// way [`Services<I>`] does, so cycles are still detected.
+struct BorrowedProvider {
+    provider_map: MapContainer<ProviderMap>,
+    service_info: ServiceInfo,
+    index: usize,
+    name: Option<String>,
+    tags: Vec<String>,
+    priority: i32,
+    // The injector which owns the provider, if services should be activated
+    // by it rather than by the injector making the request
+    owner: Option<Injector>,
+}
+
+impl BorrowedProvider {
+    /// Borrows each provider in a provider map. For each service, the
+    /// borrowed providers replace any providers already in `into`.
+    fn borrow_all(
+        provider_map: &MapContainer<ProviderMap>,
+        into: &mut ProviderMap,
+    ) {
+        for (service_info, borrowed) in
+            BorrowedProvider::borrow_each(provider_map, None)
+        {
+            drop(into.insert(service_info, Some(borrowed)));
+        }
+    }
+
+    /// Borrows each provider in a provider map, grouped by the service they
+    /// provide.
+    fn borrow_each(
+        provider_map: &MapContainer<ProviderMap>,
+        owner: Option<&Injector>,
+    ) -> Vec<(ServiceInfo, Vec<Box<dyn Provider>>)> {
+        let registrations: Vec<_> = provider_map.with_inner(|map| {
+            map.iter()
+                .map(|(&service_info, providers)| {
+                    let metadata: Vec<_> = providers
+                        .iter()
+                        .flatten()
+                        .map(|provider| {
+                            let name = provider.name().map(ToOwned::to_owned);
+                            let tags = provider
+                                .tags()
+                                .into_iter()
+                                .map(ToOwned::to_owned)
+                                .collect();
+                            (name, tags, provider.priority())
+                        })
+                        .collect();
+                    (service_info, metadata)
+                })
+                .collect()
+        });
+
+        registrations
+            .into_iter()
+            .map(|(service_info, metadata)| {
+                let borrowed = metadata
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, (name, tags, priority))| {
+                        Box::new(BorrowedProvider {
+                            provider_map: provider_map.clone(),
+                            service_info,
+                            index,
+                            name,
+                            tags,
+                            priority,
+                            owner: owner.cloned(),
+                        }) as Box<dyn Provider>
+                    })
+                    .collect();
+                (service_info, borrowed)
+            })
+            .collect()
+    }
+
+    /// Borrows each provider in a provider map which was given a name (see
+    /// [`TypedProvider::with_name()`]), grouped by their names.
+    ///
+    /// [`TypedProvider::with_name()`]: crate::TypedProvider::with_name
+    fn borrow_named(
+        provider_map: &MapContainer<ProviderMap>,
+    ) -> HashMap<String, ProviderMap> {
+        let registrations: Vec<_> = provider_map.with_inner(|map| {
+            map.iter()
+                .flat_map(|(&service_info, providers)| {
+                    providers.iter().flatten().enumerate().filter_map(
+                        move |(index, provider)| {
+                            let name = provider.name()?.to_owned();
+                            let tags = provider
+                                .tags()
+                                .into_iter()
+                                .map(ToOwned::to_owned)
+                                .collect();
+                            let priority = provider.priority();
+                            Some((service_info, index, name, tags, priority))
+                        },
+                    )
+                })
+                .collect()
+        });
+
+        let mut named = HashMap::<_, ProviderMap>::new();
+        for (service_info, index, name, tags, priority) in registrations {
+            let borrowed = Box::new(BorrowedProvider {
+                provider_map: provider_map.clone(),
+                service_info,
+                index,
+                name: Some(name.clone()),
+                tags,
+                priority,
+                owner: None,
+            });
+
+            // Should never panic
+            named
+                .entry(name)
+                .or_default()
+                .entry(service_info)
+                .or_insert_with(|| Some(Vec::new()))
+                .as_mut()
+                .unwrap()
+                .push(borrowed);
+        }
+
+        named
+    }
+
+    fn with_provider<R>(
+        &self,
+        f: impl FnOnce(&mut Box<dyn Provider>) -> InjectResult<R>,
+    ) -> InjectResult<R> {
+        let service_info = self.service_info;
+        let mut providers = self.provider_map.with_inner_mut(|map| {
+            map.get_mut(&service_info)
+                .ok_or(InjectError::MissingProvider { service_info })?
+                .take()
+                .ok_or_else(|| InjectError::CycleDetected {
+                    service_info,
+                    cycle: vec![service_info],
+                })
+        })?;
+
+        let result = match providers.get_mut(self.index) {
+            Some(provider) => f(provider),
+            None => Err(InjectError::MissingProvider { service_info }),
+        };
+
+        self.provider_map.with_inner_mut(|map| {
+            drop(map.insert(service_info, Some(providers)));
+        });
+        result
+    }
+}
+
+impl BorrowedProvider {
+    /// Gets the injector which should activate this provider's services, if
+    /// it isn't the injector making the request. The owner's providers are
+    /// used, but scoped services are stored in the requesting injector's
+    /// scope.
+    fn owner_for(&self, injector: &Injector) -> Option<Injector> {
+        self.owner.as_ref().map(|owner| Injector {
+            scope: injector.scope.clone(),
+            ..owner.clone()
+        })
+    }
+}
+
+impl Provider for BorrowedProvider {
+    fn result(&self) -> ServiceInfo {
+        self.service_info
+    }
+
+    fn provide(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<DynSvc> {
+        let owner = self.owner_for(injector);
+        let injector = owner.as_ref().unwrap_or(injector);
+        self.with_provider(|provider| provider.provide(injector, request_info))
+    }
+
+    fn provide_owned(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<OwnedDynSvc> {
+        let owner = self.owner_for(injector);
+        let injector = owner.as_ref().unwrap_or(injector);
+        self.with_provider(|provider| {
+            provider.provide_owned(injector, request_info)
+        })
+    }
+
+    fn provide_all(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Vec<DynSvc>> {
+        let owner = self.owner_for(injector);
+        let injector = owner.as_ref().unwrap_or(injector);
+        self.with_provider(|provider| {
+            provider.provide_all(injector, request_info)
+        })
+    }
+
+    fn strong_count(&self) -> Option<usize> {
+        self.provider_map.with_inner(|map| {
+            map.get(&self.service_info)?
+                .as_ref()?
+                .get(self.index)?
+                .strong_count()
+        })
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        self.tags.iter().map(String::as_str).collect()
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    fn build_time(&self) -> Option<Duration> {
+        self.provider_map.with_inner(|map| {
+            map.get(&self.service_info)?
+                .as_ref()?
+                .get(self.index)?
+                .build_time()
+        })
+    }
+
+    fn dependencies(&self) -> Vec<ServiceInfo> {
+        self.provider_map
+            .with_inner(|map| {
+                map.get(&self.service_info)?
+                    .as_ref()?
+                    .get(self.index)
+                    .map(|provider| provider.dependencies())
+            })
+            .unwrap_or_default()
+    }
+
+    fn is_available(
+        &self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> bool {
+        // The provider is taken out of the map since its condition may make
+        // requests to the injector
+        let owner = self.owner_for(injector);
+        let injector = owner.as_ref().unwrap_or(injector);
+        self.with_provider(|provider| {
+            Ok(provider.is_available(injector, request_info))
+        })
+        .unwrap_or(true)
+    }
+
+    fn lifetime(&self) -> Lifetime {
+        self.provider_map
+            .with_inner(|map| {
+                map.get(&self.service_info)?
+                    .as_ref()?
+                    .get(self.index)
+                    .map(|provider| provider.lifetime())
+            })
+            .unwrap_or(Lifetime::Custom)
+    }
+
+    fn implementation(&self) -> ServiceInfo {
+        self.provider_map
+            .with_inner(|map| {
+                map.get(&self.service_info)?
+                    .as_ref()?
+                    .get(self.index)
+                    .map(|provider| provider.implementation())
+            })
+            .unwrap_or(self.service_info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        constant, DynSvc, InjectError, InjectResult, Injector, IntoSingleton,
+        IntoTransient, Provider, RequestInfo, ServiceInfo, Svc,
+    };
+    use core::panic;
+    use std::sync::Mutex;
+
+    #[test]
+    #[allow(clippy::unnecessary_literal_unwrap)]
+    fn get_exact_returns_error_on_invalid_provider() {
+        struct BadProvider;
+        impl Provider for BadProvider {
+            fn result(&self) -> ServiceInfo {
+                ServiceInfo::of::<i32>()
+            }
+
+            fn provide(
+                &mut self,
+                _injector: &Injector,
+                _request_info: &RequestInfo,
+            ) -> InjectResult<DynSvc> {
+                Ok(Svc::new(1.2_f32))
+            }
+        }
+
+        let mut builder = Injector::builder();
+        builder.provide(BadProvider);
+
+        let injector = builder.build();
+        let bad: InjectResult<Svc<i32>> = injector.get();
+
+        match bad {
+            Err(InjectError::InvalidProvider { service_info })
+                if service_info == ServiceInfo::of::<i32>() => {}
+            Err(error) => Err(error).unwrap(),
+            Ok(value) => {
+                panic!("Value of {} was provided by an invalid provider", value)
+            }
+        }
+    }
+
+    #[test]
+    fn overlay_prefers_other_and_shares_singletons() {
+        #[derive(Default)]
+        struct Counter(Mutex<i32>);
+        struct Foo(i32);
+
+        let mut builder = Injector::builder();
+        builder.provide(Counter::default.singleton());
+        builder.provide(
+            (|counter: Svc<Counter>| {
+                let mut count = counter.0.lock().unwrap();
+                *count += 1;
+                Foo(*count)
+            })
+            .transient(),
+        );
+        let base = builder.build();
+
+        let mut builder = Injector::builder();
+        builder.provide(constant(Foo(-1)));
+        let other = builder.build();
+
+        let overlay = base.overlay(&other);
+        let foo: Svc<Foo> = overlay.get().unwrap();
+        assert_eq!(-1, foo.0);
+
+        let overlay = other.overlay(&base);
+        let foo: Svc<Foo> = overlay.get().unwrap();
+        assert_eq!(1, foo.0);
+        let foo: Svc<Foo> = base.get().unwrap();
+        assert_eq!(2, foo.0);
     }
 }
@@ -1,6 +1,7 @@
 use crate::{
-    InjectError, InjectResult, Injector, Interface, MapContainer,
-    MapContainerEx, Provider, ProviderMap, RequestInfo, ServiceInfo, Svc,
+    downcast_owned_service, downcast_service, DynSvc, InjectError,
+    InjectResult, Injector, Interface, MapContainer, MapContainerEx, Provider,
+    ProviderMap, RequestInfo, ServiceInfo, Svc,
 };
 use std::{marker::PhantomData, slice::IterMut};
 
@@ -54,6 +55,7 @@ pub struct Services<I: ?Sized + Interface> {
     request_info: RequestInfo,
     provider_map: MapContainer<ProviderMap>,
     providers: Option<Vec<Box<dyn Provider>>>,
+    from_map: bool,
     marker: PhantomData<fn() -> I>,
 }
 
@@ -63,7 +65,39 @@ impl<I: ?Sized + Interface> Services<I> {
         provider_map: MapContainer<ProviderMap>,
         request_info: RequestInfo,
     ) -> InjectResult<Self> {
-        let service_info = ServiceInfo::of::<I>();
+        Services::new_for(
+            injector,
+            provider_map,
+            request_info,
+            ServiceInfo::of::<I>(),
+        )
+    }
+
+    /// Takes the providers for a service which may not be the interface
+    /// type. This is used for services which are only known at runtime, in
+    /// which case only the methods which don't downcast the services should
+    /// be used.
+    pub(crate) fn new_for(
+        injector: Injector,
+        provider_map: MapContainer<ProviderMap>,
+        request_info: RequestInfo,
+        service_info: ServiceInfo,
+    ) -> InjectResult<Self> {
+        if let Some(provider) = request_info
+            .overrides()
+            .and_then(|overrides| overrides.provider_for(service_info))
+        {
+            return Ok(Services {
+                injector,
+                service_info,
+                request_info,
+                provider_map,
+                providers: Some(vec![provider]),
+                from_map: false,
+                marker: PhantomData,
+            });
+        }
+
         let providers = provider_map.with_inner_mut(|provider_map| {
             let providers = provider_map
                 .get_mut(&service_info)
@@ -81,6 +115,7 @@ impl<I: ?Sized + Interface> Services<I> {
             request_info,
             provider_map,
             providers: Some(providers),
+            from_map: true,
             marker: PhantomData,
         })
     }
@@ -91,6 +126,23 @@ impl<I: ?Sized + Interface> Services<I> {
     pub fn get_all(&mut self) -> ServicesIter<'_, I> {
         ServicesIter {
             provider_iter: self.providers.as_mut().unwrap().iter_mut(), /* Should never panic */
+            pending: Vec::new().into_iter(),
+            injector: &self.injector,
+            request_info: &self.request_info,
+            marker: PhantomData,
+        }
+    }
+
+    /// Lazily gets all the implementations of this interface, along with the
+    /// concrete type of each implementation. Each service will be requested
+    /// on demand rather than all at once. The [`ServiceInfo`] is the type
+    /// registered with the provider, even if the service was requested
+    /// through an interface.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn iter_with_info(&mut self) -> ServicesWithInfoIter<'_, I> {
+        ServicesWithInfoIter {
+            provider_iter: self.providers.as_mut().unwrap().iter_mut(), /* Should never panic */
+            pending: None,
             injector: &self.injector,
             request_info: &self.request_info,
             marker: PhantomData,
@@ -111,6 +163,282 @@ impl<I: ?Sized + Interface> Services<I> {
         }
     }
 
+    /// Activates the first implementation of this interface, ignoring the
+    /// rest. Implementations are ordered by the priority of their providers,
+    /// then by when their providers were registered, so this prefers the
+    /// implementation with the highest priority that was registered first.
+    /// Providers whose conditions aren't met are skipped. Returns `None` if
+    /// no implementations are provided.
+    ///
+    /// Unlike requesting a [`Svc<T>`], this does not fail if there are
+    /// multiple implementations.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     Injector, IntoSingleton, Services, Svc, WithCondition,
+    /// };
+    ///
+    /// struct Backend(&'static str);
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide((|| Backend("gpu")).singleton().with_condition(|_, _| false));
+    /// builder.provide((|| Backend("cpu")).singleton());
+    /// builder.provide((|| Backend("fallback")).singleton());
+    ///
+    /// let injector = builder.build();
+    /// let mut backends: Services<Backend> = injector.get().unwrap();
+    /// let backend: Svc<Backend> = backends.first().unwrap().unwrap();
+    /// assert_eq!("cpu", backend.0);
+    /// ```
+    pub fn first(&mut self) -> Option<InjectResult<Svc<I>>> {
+        self.get_all().next()
+    }
+
+    /// Activates the first implementation of this interface as an owned
+    /// service pointer, ignoring the rest. See [`Services::first()`] for more
+    /// information. Not all providers can provide owned service pointers, so
+    /// this may fail where [`Services::first()`] would succeed.
+    pub fn first_owned(&mut self) -> Option<InjectResult<Box<I>>> {
+        self.get_all_owned().next()
+    }
+
+    /// Gets the number of providers which share the highest priority. The
+    /// providers for a service are sorted by descending priority when the
+    /// injector is built, so these are the first providers. See
+    /// [`TypedProvider::with_priority()`](crate::TypedProvider::with_priority)
+    /// for more information.
+    #[allow(clippy::missing_panics_doc)]
+    pub(crate) fn highest_priority_count(&self) -> usize {
+        // Should never panic
+        let providers = self.providers.as_ref().unwrap();
+        match providers.first() {
+            Some(first) => {
+                let priority = first.priority();
+                providers
+                    .iter()
+                    .take_while(|provider| provider.priority() == priority)
+                    .count()
+            }
+            None => 0,
+        }
+    }
+
+    /// Lazily gets the implementations provided by the providers with the
+    /// highest priority. See [`Services::highest_priority_count()`] for more
+    /// information.
+    #[allow(clippy::missing_panics_doc)]
+    pub(crate) fn get_highest_priority(&mut self) -> ServicesIter<'_, I> {
+        let count = self.highest_priority_count();
+        ServicesIter {
+            provider_iter: self.providers.as_mut().unwrap()[..count].iter_mut(), /* Should never panic */
+            pending: Vec::new().into_iter(),
+            injector: &self.injector,
+            request_info: &self.request_info,
+            marker: PhantomData,
+        }
+    }
+
+    /// Lazily gets the implementations provided by the providers with the
+    /// highest priority as owned service pointers. See
+    /// [`Services::highest_priority_count()`] for more information.
+    #[allow(clippy::missing_panics_doc)]
+    pub(crate) fn get_highest_priority_owned(
+        &mut self,
+    ) -> OwnedServicesIter<'_, I> {
+        let count = self.highest_priority_count();
+        OwnedServicesIter {
+            provider_iter: self.providers.as_mut().unwrap()[..count].iter_mut(), /* Should never panic */
+            injector: &self.injector,
+            request_info: &self.request_info,
+            marker: PhantomData,
+        }
+    }
+
+    /// Activates the single implementation with the highest priority, like a
+    /// [`Svc<T>`] request does. This fails if there isn't exactly one such
+    /// implementation.
+    pub(crate) fn get_single(&mut self) -> InjectResult<Svc<I>> {
+        let candidates = self.highest_priority_count();
+        if candidates > 1 {
+            return Err(InjectError::MultipleProviders {
+                service_info: ServiceInfo::of::<I>(),
+                providers: candidates,
+            });
+        }
+
+        let mut services = self.get_highest_priority();
+        let service = services.next().transpose()?.ok_or(
+            InjectError::MissingProvider {
+                service_info: ServiceInfo::of::<I>(),
+            },
+        )?;
+
+        // A single provider may still provide multiple services
+        let remaining = services.count();
+        if remaining > 0 {
+            return Err(InjectError::MultipleProviders {
+                service_info: ServiceInfo::of::<I>(),
+                providers: remaining + 1,
+            });
+        }
+
+        Ok(service)
+    }
+
+    /// Activates all the implementations of this interface, stopping at the
+    /// first one which fails to activate. This is equivalent to collecting
+    /// [`Services::get_all()`] into a [`Vec<T>`], except the vector is
+    /// allocated with enough capacity for one implementation from each
+    /// provider up front.
+    ///
+    /// ## Errors
+    ///
+    /// Returns the error from the first implementation which fails to
+    /// activate. The implementations after it are not activated.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{Injector, IntoSingleton, Services, Svc};
+    ///
+    /// struct Plugin(&'static str);
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide((|| Plugin("auth")).singleton());
+    /// builder.provide((|| Plugin("metrics")).singleton());
+    ///
+    /// let injector = builder.build();
+    /// let mut plugins: Services<Plugin> = injector.get().unwrap();
+    /// let plugins: Vec<Svc<Plugin>> = plugins.try_all().unwrap();
+    /// assert_eq!(2, plugins.len());
+    /// ```
+    pub fn try_all(&mut self) -> InjectResult<Vec<Svc<I>>> {
+        let mut results = Vec::with_capacity(self.len());
+        for result in self.get_all() {
+            results.push(result?);
+        }
+
+        Ok(results)
+    }
+
+    /// Activates all the implementations of this interface as owned service
+    /// pointers, stopping at the first one which fails to activate. See
+    /// [`Services::try_all()`] for more information.
+    ///
+    /// ## Errors
+    ///
+    /// Returns the error from the first implementation which fails to
+    /// activate, including implementations whose providers can't provide
+    /// owned service pointers.
+    pub fn try_all_owned(&mut self) -> InjectResult<Vec<Box<I>>> {
+        let mut results = Vec::with_capacity(self.len());
+        for result in self.get_all_owned() {
+            results.push(result?);
+        }
+
+        Ok(results)
+    }
+
+    /// Gets all the implementations of this interface without downcasting
+    /// them to the interface type.
+    #[cfg(feature = "test-util")]
+    pub(crate) fn get_all_dyn(&mut self) -> Vec<InjectResult<DynSvc>> {
+        self.get_first_dyn(self.len())
+    }
+
+    /// Gets the implementations provided by the providers with the highest
+    /// priority without downcasting them. See
+    /// [`Services::highest_priority_count()`] for more information.
+    pub(crate) fn get_highest_priority_dyn(
+        &mut self,
+    ) -> Vec<InjectResult<DynSvc>> {
+        self.get_first_dyn(self.highest_priority_count())
+    }
+
+    /// Gets the implementations provided by the first `count` providers
+    /// without downcasting them.
+    #[allow(clippy::missing_panics_doc)]
+    fn get_first_dyn(&mut self, count: usize) -> Vec<InjectResult<DynSvc>> {
+        let mut results = Vec::new();
+        // Should never panic
+        for provider in &mut self.providers.as_mut().unwrap()[..count] {
+            match provider.provide_all(&self.injector, &self.request_info) {
+                Ok(services) => results.extend(services.into_iter().map(Ok)),
+                Err(error) => results
+                    .extend(trace_error(self.service_info, error).map(Err)),
+            }
+        }
+
+        results
+    }
+
+    /// Lazily gets all the named implementations of this interface, along
+    /// with their names. Providers which were not given a name are skipped.
+    /// See [`TypedProvider::with_name()`](crate::TypedProvider::with_name)
+    /// for more information.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn get_all_named(&mut self) -> NamedServicesIter<'_, I> {
+        NamedServicesIter {
+            provider_iter: self.providers.as_mut().unwrap().iter_mut(), /* Should never panic */
+            injector: &self.injector,
+            request_info: &self.request_info,
+            marker: PhantomData,
+        }
+    }
+
+    /// Lazily gets all the implementations of this interface which were
+    /// registered with a tag. Providers without the tag are skipped. See
+    /// [`TypedProvider::with_tag()`](crate::TypedProvider::with_tag) for
+    /// more information.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn get_all_tagged<'a>(
+        &'a mut self,
+        tag: &'a str,
+    ) -> TaggedServicesIter<'a, I> {
+        TaggedServicesIter {
+            provider_iter: self.providers.as_mut().unwrap().iter_mut(), /* Should never panic */
+            pending: Vec::new().into_iter(),
+            tag,
+            injector: &self.injector,
+            request_info: &self.request_info,
+            marker: PhantomData,
+        }
+    }
+
+    /// Gets all the named implementations of this interface as owned service
+    /// pointers, along with their names. Providers which were not given a
+    /// name are skipped.
+    #[allow(clippy::missing_panics_doc)]
+    pub(crate) fn get_all_named_owned(
+        &mut self,
+    ) -> Vec<(String, InjectResult<Box<I>>)> {
+        let mut results = Vec::new();
+        // Should never panic
+        for provider in self.providers.as_mut().unwrap() {
+            let name = match provider.name() {
+                Some(name) => name.to_owned(),
+                None => continue,
+            };
+
+            match provider.provide_owned(&self.injector, &self.request_info) {
+                Ok(result) => {
+                    results.push((name, downcast_owned_service(result)));
+                }
+                Err(error) => {
+                    if let Some(error) =
+                        trace_error(ServiceInfo::of::<I>(), error)
+                    {
+                        results.push((name, Err(error)));
+                    }
+                }
+            }
+        }
+
+        results
+    }
+
     /// Gets the max number of possible implementations of this interface. This
     /// does not take into account conditional providers, which may not return
     /// an implementation of the service.
@@ -121,6 +449,64 @@ impl<I: ?Sized + Interface> Services<I> {
         self.providers.as_ref().unwrap().len()
     }
 
+    /// Gets the number of implementations of this interface which would
+    /// actually be provided. Unlike [`Services::len()`], this skips
+    /// conditional providers whose conditions aren't met and providers which
+    /// always fail, like those registered with
+    /// [`InjectorBuilder::provide_error()`]. None of the providers are
+    /// activated. Providers which can't tell whether they'll provide their
+    /// service without activating it are counted. See
+    /// [`Provider::is_available()`] for more information.
+    ///
+    /// [`InjectorBuilder::provide_error()`]: crate::InjectorBuilder::provide_error
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     Injector, IntoSingleton, Services, WithCondition,
+    /// };
+    ///
+    /// #[derive(Default)]
+    /// struct Feature;
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Feature::default.singleton());
+    /// builder.provide(Feature::default.singleton().with_condition(|_, _| false));
+    ///
+    /// let injector = builder.build();
+    /// let features: Services<Feature> = injector.get().unwrap();
+    /// assert_eq!(2, features.len());
+    /// assert_eq!(1, features.count_available());
+    /// ```
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn count_available(&self) -> usize {
+        // Should never panic
+        self.providers
+            .as_ref()
+            .unwrap()
+            .iter()
+            .filter(|provider| {
+                provider.is_available(&self.injector, &self.request_info)
+            })
+            .count()
+    }
+
+    /// Gets the number of strong references to the instance stored by each
+    /// provider. See [`Provider::strong_count()`] for more information.
+    #[allow(clippy::missing_panics_doc)]
+    pub(crate) fn strong_counts(
+        &self,
+    ) -> impl Iterator<Item = Option<usize>> + '_ {
+        // Should never panic
+        self.providers
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|provider| provider.strong_count())
+    }
+
     /// Returns `true` if there are no possible implementations of this
     /// interface. This does not take into account conditional providers, which
     /// may not return an implementation of the service.
@@ -138,8 +524,14 @@ impl<I: ?Sized + Interface> Drop for Services<I> {
             ref service_info,
             ref mut provider_map,
             ref mut providers,
+            from_map,
             ..
-        } = self;
+        } = *self;
+
+        // Overridden services are not tracked by the provider map
+        if !from_map {
+            return;
+        }
 
         let result = provider_map.with_inner_mut(|provider_map| {
             let provider_entry =
@@ -204,41 +596,120 @@ impl<I: ?Sized + Interface> Drop for Services<I> {
 /// ```
 pub struct ServicesIter<'a, I: ?Sized + Interface> {
     provider_iter: IterMut<'a, Box<dyn Provider>>,
+    pending: std::vec::IntoIter<DynSvc>,
     injector: &'a Injector,
     request_info: &'a RequestInfo,
     marker: PhantomData<fn() -> I>,
 }
 
-impl<'a, I: ?Sized + Interface> Iterator for ServicesIter<'a, I> {
+impl<I: ?Sized + Interface> Iterator for ServicesIter<'_, I> {
     type Item = InjectResult<Svc<I>>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let ServicesIter {
-            provider_iter,
-            injector,
-            request_info,
-            ..
-        } = self;
+        loop {
+            if let Some(service) = self.pending.next() {
+                return Some(downcast_service(service));
+            }
 
-        provider_iter.find_map(|provider| {
-            match provider.provide(injector, request_info) {
-                Ok(result) => Some(I::downcast(result)),
-                Err(InjectError::ConditionsNotMet { .. }) => None,
-                Err(InjectError::CycleDetected { mut cycle, .. }) => {
-                    let service_info = ServiceInfo::of::<I>();
-                    cycle.push(service_info);
-                    Some(Err(InjectError::CycleDetected {
-                        service_info,
-                        cycle,
-                    }))
+            let provider = self.provider_iter.next()?;
+            match provider.provide_all(self.injector, self.request_info) {
+                Ok(services) => self.pending = services.into_iter(),
+                Err(error) => {
+                    if let Some(error) =
+                        trace_error(ServiceInfo::of::<I>(), error)
+                    {
+                        return Some(Err(error));
+                    }
                 }
-                Err(error) => Some(Err(error)),
             }
-        })
+        }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (0, Some(self.provider_iter.len()))
+        // Providers may create any number of services
+        (self.pending.len(), None)
+    }
+}
+
+/// An iterator over all the implementations of an interface and their
+/// concrete types. Each service is activated on demand.
+///
+/// ```
+/// use runtime_injector::{
+///     interface, Injector, IntoSingleton, Service, ServiceInfo, Services,
+///     Svc, TypedProvider,
+/// };
+/// use std::collections::HashMap;
+///
+/// trait Plugin: Service {}
+/// interface!(dyn Plugin = [Foo, Bar]);
+///
+/// #[derive(Default)]
+/// struct Foo;
+/// impl Plugin for Foo {}
+///
+/// #[derive(Default)]
+/// struct Bar;
+/// impl Plugin for Bar {}
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(Foo::default.singleton().with_interface::<dyn Plugin>());
+/// builder.provide(Bar::default.singleton().with_interface::<dyn Plugin>());
+///
+/// let injector = builder.build();
+/// let mut plugins: Services<dyn Plugin> = injector.get().unwrap();
+/// let plugins: HashMap<ServiceInfo, Svc<dyn Plugin>> =
+///     plugins.iter_with_info().collect::<Result<_, _>>().unwrap();
+/// assert!(plugins.contains_key(&ServiceInfo::of::<Foo>()));
+/// assert!(plugins.contains_key(&ServiceInfo::of::<Bar>()));
+/// ```
+pub struct ServicesWithInfoIter<'a, I: ?Sized + Interface> {
+    provider_iter: IterMut<'a, Box<dyn Provider>>,
+    pending: Option<(ServiceInfo, std::vec::IntoIter<DynSvc>)>,
+    injector: &'a Injector,
+    request_info: &'a RequestInfo,
+    marker: PhantomData<fn() -> I>,
+}
+
+impl<I: ?Sized + Interface> Iterator for ServicesWithInfoIter<'_, I> {
+    type Item = InjectResult<(ServiceInfo, Svc<I>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((service_info, pending)) = &mut self.pending {
+                if let Some(service) = pending.next() {
+                    let service_info = *service_info;
+                    return Some(
+                        downcast_service(service)
+                            .map(|service| (service_info, service)),
+                    );
+                }
+            }
+
+            let provider = self.provider_iter.next()?;
+            match provider.provide_all(self.injector, self.request_info) {
+                Ok(services) => {
+                    self.pending =
+                        Some((provider.implementation(), services.into_iter()));
+                }
+                Err(error) => {
+                    if let Some(error) =
+                        trace_error(ServiceInfo::of::<I>(), error)
+                    {
+                        return Some(Err(error));
+                    }
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Providers may create any number of services
+        let pending = self
+            .pending
+            .as_ref()
+            .map_or(0, |(_, pending)| pending.len());
+        (pending, None)
     }
 }
 
@@ -280,7 +751,7 @@ pub struct OwnedServicesIter<'a, I: ?Sized + Interface> {
     marker: PhantomData<fn() -> I>,
 }
 
-impl<'a, I: ?Sized + Interface> Iterator for OwnedServicesIter<'a, I> {
+impl<I: ?Sized + Interface> Iterator for OwnedServicesIter<'_, I> {
     type Item = InjectResult<Box<I>>;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -293,17 +764,10 @@ impl<'a, I: ?Sized + Interface> Iterator for OwnedServicesIter<'a, I> {
 
         provider_iter.find_map(|provider| {
             match provider.provide_owned(injector, request_info) {
-                Ok(result) => Some(I::downcast_owned(result)),
-                Err(InjectError::ConditionsNotMet { .. }) => None,
-                Err(InjectError::CycleDetected { mut cycle, .. }) => {
-                    let service_info = ServiceInfo::of::<I>();
-                    cycle.push(service_info);
-                    Some(Err(InjectError::CycleDetected {
-                        service_info,
-                        cycle,
-                    }))
+                Ok(result) => Some(downcast_owned_service(result)),
+                Err(error) => {
+                    trace_error(ServiceInfo::of::<I>(), error).map(Err)
                 }
-                Err(error) => Some(Err(error)),
             }
         })
     }
@@ -312,3 +776,147 @@ impl<'a, I: ?Sized + Interface> Iterator for OwnedServicesIter<'a, I> {
         (0, Some(self.provider_iter.len()))
     }
 }
+
+/// An iterator over all the named implementations of an interface and their
+/// names. Each service is activated on demand. Providers which were not given
+/// a name are skipped.
+///
+/// ```
+/// use runtime_injector::{
+///     Injector, IntoTransient, Services, Svc, TypedProvider,
+/// };
+///
+/// #[derive(Default)]
+/// struct Foo;
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(Foo::default.transient().with_name("foo"));
+/// builder.provide(Foo::default.transient());
+///
+/// let injector = builder.build();
+/// let mut foos: Services<Foo> = injector.get().unwrap();
+/// let names: Vec<_> = foos.get_all_named().map(|(name, _)| name).collect();
+/// assert_eq!(vec!["foo".to_owned()], names);
+/// ```
+pub struct NamedServicesIter<'a, I: ?Sized + Interface> {
+    provider_iter: IterMut<'a, Box<dyn Provider>>,
+    injector: &'a Injector,
+    request_info: &'a RequestInfo,
+    marker: PhantomData<fn() -> I>,
+}
+
+impl<I: ?Sized + Interface> Iterator for NamedServicesIter<'_, I> {
+    type Item = (String, InjectResult<Svc<I>>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let NamedServicesIter {
+            provider_iter,
+            injector,
+            request_info,
+            ..
+        } = self;
+
+        provider_iter.find_map(|provider| {
+            let name = provider.name()?.to_owned();
+            let result = activate::<I>(provider, injector, request_info)?;
+            Some((name, result))
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.provider_iter.len()))
+    }
+}
+
+/// An iterator over all the implementations of an interface which were
+/// registered with a tag. Each service is activated on demand. Providers
+/// without the tag are skipped.
+///
+/// ```
+/// use runtime_injector::{
+///     Injector, IntoTransient, Services, Svc, TypedProvider,
+/// };
+///
+/// #[derive(Default)]
+/// struct Foo;
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(Foo::default.transient().with_tag("startup"));
+/// builder.provide(Foo::default.transient());
+///
+/// let injector = builder.build();
+/// let mut foos: Services<Foo> = injector.get().unwrap();
+/// assert_eq!(1, foos.get_all_tagged("startup").count());
+/// ```
+pub struct TaggedServicesIter<'a, I: ?Sized + Interface> {
+    provider_iter: IterMut<'a, Box<dyn Provider>>,
+    pending: std::vec::IntoIter<DynSvc>,
+    tag: &'a str,
+    injector: &'a Injector,
+    request_info: &'a RequestInfo,
+    marker: PhantomData<fn() -> I>,
+}
+
+impl<I: ?Sized + Interface> Iterator for TaggedServicesIter<'_, I> {
+    type Item = InjectResult<Svc<I>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(service) = self.pending.next() {
+                return Some(downcast_service(service));
+            }
+
+            let provider = self.provider_iter.next()?;
+            if !provider.tags().contains(&self.tag) {
+                continue;
+            }
+
+            match provider.provide_all(self.injector, self.request_info) {
+                Ok(services) => self.pending = services.into_iter(),
+                Err(error) => {
+                    if let Some(error) =
+                        trace_error(ServiceInfo::of::<I>(), error)
+                    {
+                        return Some(Err(error));
+                    }
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Providers may create any number of services
+        (self.pending.len(), None)
+    }
+}
+
+/// Activates a provider, returning `None` if the provider should be skipped.
+fn activate<I: ?Sized + Interface>(
+    provider: &mut Box<dyn Provider>,
+    injector: &Injector,
+    request_info: &RequestInfo,
+) -> Option<InjectResult<Svc<I>>> {
+    match provider.provide(injector, request_info) {
+        Ok(result) => Some(downcast_service(result)),
+        Err(error) => trace_error(ServiceInfo::of::<I>(), error).map(Err),
+    }
+}
+
+/// Adds the requested service to a detected cycle, or returns `None` if the
+/// provider should be skipped.
+fn trace_error(
+    service_info: ServiceInfo,
+    error: InjectError,
+) -> Option<InjectError> {
+    match error {
+        InjectError::ConditionsNotMet { .. } => None,
+        InjectError::CycleDetected { mut cycle, .. } => {
+            cycle.push(service_info);
+            Some(InjectError::CycleDetected {
+                service_info,
+                cycle,
+            })
+        }
+        error => Some(error),
+    }
+}
@@ -56,7 +56,7 @@
 //! ## Service lifetimes
 //!
 //! Lifetimes of services created by the [`Injector`] are controlled by the
-//! [`Provider`] used to construct those lifetimes. Currently, there are three
+//! [`Provider`] used to construct those lifetimes. Currently, there are four
 //! built-in service provider types:
 //!
 //! - **[Transient](crate::TransientProvider):** A service is created each time
@@ -65,6 +65,10 @@
 //! - **[Singleton](crate::SingletonProvider):** A service is created only the
 //!   first time it is requested, then that single instance is reused for each
 //!   future request.
+//! - **[Scoped](crate::ScopedProvider):** A service is created only the first
+//!   time it is requested within a scope, then that instance is reused for
+//!   each future request within the same scope. New scopes can be created
+//!   with [`Injector::create_scope()`].
 //! - **[Constant](crate::ConstantProvider):** Used for services that are not
 //!   created using a service factory and instead can have their instance
 //!   provided to the container directly. This behaves similar to singleton in
@@ -239,7 +243,8 @@
     clippy::missing_errors_doc,
     clippy::doc_markdown,
     clippy::needless_doctest_main,
-    clippy::needless_pass_by_value
+    clippy::needless_pass_by_value,
+    clippy::test_attr_in_doctest
 )]
 
 #[cfg(not(any(feature = "arc", feature = "rc")))]
@@ -257,6 +262,7 @@ mod builder;
 mod injector;
 mod iter;
 mod module;
+mod registry;
 mod requests;
 mod services;
 
@@ -265,9 +271,18 @@ pub use builder::*;
 pub use injector::*;
 pub use iter::*;
 pub use module::*;
+pub use registry::*;
 pub use requests::*;
 pub use services::*;
 
+#[cfg(feature = "test-util")]
+mod test_util;
+#[cfg(feature = "test-util")]
+pub use test_util::*;
+
+#[cfg(feature = "derive")]
+pub use runtime_injector_derive::Inject;
+
 pub mod docs;
 
 #[cfg(test)]
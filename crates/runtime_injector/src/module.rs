@@ -1,5 +1,11 @@
-use crate::{Provider, ProviderMap, RequestParameter};
-use std::collections::HashMap;
+use crate::{
+    constant, insert_provider, merge_modules, AsAny, DefaultSingletonProvider,
+    DefaultTransientProvider, ErrorProvider, InjectResult, Injector, Interface,
+    InterfaceFor, IntoSingleton, IntoTransient, ManyProvider, Provider,
+    ProviderMap, Registration, RequestInfo, RequestParameter, RequiredArg,
+    Service, TypedProvider,
+};
+use std::{collections::HashMap, error::Error};
 
 /// A collection of providers that can be added all at once to an
 /// [`InjectorBuilder`](crate::InjectorBuilder). Modules can be used to group
@@ -11,21 +17,103 @@ use std::collections::HashMap;
 #[derive(Default)]
 pub struct Module {
     pub(crate) providers: ProviderMap,
+    pub(crate) named_providers: HashMap<String, ProviderMap>,
     pub(crate) parameters: HashMap<String, Box<dyn RequestParameter>>,
+    pub(crate) required_args: Vec<RequiredArg>,
 }
 
 impl Module {
     /// Assigns the provider for a service type. Multiple providers can be
     /// registered for a service.
-    #[allow(clippy::missing_panics_doc)]
     pub fn provide<P: Provider>(&mut self, provider: P) {
-        // Should never panic
-        self.providers
-            .entry(provider.result())
-            .or_insert_with(|| Some(Vec::new()))
-            .as_mut()
-            .unwrap()
-            .push(Box::new(provider));
+        self.add_provider(Box::new(provider));
+    }
+
+    /// Adds a provider to the module.
+    pub fn add_provider(&mut self, provider: Box<dyn Provider>) {
+        insert_provider(&mut self.providers, provider);
+    }
+
+    /// Assigns a provider for a service type under a name. See
+    /// [`InjectorBuilder::provide_named()`](crate::InjectorBuilder::provide_named)
+    /// for more information.
+    pub fn provide_named<P: Provider>(
+        &mut self,
+        name: impl Into<String>,
+        provider: P,
+    ) {
+        self.add_named_provider(name, Box::new(provider));
+    }
+
+    /// Adds a provider to the module under a name.
+    pub fn add_named_provider(
+        &mut self,
+        name: impl Into<String>,
+        provider: Box<dyn Provider>,
+    ) {
+        let providers = self.named_providers.entry(name.into()).or_default();
+        insert_provider(providers, provider);
+    }
+
+    /// Registers a singleton service which is created with its [`Default`]
+    /// implementation. See
+    /// [`InjectorBuilder::provide_default()`](crate::InjectorBuilder::provide_default)
+    /// for more information.
+    pub fn provide_default<T: Service + Default>(
+        &mut self,
+    ) -> Registration<'_, DefaultSingletonProvider<T>> {
+        let factory: fn() -> T = T::default;
+        Registration::new(&mut self.providers, factory.singleton())
+    }
+
+    /// Registers a transient service which is created with its [`Default`]
+    /// implementation. See
+    /// [`InjectorBuilder::provide_default()`](crate::InjectorBuilder::provide_default)
+    /// for more information.
+    pub fn provide_default_transient<T: Service + Default>(
+        &mut self,
+    ) -> Registration<'_, DefaultTransientProvider<T>> {
+        let factory: fn() -> T = T::default;
+        Registration::new(&mut self.providers, factory.transient())
+    }
+
+    /// Registers a do-nothing implementation of an interface which is only
+    /// used if no other implementation is registered for it. See
+    /// [`InjectorBuilder::provide_null()`](crate::InjectorBuilder::provide_null)
+    /// for more information.
+    pub fn provide_null<I, T>(&mut self, service: T)
+    where
+        I: ?Sized + InterfaceFor<T>,
+        T: Service,
+    {
+        self.provide(
+            constant(service)
+                .with_priority(i32::MIN)
+                .with_interface::<I>(),
+        );
+    }
+
+    /// Registers a factory which creates several instances of a service at
+    /// once. A new batch of instances is created each time the service is
+    /// requested. See [`ManyProvider`] for more information, including how to
+    /// create the batch only once.
+    pub fn provide_many<R, F>(&mut self, factory: F)
+    where
+        R: Service,
+        F: Service + Fn(&Injector, &RequestInfo) -> InjectResult<Vec<R>>,
+    {
+        self.provide(ManyProvider::transient(factory));
+    }
+
+    /// Registers a provider for a service which always fails with the error
+    /// returned by the given factory. See [`ErrorProvider`] for more
+    /// information.
+    pub fn provide_error<I, F>(&mut self, factory: F)
+    where
+        I: ?Sized + Interface,
+        F: Service + Fn() -> Box<dyn Error>,
+    {
+        self.provide(ErrorProvider::<I, F>::new(factory));
     }
 
     /// Sets the of a value request parameter for requests made by the injector
@@ -46,6 +134,84 @@ impl Module {
     ) -> Option<Box<dyn RequestParameter>> {
         self.parameters.remove(key)
     }
+
+    /// Declares that an [`Arg<T>`](crate::Arg) must be provided for the
+    /// service `S`. This does not provide a value for the argument. Instead,
+    /// [`InjectorBuilder::validate()`](crate::InjectorBuilder::validate)
+    /// reports an error if no value has been provided for it once this module
+    /// is added to the builder.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     Arg, InjectError, Injector, IntoSingleton, Module, WithArg,
+    /// };
+    ///
+    /// struct DbService(Arg<String>);
+    ///
+    /// let mut module = Module::default();
+    /// module.provide(DbService.singleton());
+    /// module.require_arg::<DbService, String>();
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.add_module(module);
+    /// match builder.validate() {
+    ///     Err(errors) => assert!(matches!(
+    ///         errors.as_slice(),
+    ///         [InjectError::MissingArgument { .. }]
+    ///     )),
+    ///     Ok(_) => unreachable!("the argument was never provided"),
+    /// }
+    ///
+    /// builder.with_arg::<DbService, String>("db://localhost".to_owned());
+    /// assert!(builder.build_checked().is_ok());
+    /// ```
+    pub fn require_arg<S: Service, T: Service + AsAny + Clone>(&mut self) {
+        let required_arg = RequiredArg::of::<S, T>();
+        if !self.required_args.contains(&required_arg) {
+            self.required_args.push(required_arg);
+        }
+    }
+
+    /// Gets the arguments which are required by this module.
+    #[must_use]
+    pub fn required_args(&self) -> &[RequiredArg] {
+        &self.required_args
+    }
+
+    /// Moves the providers, parameters, and required arguments from another
+    /// module into this one. Providers are appended to any providers already
+    /// registered for the same service, the same way as
+    /// [`InjectorBuilder::add_module()`](crate::InjectorBuilder::add_module).
+    /// If both modules set the same parameter, the value from `other` is
+    /// kept.
+    ///
+    /// ```
+    /// use runtime_injector::{Injector, IntoSingleton, Module, Svc};
+    ///
+    /// #[derive(Default)]
+    /// struct Foo;
+    /// struct Bar(Svc<Foo>);
+    ///
+    /// let mut module = Module::default();
+    /// module.provide(Foo::default.singleton());
+    /// module.insert_parameter("name", "first".to_owned());
+    ///
+    /// let mut other = Module::default();
+    /// other.provide(Bar.singleton());
+    /// other.insert_parameter("name", "second".to_owned());
+    /// module.merge(other);
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.add_module(module);
+    ///
+    /// let injector = builder.build();
+    /// let _bar: Svc<Bar> = injector.get().unwrap();
+    /// ```
+    pub fn merge(&mut self, other: Module) {
+        merge_modules(self, other);
+    }
 }
 
 /// Defines a new module using a domain specific language.
@@ -101,6 +267,13 @@ impl Module {
 ///     },
 /// };
 ///
+/// // Other modules can be included as well. Their providers are added after
+/// // the ones already defined, and their parameters override any earlier
+/// // parameters with the same key.
+/// let module = define_module! {
+///     include = [module],
+/// };
+///
 /// let mut builder = Injector::builder();
 /// builder.add_module(module);
 ///
@@ -140,6 +313,15 @@ macro_rules! define_module {
     ) => {
         $($module.provide($service);)*
     };
+    (
+        @provide $module:expr,
+        include = [
+            $($included:expr),*
+            $(,)?
+        ]
+    ) => {
+        $($crate::Module::merge($module, $included);)*
+    };
     (
         @provide $module:expr,
         interfaces = {
@@ -0,0 +1,171 @@
+use crate::{
+    builder::insert_provider, InterfaceFor, Provider, ProviderMap, Service,
+    ServiceInfo, SingletonProvider, TransientProvider, TypedProvider,
+};
+use std::collections::HashMap;
+
+/// The providers registered with an [`InjectorBuilder`], as seen by a build
+/// hook. See [`InjectorBuilder::add_build_hook()`] for more information.
+///
+/// [`InjectorBuilder`]: crate::InjectorBuilder
+/// [`InjectorBuilder::add_build_hook()`]: crate::InjectorBuilder::add_build_hook
+pub struct ProviderRegistry<'a> {
+    providers: &'a mut ProviderMap,
+    named_providers: &'a mut HashMap<String, ProviderMap>,
+}
+
+impl<'a> ProviderRegistry<'a> {
+    pub(crate) fn new(
+        providers: &'a mut ProviderMap,
+        named_providers: &'a mut HashMap<String, ProviderMap>,
+    ) -> Self {
+        ProviderRegistry {
+            providers,
+            named_providers,
+        }
+    }
+
+    /// Gets the services and interfaces which have at least one provider
+    /// registered for them. Providers registered under a name are not
+    /// included.
+    #[must_use]
+    pub fn services(&self) -> Vec<ServiceInfo> {
+        self.providers
+            .iter()
+            .filter(|(_, providers)| {
+                providers.as_ref().is_some_and(|p| !p.is_empty())
+            })
+            .map(|(&service_info, _)| service_info)
+            .collect()
+    }
+
+    /// Registers an additional provider. Any aliases of the provider are
+    /// registered as well.
+    pub fn provide<P: Provider>(&mut self, provider: P) {
+        insert_provider(self.providers, Box::new(provider));
+    }
+
+    /// Removes all providers for a service type, returning them. Providers
+    /// registered under a name are not removed.
+    pub fn remove(
+        &mut self,
+        service_info: ServiceInfo,
+    ) -> Vec<Box<dyn Provider>> {
+        self.providers
+            .remove(&service_info)
+            .flatten()
+            .unwrap_or_default()
+    }
+
+    /// Replaces each registered provider, including named providers, with
+    /// the provider returned by a callback. The returned provider must
+    /// provide the same service as the provider it replaces, which is
+    /// usually done by wrapping it.
+    pub fn rewrite<F>(&mut self, mut rewrite: F)
+    where
+        F: FnMut(Box<dyn Provider>) -> Box<dyn Provider>,
+    {
+        let named = self.named_providers.values_mut();
+        for provider_map in std::iter::once(&mut *self.providers).chain(named) {
+            for providers in provider_map.values_mut().flatten() {
+                *providers = std::mem::take(providers)
+                    .into_iter()
+                    .map(&mut rewrite)
+                    .collect();
+            }
+        }
+    }
+}
+
+/// A callback which rewrites the providers of an injector before it is built.
+/// See [`InjectorBuilder::add_build_hook()`] for more information.
+///
+/// [`InjectorBuilder::add_build_hook()`]: crate::InjectorBuilder::add_build_hook
+trait BuildCallback: Service {
+    fn call(self: Box<Self>, registry: &mut ProviderRegistry<'_>);
+}
+
+impl<F> BuildCallback for F
+where
+    F: Service + FnOnce(&mut ProviderRegistry<'_>),
+{
+    fn call(self: Box<Self>, registry: &mut ProviderRegistry<'_>) {
+        (*self)(registry);
+    }
+}
+
+/// A hook which is run once just before an injector is built.
+pub(crate) struct BuildHook {
+    callback: Box<dyn BuildCallback>,
+}
+
+impl BuildHook {
+    pub fn new<F>(callback: F) -> Self
+    where
+        F: Service + FnOnce(&mut ProviderRegistry<'_>),
+    {
+        BuildHook {
+            callback: Box::new(callback),
+        }
+    }
+
+    /// Runs the hook against the providers of an injector.
+    pub fn run(self, registry: &mut ProviderRegistry<'_>) {
+        self.callback.call(registry);
+    }
+}
+
+/// A singleton provider for a service which is created with its [`Default`]
+/// implementation. See [`InjectorBuilder::provide_default()`] for more
+/// information.
+///
+/// [`InjectorBuilder::provide_default()`]: crate::InjectorBuilder::provide_default
+pub type DefaultSingletonProvider<T> = SingletonProvider<(), T, fn() -> T>;
+
+/// A transient provider for a service which is created with its [`Default`]
+/// implementation. See [`InjectorBuilder::provide_default_transient()`] for
+/// more information.
+///
+/// [`InjectorBuilder::provide_default_transient()`]: crate::InjectorBuilder::provide_default_transient
+pub type DefaultTransientProvider<T> = TransientProvider<(), T, fn() -> T>;
+
+/// A provider which is registered once this is dropped, unless it is
+/// registered differently first. This is returned by methods which register a
+/// provider on the caller's behalf, like
+/// [`InjectorBuilder::provide_default()`], so the provider can still be
+/// configured afterwards.
+///
+/// [`InjectorBuilder::provide_default()`]: crate::InjectorBuilder::provide_default
+pub struct Registration<'a, P: TypedProvider> {
+    providers: &'a mut ProviderMap,
+    provider: Option<P>,
+}
+
+impl<'a, P: TypedProvider> Registration<'a, P> {
+    pub(crate) fn new(providers: &'a mut ProviderMap, provider: P) -> Self {
+        Registration {
+            providers,
+            provider: Some(provider),
+        }
+    }
+
+    /// Registers the provider as an implementation of an interface instead.
+    /// See [`TypedProvider::with_interface()`] for more information.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn with_interface<I: ?Sized + InterfaceFor<P::Result>>(mut self) {
+        // Should never panic
+        let provider = self.provider.take().unwrap();
+        insert_provider(
+            self.providers,
+            Box::new(provider.with_interface::<I>()),
+        );
+    }
+}
+
+impl<P: TypedProvider> Drop for Registration<'_, P> {
+    fn drop(&mut self) {
+        if let Some(provider) = self.provider.take() {
+            insert_provider(self.providers, Box::new(provider));
+        }
+    }
+}
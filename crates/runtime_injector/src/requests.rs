@@ -1,11 +1,36 @@
 mod arg;
+mod cancellation;
+mod count;
 mod factory;
+mod features;
 mod info;
+mod inject;
+mod lazy;
+mod locked;
+mod middleware;
+mod one_of;
+mod overrides;
+mod owned;
 mod parameter;
+mod pointer;
+mod ref_count;
 mod request;
+mod weak;
 
 pub use arg::*;
+pub use cancellation::*;
+pub use count::*;
 pub use factory::*;
+pub use features::*;
 pub use info::*;
+pub use inject::*;
+pub use lazy::*;
+pub use locked::*;
+pub use middleware::*;
+pub use overrides::*;
+pub use owned::*;
 pub use parameter::*;
+pub use pointer::*;
+pub use ref_count::*;
 pub use request::*;
+pub use weak::*;
@@ -1,11 +1,13 @@
 use crate::{
     AsAny, InjectError, InjectResult, Injector, InjectorBuilder, Module,
-    Request, RequestInfo, RequestParameter, Service, ServiceInfo,
+    Request, RequestInfo, RequestParameter, Service, ServiceInfo, Svc,
 };
 use std::{
+    env::VarError,
     error::Error,
     fmt::{Debug, Display, Formatter},
     ops::{Deref, DerefMut},
+    str::FromStr,
 };
 
 /// Allows custom pre-defined values to be passed as arguments to services.
@@ -29,11 +31,21 @@ pub struct Arg<T: Service + AsAny + Clone>(T);
 
 impl<T: Service + AsAny + Clone> Arg<T> {
     pub(crate) fn param_name(target: ServiceInfo) -> String {
-        format!(
-            "runtime_injector::Arg[target={:?},type={:?}]",
-            target.id(),
-            ServiceInfo::of::<T>().id()
-        )
+        arg_param_name(target, ServiceInfo::of::<T>())
+    }
+
+    pub(crate) fn default_param_name(target: ServiceInfo) -> String {
+        arg_default_param_name(target, ServiceInfo::of::<T>())
+    }
+
+    fn type_mismatch(found: ServiceInfo) -> InjectError {
+        InjectError::ActivationFailed {
+            service_info: ServiceInfo::of::<Self>(),
+            inner: Box::new(ArgRequestError::ParameterTypeMismatch {
+                expected: ServiceInfo::of::<T>(),
+                found,
+            }),
+        }
     }
 
     /// Converts an argument into its inner value.
@@ -59,29 +71,133 @@ impl<T: Service + AsAny + Clone> DerefMut for Arg<T> {
 /// Allows custom pre-defined values to be passed as arguments to services.
 impl<T: Service + AsAny + Clone> Request for Arg<T> {
     fn request(_injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
-        let parent_request = info.service_path().last().ok_or_else(|| {
+        let parent_request = info.parent_service().ok_or_else(|| {
             InjectError::ActivationFailed {
                 service_info: ServiceInfo::of::<Self>(),
                 inner: Box::new(ArgRequestError::NoParentRequest),
             }
         })?;
 
-        let request_name = Self::param_name(*parent_request);
-        let param = info.get_parameter(&request_name).ok_or_else(|| {
-            InjectError::ActivationFailed {
+        let request_name = Self::param_name(parent_request);
+        if let Some(param) = info.get_parameter(&request_name) {
+            let param: &T = param
+                .downcast_ref()
+                .ok_or_else(|| Self::type_mismatch(param.service_info()))?;
+
+            return Ok(Arg(param.clone()));
+        }
+
+        // Fall back to the default value if there is one
+        let default_name = Self::default_param_name(parent_request);
+        if let Some(default) = info
+            .get_parameter(&default_name)
+            .and_then(|param| param.downcast_ref::<ArgDefault<T>>())
+        {
+            return Ok(Arg(default.factory.create()));
+        }
+
+        // Check if a value of a different type was provided instead
+        let prefix = arg_param_prefix(parent_request);
+        let found = info
+            .parameters()
+            .iter()
+            .find(|(name, _)| name.starts_with(&prefix))
+            .map(|(_, param)| param.as_ref().service_info());
+        Err(match found {
+            Some(found) => Self::type_mismatch(found),
+            None => InjectError::ActivationFailed {
                 service_info: ServiceInfo::of::<Self>(),
                 inner: Box::new(ArgRequestError::MissingParameter),
-            }
-        })?;
+            },
+        })
+    }
+}
 
-        let param: &T = param.downcast_ref().ok_or_else(|| {
-            InjectError::ActivationFailed {
-                service_info: ServiceInfo::of::<Self>(),
-                inner: Box::new(ArgRequestError::ParameterTypeInvalid),
-            }
-        })?;
+fn arg_param_prefix(target: ServiceInfo) -> String {
+    format!("runtime_injector::Arg[target={:?},", target.id())
+}
+
+fn arg_param_name(target: ServiceInfo, arg_info: ServiceInfo) -> String {
+    format!("{}type={:?}]", arg_param_prefix(target), arg_info.id())
+}
+
+fn arg_default_param_name(
+    target: ServiceInfo,
+    arg_info: ServiceInfo,
+) -> String {
+    format!(
+        "runtime_injector::ArgDefault[target={:?},type={:?}]",
+        target.id(),
+        arg_info.id()
+    )
+}
+
+/// Creates the default value of an argument.
+trait ArgFactory<T>: Service {
+    fn create(&self) -> T;
+}
+
+impl<T, F> ArgFactory<T> for F
+where
+    F: Service + Fn() -> T,
+{
+    fn create(&self) -> T {
+        self()
+    }
+}
+
+/// A request parameter holding the factory for the default value of an
+/// [`Arg<T>`].
+struct ArgDefault<T: Service> {
+    factory: Svc<dyn ArgFactory<T>>,
+}
+
+impl<T: Service> Clone for ArgDefault<T> {
+    fn clone(&self) -> Self {
+        ArgDefault {
+            factory: self.factory.clone(),
+        }
+    }
+}
+
+/// An argument which must be provided for a service. Required arguments are
+/// declared with [`Module::require_arg()`] or
+/// [`InjectorBuilder::require_arg()`], and are checked by
+/// [`InjectorBuilder::validate()`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct RequiredArg {
+    service_info: ServiceInfo,
+    arg_info: ServiceInfo,
+}
+
+impl RequiredArg {
+    /// Creates a [`RequiredArg`] for an [`Arg<T>`] requested by `S`.
+    #[must_use]
+    pub fn of<S: Service, T: Service + AsAny + Clone>() -> Self {
+        RequiredArg {
+            service_info: ServiceInfo::of::<S>(),
+            arg_info: ServiceInfo::of::<T>(),
+        }
+    }
+
+    /// Gets the service which requires the argument.
+    #[must_use]
+    pub fn service_info(&self) -> ServiceInfo {
+        self.service_info
+    }
+
+    /// Gets the type of the argument.
+    #[must_use]
+    pub fn arg_info(&self) -> ServiceInfo {
+        self.arg_info
+    }
 
-        Ok(Arg(param.clone()))
+    pub(crate) fn param_name(&self) -> String {
+        arg_param_name(self.service_info, self.arg_info)
+    }
+
+    pub(crate) fn default_param_name(&self) -> String {
+        arg_default_param_name(self.service_info, self.arg_info)
     }
 }
 
@@ -90,8 +206,16 @@ impl<T: Service + AsAny + Clone> Request for Arg<T> {
 pub enum ArgRequestError {
     /// The argument value was not provided.
     MissingParameter,
-    /// The argument value is the wrong type. This should never happen.
-    ParameterTypeInvalid,
+    /// The argument value is the wrong type. This happens if the value was
+    /// inserted directly as a request parameter with the wrong type, or if a
+    /// value of a different type was provided for the service, like a `u32`
+    /// for an [`Arg<i32>`].
+    ParameterTypeMismatch {
+        /// The type of the requested argument.
+        expected: ServiceInfo,
+        /// The type of the value that was provided instead.
+        found: ServiceInfo,
+    },
     /// There is no parent request.
     NoParentRequest,
 }
@@ -104,8 +228,13 @@ impl Display for ArgRequestError {
             ArgRequestError::MissingParameter => {
                 write!(f, "no value assigned for this argument")
             }
-            ArgRequestError::ParameterTypeInvalid => {
-                write!(f, "argument value is the wrong type")
+            ArgRequestError::ParameterTypeMismatch { expected, found } => {
+                write!(
+                    f,
+                    "argument value is the wrong type (expected {}, found {})",
+                    expected.name(),
+                    found.name()
+                )
             }
             ArgRequestError::NoParentRequest => {
                 write!(f, "no parent request was found")
@@ -114,6 +243,47 @@ impl Display for ArgRequestError {
     }
 }
 
+/// An error occurred while reading an argument from an environment variable.
+#[derive(Debug)]
+pub enum EnvArgError {
+    /// The environment variable is not set or is not valid unicode.
+    MissingVariable {
+        /// The name of the environment variable.
+        variable: String,
+        /// The error returned while reading the variable.
+        inner: VarError,
+    },
+    /// The value of the environment variable could not be parsed.
+    ParseFailed {
+        /// The name of the environment variable.
+        variable: String,
+        /// The error returned while parsing the value.
+        inner: Box<dyn Error + Send + Sync + 'static>,
+    },
+}
+
+impl Error for EnvArgError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            EnvArgError::MissingVariable { inner, .. } => Some(inner),
+            EnvArgError::ParseFailed { inner, .. } => Some(inner.as_ref()),
+        }
+    }
+}
+
+impl Display for EnvArgError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EnvArgError::MissingVariable { variable, inner } => {
+                write!(f, "failed to read {variable}: {inner}")
+            }
+            EnvArgError::ParseFailed { variable, inner } => {
+                write!(f, "failed to parse {variable}: {inner}")
+            }
+        }
+    }
+}
+
 /// Allows defining pre-defined arguments to services.
 pub trait WithArg {
     /// Adds an argument for a service. See the docs for [`Arg<T>`].
@@ -121,6 +291,123 @@ pub trait WithArg {
         &mut self,
         value: T,
     ) -> Option<Box<dyn RequestParameter>>;
+
+    /// Adds a factory for the default value of an argument for a service.
+    /// The factory is called each time the argument is requested without a
+    /// value having been provided for it. If a value was provided for the
+    /// argument, then the default is never used, even if the value is the
+    /// wrong type. If a default was already set, then it is returned.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{Arg, Injector, IntoTransient, WithArg};
+    ///
+    /// struct Server(Arg<u16>);
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Server.transient());
+    /// builder.with_arg_or::<Server, u16, _>(|| 8080);
+    ///
+    /// let injector = builder.build();
+    /// let server: Box<Server> = injector.get().unwrap();
+    /// assert_eq!(8080, *server.0);
+    /// ```
+    fn with_arg_or<S, T, F>(
+        &mut self,
+        factory: F,
+    ) -> Option<Box<dyn RequestParameter>>
+    where
+        S: Service,
+        T: Service + AsAny + Clone,
+        F: Service + Fn() -> T;
+
+    /// Adds an argument for a service using the value of an environment
+    /// variable. The variable is read immediately, so a missing variable can
+    /// be reported at startup rather than when the service is requested.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{Arg, Injector, IntoTransient, WithArg};
+    ///
+    /// struct Database(Arg<String>);
+    ///
+    /// std::env::set_var("EXAMPLE_DATABASE_URL", "postgres://localhost");
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Database.transient());
+    /// builder
+    ///     .with_arg_from_env::<Database, String>("EXAMPLE_DATABASE_URL")
+    ///     .unwrap();
+    ///
+    /// let injector = builder.build();
+    /// let database: Box<Database> = injector.get().unwrap();
+    /// assert_eq!("postgres://localhost", database.0.as_str());
+    /// ```
+    fn with_arg_from_env<S, T>(
+        &mut self,
+        variable: &str,
+    ) -> Result<Option<Box<dyn RequestParameter>>, EnvArgError>
+    where
+        S: Service,
+        T: Service + AsAny + Clone + From<String>,
+    {
+        let value = std::env::var(variable).map_err(|inner| {
+            EnvArgError::MissingVariable {
+                variable: variable.to_owned(),
+                inner,
+            }
+        })?;
+
+        Ok(self.with_arg::<S, T>(T::from(value)))
+    }
+
+    /// Adds an argument for a service by parsing the value of an environment
+    /// variable. See [`WithArg::with_arg_from_env()`] for more information.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{Arg, Injector, IntoTransient, WithArg};
+    ///
+    /// struct Server(Arg<u16>);
+    ///
+    /// std::env::set_var("EXAMPLE_PORT", "8080");
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Server.transient());
+    /// builder
+    ///     .with_arg_from_env_parsed::<Server, u16>("EXAMPLE_PORT")
+    ///     .unwrap();
+    ///
+    /// let injector = builder.build();
+    /// let server: Box<Server> = injector.get().unwrap();
+    /// assert_eq!(8080, *server.0);
+    /// ```
+    fn with_arg_from_env_parsed<S, T>(
+        &mut self,
+        variable: &str,
+    ) -> Result<Option<Box<dyn RequestParameter>>, EnvArgError>
+    where
+        S: Service,
+        T: Service + AsAny + Clone + FromStr,
+        T::Err: Error + Send + Sync + 'static,
+    {
+        let value = std::env::var(variable).map_err(|inner| {
+            EnvArgError::MissingVariable {
+                variable: variable.to_owned(),
+                inner,
+            }
+        })?;
+        let value =
+            value.parse().map_err(|inner| EnvArgError::ParseFailed {
+                variable: variable.to_owned(),
+                inner: Box::new(inner),
+            })?;
+
+        Ok(self.with_arg::<S, T>(value))
+    }
 }
 
 impl WithArg for RequestInfo {
@@ -133,6 +420,23 @@ impl WithArg for RequestInfo {
             value,
         )
     }
+
+    fn with_arg_or<S, T, F>(
+        &mut self,
+        factory: F,
+    ) -> Option<Box<dyn RequestParameter>>
+    where
+        S: Service,
+        T: Service + AsAny + Clone,
+        F: Service + Fn() -> T,
+    {
+        self.insert_parameter(
+            &Arg::<T>::default_param_name(ServiceInfo::of::<S>()),
+            ArgDefault {
+                factory: Svc::new(factory) as Svc<dyn ArgFactory<T>>,
+            },
+        )
+    }
 }
 
 impl WithArg for InjectorBuilder {
@@ -142,6 +446,18 @@ impl WithArg for InjectorBuilder {
     ) -> Option<Box<dyn RequestParameter>> {
         self.root_info_mut().with_arg::<S, T>(value)
     }
+
+    fn with_arg_or<S, T, F>(
+        &mut self,
+        factory: F,
+    ) -> Option<Box<dyn RequestParameter>>
+    where
+        S: Service,
+        T: Service + AsAny + Clone,
+        F: Service + Fn() -> T,
+    {
+        self.root_info_mut().with_arg_or::<S, T, F>(factory)
+    }
 }
 
 impl WithArg for Module {
@@ -154,17 +470,38 @@ impl WithArg for Module {
             value,
         )
     }
+
+    fn with_arg_or<S, T, F>(
+        &mut self,
+        factory: F,
+    ) -> Option<Box<dyn RequestParameter>>
+    where
+        S: Service,
+        T: Service + AsAny + Clone,
+        F: Service + Fn() -> T,
+    {
+        self.insert_parameter(
+            &Arg::<T>::default_param_name(ServiceInfo::of::<S>()),
+            ArgDefault {
+                factory: Svc::new(factory) as Svc<dyn ArgFactory<T>>,
+            },
+        )
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        define_module, Arg, ArgRequestError, InjectError, Injector,
-        IntoSingleton, ServiceInfo, Svc,
+        define_module, Arg, ArgRequestError, EnvArgError, InjectError,
+        Injector, IntoSingleton, IntoTransient, Module, RequestInfo,
+        ServiceInfo, Svc, WithArg,
     };
+    use std::error::Error;
 
     #[test]
+    #[allow(clippy::unnecessary_literal_unwrap)]
     fn request_fails_if_missing_arg() {
+        #[allow(dead_code)]
         struct Foo(Arg<i32>);
 
         let module = define_module! {
@@ -181,11 +518,66 @@ mod tests {
                 service_info,
                 inner,
             }) => {
-                assert_eq!(ServiceInfo::of::<Arg<i32>>(), service_info);
-                let inner: &ArgRequestError =
-                    inner.downcast_ref().expect("failed to downcast error");
+                assert_eq!(ServiceInfo::of::<Foo>(), service_info);
+                match inner.downcast_ref() {
+                    Some(InjectError::ActivationFailed {
+                        service_info,
+                        inner,
+                    }) => {
+                        assert_eq!(
+                            ServiceInfo::of::<Arg<i32>>(),
+                            *service_info
+                        );
+                        let inner: &ArgRequestError = inner
+                            .downcast_ref()
+                            .expect("failed to downcast error");
+                        match inner {
+                            ArgRequestError::MissingParameter => {}
+                            inner => Err(inner).unwrap(),
+                        }
+                    }
+                    inner => panic!("unexpected inner error: {:?}", inner),
+                }
+            }
+            Err(error) => Err(error).unwrap(),
+        }
+    }
+
+    #[test]
+    #[allow(clippy::unnecessary_literal_unwrap)]
+    fn request_uses_default_only_if_arg_missing() {
+        struct Foo(Arg<i32>);
+
+        let mut builder = Injector::builder();
+        builder.provide(Foo.transient());
+        builder.with_arg_or::<Foo, i32, _>(|| 1);
+
+        let injector = builder.build();
+        let foo: Svc<Foo> = injector.get().unwrap();
+        assert_eq!(1, *foo.0);
+
+        let mut request_info = RequestInfo::new();
+        request_info.insert_parameter(
+            &Arg::<i32>::param_name(ServiceInfo::of::<Foo>()),
+            2u8,
+        );
+        match injector.get_with::<Svc<Foo>>(&request_info) {
+            Ok(_) => unreachable!("request should have failed"),
+            Err(error @ InjectError::ActivationFailed { .. }) => {
+                // The argument's error is the source of the service's error
+                let inner = error
+                    .source()
+                    .and_then(Error::source)
+                    .and_then(<dyn Error>::downcast_ref::<ArgRequestError>)
+                    .expect("failed to downcast error");
                 match inner {
-                    ArgRequestError::MissingParameter => {}
+                    ArgRequestError::ParameterTypeMismatch {
+                        expected,
+                        found,
+                    } => {
+                        assert_eq!(ServiceInfo::of::<i32>(), *expected);
+                        assert_eq!(ServiceInfo::of::<u8>(), *found);
+                    }
                     inner => Err(inner).unwrap(),
                 }
             }
@@ -194,6 +586,76 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::unnecessary_literal_unwrap)]
+    fn request_fails_if_arg_is_wrong_type() {
+        #[allow(dead_code)]
+        struct Foo(Arg<i32>);
+
+        let mut builder = Injector::builder();
+        builder.provide(Foo.transient());
+        builder.with_arg::<Foo, u32>(42);
+
+        let injector = builder.build();
+        match injector.get::<Svc<Foo>>() {
+            Ok(_) => unreachable!("request should have failed"),
+            Err(error @ InjectError::ActivationFailed { .. }) => {
+                let inner = error
+                    .source()
+                    .and_then(Error::source)
+                    .and_then(<dyn Error>::downcast_ref::<ArgRequestError>)
+                    .expect("failed to downcast error");
+                match inner {
+                    ArgRequestError::ParameterTypeMismatch {
+                        expected,
+                        found,
+                    } => {
+                        assert_eq!(ServiceInfo::of::<i32>(), *expected);
+                        assert_eq!(ServiceInfo::of::<u32>(), *found);
+                    }
+                    inner => Err(inner).unwrap(),
+                }
+            }
+            Err(error) => Err(error).unwrap(),
+        }
+    }
+
+    #[test]
+    fn with_arg_from_env_reports_missing_and_invalid_variables() {
+        struct Foo(Arg<u16>);
+
+        std::env::remove_var("RUNTIME_INJECTOR_TEST_MISSING_PORT");
+        std::env::set_var("RUNTIME_INJECTOR_TEST_INVALID_PORT", "port");
+        std::env::set_var("RUNTIME_INJECTOR_TEST_PORT", "8080");
+
+        let mut builder = Injector::builder();
+        builder.provide(Foo.transient());
+        match builder.with_arg_from_env_parsed::<Foo, u16>(
+            "RUNTIME_INJECTOR_TEST_MISSING_PORT",
+        ) {
+            Err(EnvArgError::MissingVariable { variable, .. }) => {
+                assert_eq!("RUNTIME_INJECTOR_TEST_MISSING_PORT", variable);
+            }
+            result => panic!("unexpected result: {:?}", result.map(|_| ())),
+        }
+        match builder.with_arg_from_env_parsed::<Foo, u16>(
+            "RUNTIME_INJECTOR_TEST_INVALID_PORT",
+        ) {
+            Err(EnvArgError::ParseFailed { variable, .. }) => {
+                assert_eq!("RUNTIME_INJECTOR_TEST_INVALID_PORT", variable);
+            }
+            result => panic!("unexpected result: {:?}", result.map(|_| ())),
+        }
+        builder
+            .with_arg_from_env_parsed::<Foo, u16>("RUNTIME_INJECTOR_TEST_PORT")
+            .unwrap();
+
+        let injector = builder.build();
+        let foo: Box<Foo> = injector.get().unwrap();
+        assert_eq!(8080, *foo.0);
+    }
+
+    #[test]
+    #[allow(clippy::unnecessary_literal_unwrap)]
     fn request_fails_if_arg_has_no_parent_request() {
         let builder = Injector::builder();
         let injector = builder.build();
@@ -214,4 +676,51 @@ mod tests {
             Err(error) => Err(error).unwrap(),
         }
     }
+
+    #[test]
+    fn build_checked_fails_if_missing_required_arg() {
+        #[allow(dead_code)]
+        struct Foo(Arg<i32>);
+
+        let mut module = Module::default();
+        module.provide(Foo.singleton());
+        module.require_arg::<Foo, i32>();
+
+        let mut builder = Injector::builder();
+        builder.add_module(module);
+
+        match builder.build_checked() {
+            Ok(_) => unreachable!("build should have failed"),
+            Err(errors) => match errors.as_slice() {
+                [InjectError::MissingArgument {
+                    service_info,
+                    arg_info,
+                }] => {
+                    assert_eq!(ServiceInfo::of::<Foo>(), *service_info);
+                    assert_eq!(ServiceInfo::of::<i32>(), *arg_info);
+                }
+                errors => panic!("unexpected errors: {:?}", errors),
+            },
+        }
+    }
+
+    #[test]
+    fn build_checked_succeeds_if_required_arg_provided() {
+        struct Foo(Arg<i32>);
+
+        let mut module = define_module! {
+            services = [Foo.singleton()],
+            arguments = {
+                Foo = [12i32],
+            },
+        };
+        module.require_arg::<Foo, i32>();
+
+        let mut builder = Injector::builder();
+        builder.add_module(module);
+
+        let injector = builder.build_checked().unwrap();
+        let foo: Svc<Foo> = injector.get().unwrap();
+        assert_eq!(12, *foo.0);
+    }
 }
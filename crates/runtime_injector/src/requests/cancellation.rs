@@ -0,0 +1,20 @@
+use crate::Service;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A token which can signal that a request should stop activating services.
+/// See [`RequestInfo::set_cancellation()`] for more information.
+///
+/// This is implemented for [`AtomicBool`], and can be implemented for the
+/// cancellation tokens of any runtime.
+///
+/// [`RequestInfo::set_cancellation()`]: crate::RequestInfo::set_cancellation
+pub trait IsCancelled: Service {
+    /// Checks whether the request has been cancelled.
+    fn is_cancelled(&self) -> bool;
+}
+
+impl IsCancelled for AtomicBool {
+    fn is_cancelled(&self) -> bool {
+        self.load(Ordering::Relaxed)
+    }
+}
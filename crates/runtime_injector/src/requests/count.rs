@@ -0,0 +1,87 @@
+use crate::{
+    InjectResult, Injector, Interface, Request, RequestInfo, ServiceInfo,
+};
+use std::{
+    fmt::{Debug, Formatter},
+    marker::PhantomData,
+};
+
+/// Requests the number of providers registered for a service or interface
+/// without activating any of them. This is useful for deciding whether to
+/// request the implementations of an interface at all.
+///
+/// Since no providers are invoked, conditional providers are counted as
+/// potential implementations even if their conditions would not be met, and
+/// providers which create several instances at once are only counted once.
+/// If no providers are registered for the service, then the count is `0`.
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{
+///     interface, Count, Injector, IntoSingleton, Service, TypedProvider,
+///     WithCondition,
+/// };
+///
+/// trait Plugin: Service {}
+/// interface!(dyn Plugin = [Foo, Bar]);
+///
+/// #[derive(Default)]
+/// struct Foo;
+/// impl Plugin for Foo {}
+///
+/// #[derive(Default)]
+/// struct Bar;
+/// impl Plugin for Bar {}
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(Foo::default.singleton().with_interface::<dyn Plugin>());
+/// builder.provide(
+///     Bar::default
+///         .singleton()
+///         .with_condition(|_: &_, _: &_| false)
+///         .with_interface::<dyn Plugin>(),
+/// );
+///
+/// let injector = builder.build();
+/// let count: Count<dyn Plugin> = injector.get().unwrap();
+/// assert_eq!(2, count.get());
+/// ```
+pub struct Count<I: ?Sized + Interface> {
+    count: usize,
+    marker: PhantomData<fn() -> I>,
+}
+
+impl<I: ?Sized + Interface> Count<I> {
+    /// Gets the number of providers registered for the service.
+    #[must_use]
+    pub fn get(&self) -> usize {
+        self.count
+    }
+}
+
+impl<I: ?Sized + Interface> Clone for Count<I> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I: ?Sized + Interface> Copy for Count<I> {}
+
+impl<I: ?Sized + Interface> Debug for Count<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Count").field(&self.count).finish()
+    }
+}
+
+/// Requests the number of providers registered for a service. See the docs
+/// for [`Count<T>`].
+impl<I: ?Sized + Interface> Request for Count<I> {
+    fn request(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
+        let count = injector.count_providers(ServiceInfo::of::<I>(), info)?;
+        Ok(Count {
+            count,
+            marker: PhantomData,
+        })
+    }
+}
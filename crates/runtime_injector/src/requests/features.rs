@@ -0,0 +1,76 @@
+use std::{collections::HashSet, iter::FromIterator};
+
+/// A set of feature flags which are enabled at runtime. Providers can be
+/// gated by a feature so that they are only used while it is enabled, which
+/// allows the services used by an application to be toggled without
+/// recompiling it. See [`WithCondition::gated_by()`] for more information.
+///
+/// The features for a request are stored in its [`RequestInfo`] and are
+/// shared with the requests for each of its dependencies. To enable features
+/// for every request made to an injector, enable them in the
+/// [root request info](crate::InjectorBuilder::root_info_mut) when building
+/// the injector.
+///
+/// [`WithCondition::gated_by()`]: crate::WithCondition::gated_by
+/// [`RequestInfo`]: crate::RequestInfo
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::FeatureSet;
+///
+/// let mut features = FeatureSet::new();
+/// assert!(features.enable("new_pipeline"));
+/// assert!(features.is_enabled("new_pipeline"));
+///
+/// assert!(features.disable("new_pipeline"));
+/// assert!(!features.is_enabled("new_pipeline"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FeatureSet {
+    enabled: HashSet<String>,
+}
+
+impl FeatureSet {
+    /// Creates a new, empty feature set.
+    #[must_use]
+    pub fn new() -> Self {
+        FeatureSet::default()
+    }
+
+    /// Enables a feature. Returns `true` if the feature was not already
+    /// enabled.
+    pub fn enable(&mut self, feature: impl Into<String>) -> bool {
+        self.enabled.insert(feature.into())
+    }
+
+    /// Disables a feature. Returns `true` if the feature was enabled.
+    pub fn disable(&mut self, feature: &str) -> bool {
+        self.enabled.remove(feature)
+    }
+
+    /// Checks whether a feature is enabled.
+    #[must_use]
+    pub fn is_enabled(&self, feature: &str) -> bool {
+        self.enabled.contains(feature)
+    }
+
+    /// Gets an iterator over the enabled features in an arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.enabled.iter().map(String::as_str)
+    }
+}
+
+impl<S: Into<String>> FromIterator<S> for FeatureSet {
+    fn from_iter<T: IntoIterator<Item = S>>(iter: T) -> Self {
+        FeatureSet {
+            enabled: iter.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl<S: Into<String>> Extend<S> for FeatureSet {
+    fn extend<T: IntoIterator<Item = S>>(&mut self, iter: T) {
+        self.enabled.extend(iter.into_iter().map(Into::into));
+    }
+}
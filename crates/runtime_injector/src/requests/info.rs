@@ -1,4 +1,7 @@
-use crate::{RequestParameter, ServiceInfo};
+use crate::{
+    FeatureSet, IsCancelled, MapContainer, MapContainerEx, Overrides,
+    RequestParameter, ServiceInfo, Svc,
+};
 use std::{
     collections::HashMap,
     fmt::{Debug, Formatter},
@@ -9,6 +12,12 @@ use std::{
 pub struct RequestInfo {
     service_path: Vec<ServiceInfo>,
     parameters: HashMap<String, Box<dyn RequestParameter>>,
+    overrides: Option<Svc<Overrides>>,
+    nested: bool,
+    provider_name: Option<String>,
+    cancellation: Option<Svc<dyn IsCancelled>>,
+    features: FeatureSet,
+    trace: Option<MapContainer<Vec<ServiceInfo>>>,
 }
 
 impl RequestInfo {
@@ -18,6 +27,35 @@ impl RequestInfo {
         RequestInfo {
             service_path: Vec::new(),
             parameters: HashMap::new(),
+            overrides: None,
+            nested: false,
+            provider_name: None,
+            cancellation: None,
+            features: FeatureSet::new(),
+            trace: None,
+        }
+    }
+
+    /// Creates a builder for a [`RequestInfo`]. This is a shorthand for
+    /// creating an empty request info and inserting parameters into it.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::RequestInfo;
+    ///
+    /// let request_info = RequestInfo::builder()
+    ///     .param("x", 5)
+    ///     .param("name", "foo".to_owned())
+    ///     .build();
+    ///
+    /// let x = request_info.get_parameter("x").unwrap();
+    /// assert_eq!(Some(&5), x.downcast_ref::<i32>());
+    /// ```
+    #[must_use]
+    pub fn builder() -> RequestInfoBuilder {
+        RequestInfoBuilder {
+            request_info: RequestInfo::new(),
         }
     }
 
@@ -27,6 +65,7 @@ impl RequestInfo {
     pub fn with_request(&self, service: ServiceInfo) -> Self {
         let mut child = self.clone();
         child.service_path.push(service);
+        child.provider_name = None;
         child
     }
 
@@ -76,6 +115,69 @@ impl RequestInfo {
         &self.service_path
     }
 
+    /// Gets the service which is making the current request, if any. This is
+    /// the last service in the [request path](RequestInfo::service_path()).
+    /// When a service's dependencies are requested, this is the service
+    /// being activated, so custom [`Request`] implementations can use it to
+    /// find out which service they are being injected into. It is [`None`]
+    /// for requests made directly to the injector.
+    ///
+    /// [`Request`]: crate::Request
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     InjectResult, Injector, IntoTransient, Request, RequestInfo,
+    ///     ServiceInfo, Svc,
+    /// };
+    ///
+    /// struct RequestedBy(Option<ServiceInfo>);
+    ///
+    /// impl Request for RequestedBy {
+    ///     fn request(
+    ///         _injector: &Injector,
+    ///         info: &RequestInfo,
+    ///     ) -> InjectResult<Self> {
+    ///         Ok(RequestedBy(info.parent_service()))
+    ///     }
+    /// }
+    ///
+    /// struct Foo(RequestedBy);
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Foo.transient());
+    ///
+    /// let injector = builder.build();
+    /// let foo: Svc<Foo> = injector.get().unwrap();
+    /// assert_eq!(Some(ServiceInfo::of::<Foo>()), foo.0 .0);
+    ///
+    /// let requested_by: RequestedBy = injector.get().unwrap();
+    /// assert_eq!(None, requested_by.0);
+    /// ```
+    #[must_use]
+    pub fn parent_service(&self) -> Option<ServiceInfo> {
+        self.service_path.last().copied()
+    }
+
+    /// Gets the service which was originally requested from the injector, if
+    /// any. This is the first service in the
+    /// [request path](RequestInfo::service_path()), and is [`None`] for
+    /// requests made directly to the injector. See
+    /// [`RequestInfo::parent_service()`] for more information.
+    #[must_use]
+    pub fn root_service(&self) -> Option<ServiceInfo> {
+        self.service_path.first().copied()
+    }
+
+    /// Gets the depth of the current request. This is the number of services
+    /// in the [request path](RequestInfo::service_path()), so requests made
+    /// directly to the injector have a depth of zero.
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.service_path.len()
+    }
+
     /// Sets the value of a request parameter for the request. If a parameter
     /// has already been set to a value, then that value is returned.
     pub fn insert_parameter(
@@ -116,6 +218,171 @@ impl RequestInfo {
     ) -> Option<&mut dyn RequestParameter> {
         self.parameters.get_mut(key).map(AsMut::as_mut)
     }
+
+    /// Sets the cancellation token for the request. The token is shared with
+    /// the requests for each of the service's dependencies. Before each
+    /// service is requested, the token is checked, and if it has been
+    /// cancelled, then the request fails with [`InjectError::Cancelled`]
+    /// instead. This allows a request to stop constructing expensive services
+    /// midway, like when the HTTP request it is being made for is dropped.
+    ///
+    /// Services which are already being activated are not interrupted, but
+    /// factories can check [`RequestInfo::cancellation()`] themselves.
+    ///
+    /// [`InjectError::Cancelled`]: crate::InjectError::Cancelled
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     InjectError, Injector, IntoTransient, RequestInfo, Svc,
+    /// };
+    /// use std::sync::atomic::AtomicBool;
+    ///
+    /// #[derive(Default)]
+    /// struct Database;
+    /// struct Report(Svc<Database>);
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Database::default.transient());
+    /// builder.provide(Report.transient());
+    ///
+    /// let injector = builder.build();
+    /// let mut request_info = RequestInfo::new();
+    /// request_info.set_cancellation(Svc::new(AtomicBool::new(true)));
+    /// assert!(matches!(
+    ///     injector.get_with::<Svc<Report>>(&request_info),
+    ///     Err(InjectError::Cancelled { .. })
+    /// ));
+    /// ```
+    pub fn set_cancellation<T: IsCancelled>(&mut self, token: Svc<T>) {
+        self.cancellation = Some(token);
+    }
+
+    /// Gets the cancellation token for the request, if one has been set. See
+    /// [`RequestInfo::set_cancellation()`] for more information.
+    #[must_use]
+    pub fn cancellation(&self) -> Option<&dyn IsCancelled> {
+        self.cancellation.as_deref()
+    }
+
+    /// Gets the features which are enabled for the request. See
+    /// [`FeatureSet`] for more information.
+    #[must_use]
+    pub fn features(&self) -> &FeatureSet {
+        &self.features
+    }
+
+    /// Mutably gets the features which are enabled for the request. See
+    /// [`FeatureSet`] for more information.
+    pub fn features_mut(&mut self) -> &mut FeatureSet {
+        &mut self.features
+    }
+
+    /// Checks whether the request has been cancelled. Requests without a
+    /// cancellation token are never cancelled.
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancellation
+            .as_ref()
+            .is_some_and(|token| token.is_cancelled())
+    }
+
+    /// Starts tracing the request. See [`Injector::get_traced()`] for more
+    /// information.
+    ///
+    /// [`Injector::get_traced()`]: crate::Injector::get_traced
+    pub(crate) fn start_trace(&mut self) -> MapContainer<Vec<ServiceInfo>> {
+        let trace: MapContainer<_> = MapContainerEx::new(Vec::new());
+        self.trace = Some(trace.clone());
+        trace
+    }
+
+    /// Records that a service is being requested if the request is being
+    /// traced.
+    pub(crate) fn trace_step(&self, service_info: ServiceInfo) {
+        if let Some(trace) = &self.trace {
+            trace.with_inner_mut(|trace| {
+                trace.clone_from(&self.service_path);
+                trace.push(service_info);
+            });
+        }
+    }
+
+    /// Gets all the parameters set for the request.
+    pub(crate) fn parameters(
+        &self,
+    ) -> &HashMap<String, Box<dyn RequestParameter>> {
+        &self.parameters
+    }
+
+    /// Gets the services which take precedence over the registered providers
+    /// for this request.
+    pub(crate) fn overrides(&self) -> Option<&Overrides> {
+        self.overrides.as_deref()
+    }
+
+    pub(crate) fn set_overrides(&mut self, overrides: Overrides) {
+        self.overrides = Some(Svc::new(overrides));
+    }
+
+    /// Whether this request is being made on behalf of another request that
+    /// has already passed through the injector's request middleware.
+    pub(crate) fn is_nested(&self) -> bool {
+        self.nested
+    }
+
+    pub(crate) fn set_nested(&mut self) {
+        self.nested = true;
+    }
+
+    /// The name of the providers this request should be resolved from, if
+    /// any. Requests for dependencies never inherit this name.
+    pub(crate) fn provider_name(&self) -> Option<&str> {
+        self.provider_name.as_deref()
+    }
+
+    pub(crate) fn set_provider_name(&mut self, name: &str) {
+        self.provider_name = Some(name.to_owned());
+    }
+}
+
+/// A builder for a [`RequestInfo`]. See [`RequestInfo::builder()`] for more
+/// information.
+#[derive(Clone, Debug)]
+pub struct RequestInfoBuilder {
+    request_info: RequestInfo,
+}
+
+impl RequestInfoBuilder {
+    /// Sets the value of a request parameter, replacing any value which has
+    /// already been set for the parameter.
+    #[must_use]
+    pub fn param(mut self, key: &str, value: impl RequestParameter) -> Self {
+        self.request_info.insert_parameter(key, value);
+        self
+    }
+
+    /// Sets the cancellation token for the request. See
+    /// [`RequestInfo::set_cancellation()`] for more information.
+    #[must_use]
+    pub fn cancellation<T: IsCancelled>(mut self, token: Svc<T>) -> Self {
+        self.request_info.set_cancellation(token);
+        self
+    }
+
+    /// Enables a feature for the request. See [`FeatureSet`] for more
+    /// information.
+    #[must_use]
+    pub fn feature(mut self, feature: impl Into<String>) -> Self {
+        self.request_info.features_mut().enable(feature);
+        self
+    }
+
+    /// Builds the request info.
+    #[must_use]
+    pub fn build(self) -> RequestInfo {
+        self.request_info
+    }
 }
 
 impl Default for RequestInfo {
@@ -126,9 +393,8 @@ impl Default for RequestInfo {
 
 impl Debug for RequestInfo {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        // TODO: maybe use finish_non_exhaustive when 1.53 hits stable
         f.debug_struct("RequestInfo")
             .field("service_path", &self.service_path)
-            .finish()
+            .finish_non_exhaustive()
     }
 }
@@ -0,0 +1,55 @@
+use crate::{InjectResult, Injector, Request, RequestInfo};
+
+/// A value which can be created by an injector from several requests. This
+/// is implemented for every [`Request`], and can be implemented manually for
+/// structs which are composed of multiple requests. Values which implement
+/// this trait can be created with [`Injector::inject()`].
+///
+/// Unlike a [`Request`], a type which only implements [`Inject`] can't be
+/// used as a dependency of a service. Instead, it is useful as an entry point
+/// for code which needs several services at once, like a request handler.
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{
+///     Inject, InjectResult, Injector, IntoSingleton, Request, RequestInfo,
+///     Svc,
+/// };
+///
+/// #[derive(Default)]
+/// struct Database;
+/// #[derive(Default)]
+/// struct Cache;
+///
+/// struct Handler {
+///     database: Svc<Database>,
+///     cache: Option<Svc<Cache>>,
+/// }
+///
+/// impl Inject for Handler {
+///     fn inject(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
+///         Ok(Handler {
+///             database: Request::request(injector, info)?,
+///             cache: Request::request(injector, info)?,
+///         })
+///     }
+/// }
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(Database::default.singleton());
+///
+/// let injector = builder.build();
+/// let handler: Handler = injector.inject().unwrap();
+/// assert!(handler.cache.is_none());
+/// ```
+pub trait Inject: Sized {
+    /// Creates the value using services from the injector.
+    fn inject(injector: &Injector, info: &RequestInfo) -> InjectResult<Self>;
+}
+
+impl<R: Request> Inject for R {
+    fn inject(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
+        R::request(injector, info)
+    }
+}
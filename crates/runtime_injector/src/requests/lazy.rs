@@ -0,0 +1,132 @@
+use crate::{
+    InjectResult, Injector, MapContainer, MapContainerEx, Request, RequestInfo,
+    ServiceInfo, Svc,
+};
+
+#[cfg(feature = "arc")]
+type LazyCell<T> = std::sync::OnceLock<T>;
+
+#[cfg(feature = "rc")]
+type LazyCell<T> = std::cell::OnceCell<T>;
+
+/// Lazily performs a request the first time its value is needed, then caches
+/// the result.
+///
+/// Unlike a singleton, the cached value is owned by the requesting service
+/// rather than the container, so each [`Lazy<R>`] performs its request
+/// separately. Unlike a [`Factory<R>`](crate::Factory), the request is only
+/// performed until it succeeds once. This is useful when a service only needs
+/// a dependency in rare code paths.
+///
+/// Cloning a [`Lazy<R>`] shares its cache with the clone, so the request is
+/// only performed once between all of them.
+///
+/// Until the request succeeds, a [`Lazy<R>`] holds onto the injector it was
+/// requested from. Once the value has been cached, the injector is released,
+/// so a singleton which stores a [`Lazy<R>`] doesn't keep the injector alive
+/// after the value has been created.
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{
+///     constant, Injector, IntoSingleton, IntoTransient, Lazy, Svc,
+/// };
+/// use std::sync::Mutex;
+///
+/// struct Expensive;
+/// impl Expensive {
+///     fn new(counter: Svc<Mutex<i32>>) -> Self {
+///         *counter.lock().unwrap() += 1;
+///         Expensive
+///     }
+/// }
+///
+/// struct Foo(Lazy<Box<Expensive>>);
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(constant(Mutex::new(0i32)));
+/// builder.provide(Expensive::new.transient());
+/// builder.provide(Foo.singleton());
+///
+/// let injector = builder.build();
+/// let counter: Svc<Mutex<i32>> = injector.get().unwrap();
+/// let foo: Svc<Foo> = injector.get().unwrap();
+/// assert_eq!(0, *counter.lock().unwrap());
+///
+/// let _expensive = foo.0.get().unwrap();
+/// let _expensive = foo.0.get().unwrap();
+/// assert_eq!(1, *counter.lock().unwrap());
+/// ```
+pub struct Lazy<R: Request> {
+    // Released once the value has been cached
+    source: MapContainer<Option<(Injector, RequestInfo)>>,
+    value: Svc<LazyCell<R>>,
+}
+
+impl<R: Request> Lazy<R> {
+    /// Gets the value of the request, performing the request if it hasn't
+    /// succeeded yet. If the request fails, then the error is returned and
+    /// the request is performed again the next time this is called.
+    ///
+    /// With the "arc" feature enabled, if multiple threads call this at the
+    /// same time before the value has been cached, then the request may be
+    /// performed more than once. Only one of the results is cached, and it is
+    /// returned to all callers.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn get(&self) -> InjectResult<&R> {
+        if let Some(value) = self.value.get() {
+            return Ok(value);
+        }
+
+        // The source is only released after the value has been cached
+        let Some((injector, request_info)) =
+            self.source.with_inner(Clone::clone)
+        else {
+            // Should never panic
+            return Ok(self.value.get().unwrap());
+        };
+
+        let value = R::request(&injector, &request_info)?;
+        let value = self.value.get_or_init(|| value);
+        self.source.with_inner_mut(|source| *source = None);
+        Ok(value)
+    }
+
+    /// Gets the value of the request if it has already been performed.
+    #[must_use]
+    pub fn get_cached(&self) -> Option<&R> {
+        self.value.get()
+    }
+
+    /// Converts this into its cached value if the request has already been
+    /// performed. If this shares its cache with any clones, then `None` is
+    /// returned instead.
+    #[must_use]
+    pub fn into_inner(lazy: Self) -> Option<R> {
+        Svc::try_unwrap(lazy.value).ok()?.into_inner()
+    }
+}
+
+impl<R: Request> Clone for Lazy<R> {
+    fn clone(&self) -> Self {
+        Lazy {
+            source: self.source.clone(),
+            value: self.value.clone(),
+        }
+    }
+}
+
+/// Lazily performs a request the first time its value is needed.
+impl<R: Request> Request for Lazy<R> {
+    fn request(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
+        Ok(Lazy {
+            source: MapContainerEx::new(Some((injector.clone(), info.clone()))),
+            value: Svc::new(LazyCell::new()),
+        })
+    }
+
+    fn dependencies() -> Vec<ServiceInfo> {
+        R::dependencies()
+    }
+}
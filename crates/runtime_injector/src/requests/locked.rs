@@ -0,0 +1,136 @@
+use crate::{InjectResult, Injector, Request, RequestInfo, Service, Svc};
+
+#[cfg(feature = "arc")]
+mod types {
+    use std::sync::{Mutex, MutexGuard};
+
+    /// The lock used to wrap mutable services. The lock type is determined by
+    /// the feature flags passed to this crate.
+    ///
+    /// - **rc**: Lock type is [`RefCell<T>`](std::cell::RefCell)
+    /// - **arc**: Lock type is [`Mutex<T>`] (default)
+    pub type SvcLock<T> = Mutex<T>;
+
+    /// A guard holding a [`SvcLock<T>`]. The lock is released when the guard
+    /// is dropped.
+    pub type SvcLockGuard<'a, T> = MutexGuard<'a, T>;
+
+    pub(super) fn lock<T>(lock: &SvcLock<T>) -> SvcLockGuard<'_, T> {
+        // A panic while the lock was held doesn't invalidate the service
+        lock.lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[cfg(feature = "rc")]
+mod types {
+    use std::cell::{RefCell, RefMut};
+
+    /// The lock used to wrap mutable services. The lock type is determined by
+    /// the feature flags passed to this crate.
+    ///
+    /// - **rc**: Lock type is [`RefCell<T>`] (default)
+    /// - **arc**: Lock type is [`Mutex<T>`](std::sync::Mutex)
+    pub type SvcLock<T> = RefCell<T>;
+
+    /// A guard holding a [`SvcLock<T>`]. The lock is released when the guard
+    /// is dropped.
+    pub type SvcLockGuard<'a, T> = RefMut<'a, T>;
+
+    pub(super) fn lock<T>(lock: &SvcLock<T>) -> SvcLockGuard<'_, T> {
+        lock.borrow_mut()
+    }
+}
+
+pub use types::{SvcLock, SvcLockGuard};
+
+/// Requests a service wrapped in a [`SvcLock<T>`] and allows it to be locked
+/// directly. With the "arc" feature enabled, this resolves a
+/// [`Svc<Mutex<T>>`](std::sync::Mutex), and with the "rc" feature enabled,
+/// this resolves a [`Svc<RefCell<T>>`](std::cell::RefCell).
+///
+/// The guard returned by [`Locked::lock()`] borrows from the [`Locked<T>`],
+/// so the lock can be released and re-acquired as many times as needed.
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{constant, Injector, Locked, SvcLock};
+///
+/// struct Counter(i32);
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(constant(SvcLock::new(Counter(0))));
+///
+/// let injector = builder.build();
+/// let counter: Locked<Counter> = injector.get().unwrap();
+/// counter.lock().0 += 1;
+/// counter.lock().0 += 1;
+///
+/// let counter: Locked<Counter> = injector.get().unwrap();
+/// assert_eq!(2, counter.lock().0);
+/// ```
+pub struct Locked<T: Service>(Svc<SvcLock<T>>)
+where
+    SvcLock<T>: Service;
+
+impl<T: Service> Locked<T>
+where
+    SvcLock<T>: Service,
+{
+    /// Acquires the lock on the service. With the "arc" feature enabled, this
+    /// blocks until the lock is acquired. If a thread panicked while holding
+    /// the lock, the lock is acquired anyway.
+    ///
+    /// ## Panics
+    ///
+    /// With the "rc" feature enabled, this panics if the service is already
+    /// borrowed.
+    #[must_use = "the lock is released when the guard is dropped"]
+    pub fn lock(&self) -> SvcLockGuard<'_, T> {
+        types::lock(&self.0)
+    }
+
+    /// Acquires the lock on the service, calls the given function with a
+    /// mutable reference to it, then releases the lock. See
+    /// [`Locked::lock()`] for more information on how the lock is acquired.
+    ///
+    /// ## Panics
+    ///
+    /// With the "rc" feature enabled, this panics if the service is already
+    /// borrowed.
+    pub fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        f(&mut self.lock())
+    }
+
+    /// Converts a [`Locked<T>`] into its inner service pointer.
+    #[must_use]
+    pub fn into_inner(value: Self) -> Svc<SvcLock<T>> {
+        value.0
+    }
+}
+
+impl<T: Service> Clone for Locked<T>
+where
+    SvcLock<T>: Service,
+{
+    fn clone(&self) -> Self {
+        Locked(self.0.clone())
+    }
+}
+
+/// Requests a service wrapped in a lock. See the docs for [`Locked<T>`].
+impl<T: Service> Request for Locked<T>
+where
+    SvcLock<T>: Service,
+{
+    fn request(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
+        let inner: Svc<SvcLock<T>> = injector.get_with(info)?;
+        Ok(Locked(inner))
+    }
+}
+
+/// Requests a mutable service. This is an alias for [`Locked<T>`], and is
+/// usually used with services provided by
+/// [`constant_mut()`](crate::constant_mut).
+pub type SvcMut<T> = Locked<T>;
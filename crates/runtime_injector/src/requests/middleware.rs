@@ -0,0 +1,73 @@
+use crate::{InjectResult, RequestInfo, Service, Svc};
+
+/// The continuation of a request passed to a request middleware. Calling it
+/// runs the remaining middleware, then performs the request itself. See
+/// [`InjectorBuilder::add_request_middleware()`][add_request_middleware] for
+/// more information.
+///
+/// [add_request_middleware]: crate::InjectorBuilder::add_request_middleware
+pub type NextMiddleware<'a> =
+    &'a mut dyn FnMut(&mut RequestInfo) -> InjectResult<()>;
+
+/// Intercepts requests made to an injector.
+pub(crate) trait RequestMiddleware: Service {
+    fn handle(
+        &self,
+        request_info: &mut RequestInfo,
+        next: NextMiddleware<'_>,
+    ) -> InjectResult<()>;
+}
+
+impl<F> RequestMiddleware for F
+where
+    F: Service + Fn(&mut RequestInfo, NextMiddleware<'_>) -> InjectResult<()>,
+{
+    fn handle(
+        &self,
+        request_info: &mut RequestInfo,
+        next: NextMiddleware<'_>,
+    ) -> InjectResult<()> {
+        self(request_info, next)
+    }
+}
+
+/// A registered request middleware.
+#[derive(Clone)]
+pub(crate) struct MiddlewareEntry {
+    middleware: Svc<dyn RequestMiddleware>,
+    nested: bool,
+}
+
+impl MiddlewareEntry {
+    pub fn new<F>(middleware: F, nested: bool) -> Self
+    where
+        F: Service
+            + Fn(&mut RequestInfo, NextMiddleware<'_>) -> InjectResult<()>,
+    {
+        MiddlewareEntry {
+            middleware: Svc::new(middleware),
+            nested,
+        }
+    }
+
+    /// Runs a chain of middleware, ending with the request itself.
+    pub fn run_chain(
+        chain: &[&MiddlewareEntry],
+        request_info: &mut RequestInfo,
+        request: NextMiddleware<'_>,
+    ) -> InjectResult<()> {
+        match chain.split_first() {
+            Some((first, rest)) => {
+                first.middleware.handle(request_info, &mut |request_info| {
+                    Self::run_chain(rest, request_info, request)
+                })
+            }
+            None => request(request_info),
+        }
+    }
+
+    /// Whether this middleware should run for the given request.
+    pub fn applies_to(&self, request_info: &RequestInfo) -> bool {
+        self.nested || !request_info.is_nested()
+    }
+}
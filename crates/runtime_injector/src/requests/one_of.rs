@@ -0,0 +1,77 @@
+/// Defines an enum which can be requested from an injector and resolves to
+/// whichever of several known implementations has been registered. This lets
+/// you match on the concrete implementation of a service without needing to
+/// downcast it.
+///
+/// Each variant of the enum holds a [`Svc<T>`](crate::Svc) for its service.
+/// When the enum is requested, each service is requested in the order the
+/// variants are listed as an [`Option<Svc<T>>`], and the first one that has a
+/// provider is used. This means that if multiple services are registered,
+/// the earliest listed variant takes priority. If none of the services have
+/// a provider, then the request fails with
+/// [`InjectError::MissingProvider`](crate::InjectError::MissingProvider) for
+/// the enum type itself. Any other errors encountered while requesting the
+/// services are returned immediately.
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{one_of, Injector, IntoSingleton};
+///
+/// #[derive(Default)]
+/// struct SqlDb;
+/// #[derive(Default)]
+/// struct MockDb;
+///
+/// one_of! {
+///     /// The database the application is configured to use.
+///     pub enum Database {
+///         Sql(SqlDb),
+///         Mock(MockDb),
+///     }
+/// }
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(MockDb::default.singleton());
+///
+/// let injector = builder.build();
+/// let database: Database = injector.get().unwrap();
+/// assert!(matches!(database, Database::Mock(_)));
+/// ```
+#[macro_export]
+macro_rules! one_of {
+    {
+        $(#[$attr:meta])*
+        $vis:vis enum $name:ident {
+            $($variant:ident($service:ty)),+
+            $(,)?
+        }
+    } => {
+        $(#[$attr])*
+        $vis enum $name {
+            $(
+                #[allow(missing_docs)]
+                $variant($crate::Svc<$service>),
+            )+
+        }
+
+        impl $crate::Request for $name {
+            fn request(
+                injector: &$crate::Injector,
+                info: &$crate::RequestInfo,
+            ) -> $crate::InjectResult<Self> {
+                $(
+                    let service: ::std::option::Option<$crate::Svc<$service>> =
+                        injector.get_with(info)?;
+                    if let ::std::option::Option::Some(service) = service {
+                        return ::std::result::Result::Ok($name::$variant(service));
+                    }
+                )+
+
+                ::std::result::Result::Err($crate::InjectError::MissingProvider {
+                    service_info: $crate::ServiceInfo::of::<Self>(),
+                })
+            }
+        }
+    };
+}
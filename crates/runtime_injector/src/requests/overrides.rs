@@ -0,0 +1,108 @@
+use crate::{
+    DynSvc, InjectResult, Injector, InterfaceFor, Lifetime, Provider,
+    RequestInfo, Service, ServiceInfo, Svc,
+};
+use std::collections::HashMap;
+
+/// A collection of service instances which take precedence over the
+/// registered providers for a single request. See
+/// [`Injector::get_with_overrides()`] for more information.
+#[derive(Clone, Default)]
+pub struct Overrides {
+    services: HashMap<ServiceInfo, DynSvc>,
+}
+
+impl Overrides {
+    /// Creates a new, empty set of overrides.
+    #[must_use]
+    pub fn new() -> Self {
+        Overrides::default()
+    }
+
+    /// Overrides a service with the given instance. If the service was
+    /// already overridden, then the previous instance is returned.
+    pub fn insert<T: Service>(&mut self, service: Svc<T>) -> Option<DynSvc> {
+        self.services.insert(ServiceInfo::of::<T>(), service)
+    }
+
+    /// Overrides an interface with the given implementation. If the interface
+    /// was already overridden, then the previous instance is returned.
+    pub fn insert_as<I, T>(&mut self, service: Svc<T>) -> Option<DynSvc>
+    where
+        I: ?Sized + InterfaceFor<T>,
+        T: Service,
+    {
+        self.services.insert(ServiceInfo::of::<I>(), service)
+    }
+
+    /// Removes the override for a service or interface if it's been set.
+    pub fn remove(&mut self, service_info: ServiceInfo) -> Option<DynSvc> {
+        self.services.remove(&service_info)
+    }
+
+    pub(crate) fn contains(&self, service_info: ServiceInfo) -> bool {
+        self.services.contains_key(&service_info)
+    }
+
+    pub(crate) fn provider_for(
+        &self,
+        service_info: ServiceInfo,
+    ) -> Option<Box<dyn Provider>> {
+        self.services.get(&service_info).map(|service| {
+            Box::new(OverrideProvider {
+                service_info,
+                service: service.clone(),
+            }) as Box<dyn Provider>
+        })
+    }
+}
+
+/// Defines a conversion into a set of [`Overrides`].
+pub trait IntoOverrides {
+    /// Converts this value into a set of overrides.
+    fn into_overrides(self) -> Overrides;
+}
+
+impl IntoOverrides for Overrides {
+    fn into_overrides(self) -> Overrides {
+        self
+    }
+}
+
+impl<T> IntoOverrides for T
+where
+    T: IntoIterator<Item = (ServiceInfo, DynSvc)>,
+{
+    fn into_overrides(self) -> Overrides {
+        Overrides {
+            services: self.into_iter().collect(),
+        }
+    }
+}
+
+struct OverrideProvider {
+    service_info: ServiceInfo,
+    service: DynSvc,
+}
+
+impl Provider for OverrideProvider {
+    fn result(&self) -> ServiceInfo {
+        self.service_info
+    }
+
+    fn provide(
+        &mut self,
+        _injector: &Injector,
+        _request_info: &RequestInfo,
+    ) -> InjectResult<DynSvc> {
+        Ok(self.service.clone())
+    }
+
+    fn strong_count(&self) -> Option<usize> {
+        Some(DynSvc::strong_count(&self.service))
+    }
+
+    fn lifetime(&self) -> Lifetime {
+        Lifetime::Constant
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{
+    InjectResult, Injector, Request, RequestInfo, Service, ServiceInfo, Svc,
+};
+use std::{
+    borrow::Cow,
+    ops::{Deref, DerefMut},
+};
+
+/// Requests an owned clone of a service. This resolves the service the same
+/// way as a [`Svc<T>`] request, then clones the value out of the service
+/// pointer. This is useful for services which need to own plain values, like
+/// configuration, which are registered as singletons or constants.
+///
+/// Unlike a [`Box<T>`] request, the service's provider doesn't need to
+/// support providing owned services, but the service must implement
+/// [`Clone`].
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{constant, Injector, IntoTransient, Owned, Svc};
+///
+/// #[derive(Clone)]
+/// struct Config {
+///     retries: u32,
+/// }
+///
+/// struct Client {
+///     config: Config,
+/// }
+///
+/// impl Client {
+///     fn new(config: Owned<Config>) -> Self {
+///         Client {
+///             config: Owned::into_inner(config),
+///         }
+///     }
+/// }
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(constant(Config { retries: 3 }));
+/// builder.provide(Client::new.transient());
+///
+/// let injector = builder.build();
+/// let client: Svc<Client> = injector.get().unwrap();
+/// assert_eq!(3, client.config.retries);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Owned<T: Service + Clone>(T);
+
+impl<T: Service + Clone> Owned<T> {
+    /// Converts an owned request into its inner value.
+    #[must_use]
+    pub fn into_inner(owned: Self) -> T {
+        owned.0
+    }
+}
+
+impl<T: Service + Clone> Deref for Owned<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Service + Clone> DerefMut for Owned<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// Requests an owned clone of a service. See the docs for [`Owned<T>`].
+impl<T: Service + Clone> Request for Owned<T> {
+    fn request(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
+        let service: Svc<T> = injector.get_with(info)?;
+        Ok(Owned(service.as_ref().clone()))
+    }
+
+    fn dependencies() -> Vec<ServiceInfo> {
+        Svc::<T>::dependencies()
+    }
+}
+
+/// Requests a clone of a registered string. This is equivalent to requesting
+/// [`Owned<Cow<'static, str>>`](Owned), and lets strings like connection
+/// strings or URLs be registered as constants and injected directly.
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{constant, Injector, IntoTransient, Svc};
+/// use std::borrow::Cow;
+///
+/// struct Database {
+///     url: Cow<'static, str>,
+/// }
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(constant(Cow::Borrowed("postgres://localhost")));
+/// builder.provide((|url| Database { url }).transient());
+///
+/// let injector = builder.build();
+/// let database: Svc<Database> = injector.get().unwrap();
+/// assert_eq!("postgres://localhost", database.url);
+/// ```
+impl Request for Cow<'static, str> {
+    fn request(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
+        Owned::request(injector, info).map(Owned::into_inner)
+    }
+
+    fn dependencies() -> Vec<ServiceInfo> {
+        Owned::<Self>::dependencies()
+    }
+}
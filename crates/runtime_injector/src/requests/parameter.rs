@@ -1,15 +1,22 @@
-use crate::{AsAny, Service};
+use crate::{AsAny, Service, ServiceInfo};
 
 /// A parameter for configuring requested services.
 pub trait RequestParameter: Service + AsAny {
     /// Clones this parameter into a boxed trait object.
     fn clone_dyn(&self) -> Box<dyn RequestParameter>;
+
+    /// Gets the type of this parameter.
+    fn service_info(&self) -> ServiceInfo;
 }
 
 impl<T: Service + Clone + AsAny> RequestParameter for T {
     fn clone_dyn(&self) -> Box<dyn RequestParameter> {
         Box::new(self.clone())
     }
+
+    fn service_info(&self) -> ServiceInfo {
+        ServiceInfo::of::<T>()
+    }
 }
 
 impl dyn RequestParameter {
@@ -0,0 +1,164 @@
+use crate::{InjectResult, Injector, Interface, Request, RequestInfo};
+use std::{ops::Deref, rc::Rc, sync::Arc};
+
+/// Requests a service as an [`Arc<T>`], regardless of the pointer type used
+/// by [`Svc<T>`](crate::Svc).
+///
+/// This is useful for interop with APIs which require a specific pointer
+/// type. With the "arc" feature enabled, this is the same as requesting a
+/// [`Svc<T>`](crate::Svc). With the "rc" feature enabled, the service must be
+/// [`Send`] + [`Sync`], and a new [`Arc<T>`] is created from an owned service
+/// pointer. This means that the provider must be able to provide owned
+/// service pointers (for example, a transient provider), and that the
+/// returned pointer does not share its instance with any other request.
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{ExplicitArc, Injector, IntoTransient};
+/// use std::sync::Arc;
+///
+/// #[derive(Default)]
+/// struct Foo;
+///
+/// fn requires_arc(_foo: Arc<Foo>) {}
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(Foo::default.transient());
+///
+/// let injector = builder.build();
+/// let foo: ExplicitArc<Foo> = injector.get().unwrap();
+/// requires_arc(ExplicitArc::into_inner(foo));
+/// ```
+pub struct ExplicitArc<I: ?Sized + Interface + Send + Sync>(Arc<I>);
+
+impl<I: ?Sized + Interface + Send + Sync> ExplicitArc<I> {
+    /// Converts an [`ExplicitArc<T>`] into its inner pointer.
+    #[must_use]
+    pub fn into_inner(value: Self) -> Arc<I> {
+        value.0
+    }
+}
+
+impl<I: ?Sized + Interface + Send + Sync> Deref for ExplicitArc<I> {
+    type Target = Arc<I>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Requests a service as an [`Arc<T>`]. See the docs for [`ExplicitArc<T>`].
+impl<I: ?Sized + Interface + Send + Sync> Request for ExplicitArc<I> {
+    #[cfg(feature = "arc")]
+    fn request(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
+        let service: Arc<I> = injector.get_with(info)?;
+        Ok(ExplicitArc(service))
+    }
+
+    #[cfg(feature = "rc")]
+    fn request(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
+        let service: Box<I> = injector.get_with(info)?;
+        Ok(ExplicitArc(Arc::from(service)))
+    }
+}
+
+/// Requests a service as an [`Rc<T>`], regardless of the pointer type used by
+/// [`Svc<T>`](crate::Svc).
+///
+/// This is useful for interop with APIs which require a specific pointer
+/// type. With the "rc" feature enabled, this is the same as requesting a
+/// [`Svc<T>`](crate::Svc). With the "arc" feature enabled, a new [`Rc<T>`] is
+/// created from an owned service pointer. This means that the provider must be
+/// able to provide owned service pointers (for example, a transient provider),
+/// and that the returned pointer does not share its instance with any other
+/// request.
+pub struct ExplicitRc<I: ?Sized + Interface>(Rc<I>);
+
+impl<I: ?Sized + Interface> ExplicitRc<I> {
+    /// Converts an [`ExplicitRc<T>`] into its inner pointer.
+    #[must_use]
+    pub fn into_inner(value: Self) -> Rc<I> {
+        value.0
+    }
+}
+
+impl<I: ?Sized + Interface> Deref for ExplicitRc<I> {
+    type Target = Rc<I>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Requests a service as an [`Rc<T>`]. See the docs for [`ExplicitRc<T>`].
+impl<I: ?Sized + Interface> Request for ExplicitRc<I> {
+    #[cfg(feature = "rc")]
+    fn request(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
+        let service: Rc<I> = injector.get_with(info)?;
+        Ok(ExplicitRc(service))
+    }
+
+    #[cfg(feature = "arc")]
+    fn request(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
+        let service: Box<I> = injector.get_with(info)?;
+        Ok(ExplicitRc(Rc::from(service)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ExplicitArc, ExplicitRc, InjectError, Injector, IntoSingleton,
+        IntoTransient, ServiceInfo, Svc,
+    };
+
+    #[test]
+    fn explicit_pointers_can_be_requested_from_transients() {
+        let mut builder = Injector::builder();
+        builder.provide((|| 4i32).transient());
+
+        let injector = builder.build();
+        let arc: ExplicitArc<i32> = injector.get().unwrap();
+        let rc: ExplicitRc<i32> = injector.get().unwrap();
+        assert_eq!(4, **arc);
+        assert_eq!(4, **rc);
+    }
+
+    #[test]
+    fn native_explicit_pointer_shares_instance() {
+        let mut builder = Injector::builder();
+        builder.provide((|| 4i32).singleton());
+
+        let injector = builder.build();
+        let svc: Svc<i32> = injector.get().unwrap();
+
+        #[cfg(feature = "arc")]
+        let explicit = ExplicitArc::into_inner(injector.get().unwrap());
+        #[cfg(feature = "rc")]
+        let explicit = ExplicitRc::into_inner(injector.get().unwrap());
+
+        assert!(Svc::ptr_eq(&svc, &explicit));
+    }
+
+    #[test]
+    #[allow(clippy::unnecessary_literal_unwrap)]
+    fn foreign_explicit_pointer_requires_owned_provider() {
+        let mut builder = Injector::builder();
+        builder.provide((|| 4i32).singleton());
+
+        let injector = builder.build();
+
+        #[cfg(feature = "arc")]
+        let result = injector.get::<ExplicitRc<i32>>().map(|_| ());
+        #[cfg(feature = "rc")]
+        let result = injector.get::<ExplicitArc<i32>>().map(|_| ());
+
+        match result {
+            Err(InjectError::OwnedNotSupported { service_info })
+                if service_info == ServiceInfo::of::<i32>() => {}
+            Err(error) => Err(error).unwrap(),
+            Ok(()) => unreachable!("singletons can't provide owned pointers"),
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{
+    InjectError, InjectResult, Injector, Interface, Request, RequestInfo,
+    ServiceInfo, Services,
+};
+use std::{
+    fmt::{Debug, Formatter},
+    marker::PhantomData,
+};
+
+/// Requests the current number of strong references to the instance of a
+/// service stored by its provider, as reported by
+/// [`Arc::strong_count`](std::sync::Arc::strong_count) (or
+/// [`Rc::strong_count`](std::rc::Rc::strong_count) with the "rc" feature
+/// enabled). This is useful for tracking down services which are being
+/// retained unexpectedly.
+///
+/// The count includes the reference held by the provider itself, so a count
+/// of `1` means that no other references to the service exist. If the
+/// service is a singleton that hasn't been created yet, then the count is
+/// `0`. The request fails with [`InjectError::RefCountNotSupported`] if the
+/// provider doesn't store an instance of its service, like a transient
+/// provider. Like a [`Svc<T>`](crate::Svc) request, exactly one provider must
+/// be registered for the service.
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{Injector, IntoSingleton, RefCount, Svc};
+///
+/// #[derive(Default)]
+/// struct Foo;
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(Foo::default.singleton());
+///
+/// let injector = builder.build();
+/// let count: RefCount<Foo> = injector.get().unwrap();
+/// assert_eq!(0, count.get());
+///
+/// let _foo: Svc<Foo> = injector.get().unwrap();
+/// let count: RefCount<Foo> = injector.get().unwrap();
+/// assert_eq!(2, count.get());
+/// ```
+pub struct RefCount<I: ?Sized + Interface> {
+    count: usize,
+    marker: PhantomData<fn() -> I>,
+}
+
+impl<I: ?Sized + Interface> RefCount<I> {
+    /// Gets the number of strong references to the service.
+    #[must_use]
+    pub fn get(&self) -> usize {
+        self.count
+    }
+}
+
+impl<I: ?Sized + Interface> Clone for RefCount<I> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I: ?Sized + Interface> Copy for RefCount<I> {}
+
+impl<I: ?Sized + Interface> Debug for RefCount<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("RefCount").field(&self.count).finish()
+    }
+}
+
+/// Requests the strong reference count of a service. See the docs for
+/// [`RefCount<T>`].
+impl<I: ?Sized + Interface> Request for RefCount<I> {
+    fn request(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
+        let services: Services<I> = injector.get_with(info)?;
+        let service_info = ServiceInfo::of::<I>();
+        let mut counts = services.strong_counts();
+        match (counts.next(), counts.next()) {
+            (None, _) => Err(InjectError::MissingProvider { service_info }),
+            (Some(_), Some(_)) => Err(InjectError::MultipleProviders {
+                service_info,
+                providers: services.len(),
+            }),
+            (Some(count), None) => {
+                let count = count.ok_or(InjectError::RefCountNotSupported {
+                    service_info,
+                })?;
+                Ok(RefCount {
+                    count,
+                    marker: PhantomData,
+                })
+            }
+        }
+    }
+}
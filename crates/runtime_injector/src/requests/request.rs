@@ -2,6 +2,12 @@ use crate::{
     InjectError, InjectResult, Injector, Interface, RequestInfo, ServiceInfo,
     Services, Svc,
 };
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    convert::TryFrom,
+    hash::BuildHasher,
+    iter::FromIterator,
+};
 
 /// A request to an injector.
 ///
@@ -59,6 +65,18 @@ use crate::{
 pub trait Request: Sized {
     /// Performs the request to the injector.
     fn request(injector: &Injector, info: &RequestInfo) -> InjectResult<Self>;
+
+    /// Gets the services which must have a provider registered for this
+    /// request to succeed. This is used to validate an injector before it's
+    /// built (see [`InjectorBuilder::validate()`]). Requests which can succeed
+    /// without a provider for a service, like [`Option<Svc<T>>`], should not
+    /// include that service.
+    ///
+    /// [`InjectorBuilder::validate()`]: crate::InjectorBuilder::validate
+    #[must_use]
+    fn dependencies() -> Vec<ServiceInfo> {
+        Vec::new()
+    }
 }
 
 /// Requests the injector used to resolve services.
@@ -76,24 +94,19 @@ impl Request for RequestInfo {
 }
 
 /// Requests a service pointer to a service or interface. This request fails if
-/// there is not exactly one implementation of the given interface.
+/// there is not exactly one implementation of the given interface, unless
+/// one of its providers has a higher priority than the rest. See
+/// [`TypedProvider::with_priority()`] for more information.
+///
+/// [`TypedProvider::with_priority()`]: crate::TypedProvider::with_priority
 impl<I: ?Sized + Interface> Request for Svc<I> {
     fn request(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
         let mut services: Services<I> = injector.get_with(info)?;
-        if services.len() > 1 {
-            Err(InjectError::MultipleProviders {
-                service_info: ServiceInfo::of::<I>(),
-                providers: services.len(),
-            })
-        } else {
-            let service = services.get_all().next().transpose()?.ok_or(
-                InjectError::MissingProvider {
-                    service_info: ServiceInfo::of::<I>(),
-                },
-            )?;
+        services.get_single()
+    }
 
-            Ok(service)
-        }
+    fn dependencies() -> Vec<ServiceInfo> {
+        vec![ServiceInfo::of::<I>()]
     }
 }
 
@@ -103,21 +116,28 @@ impl<I: ?Sized + Interface> Request for Svc<I> {
 impl<I: ?Sized + Interface> Request for Box<I> {
     fn request(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
         let mut services: Services<I> = injector.get_with(info)?;
-        if services.len() > 1 {
+        let candidates = services.highest_priority_count();
+        if candidates > 1 {
             Err(InjectError::MultipleProviders {
                 service_info: ServiceInfo::of::<I>(),
-                providers: services.len(),
+                providers: candidates,
             })
         } else {
-            let service = services.get_all_owned().next().transpose()?.ok_or(
-                InjectError::MissingProvider {
+            let service = services
+                .get_highest_priority_owned()
+                .next()
+                .transpose()?
+                .ok_or(InjectError::MissingProvider {
                     service_info: ServiceInfo::of::<I>(),
-                },
-            )?;
+                })?;
 
             Ok(service)
         }
     }
+
+    fn dependencies() -> Vec<ServiceInfo> {
+        vec![ServiceInfo::of::<I>()]
+    }
 }
 
 /// Lazily requests all the implementations of an interface.
@@ -133,7 +153,7 @@ impl<I: ?Sized + Interface> Request for Services<I> {
 impl<I: ?Sized + Interface> Request for Vec<Svc<I>> {
     fn request(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
         let mut impls: Services<I> = injector.get_with(info)?;
-        impls.get_all().collect()
+        impls.try_all()
     }
 }
 
@@ -144,10 +164,302 @@ impl<I: ?Sized + Interface> Request for Vec<Svc<I>> {
 impl<I: ?Sized + Interface> Request for Vec<Box<I>> {
     fn request(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
         let mut impls: Services<I> = injector.get_with(info)?;
-        impls.get_all_owned().collect()
+        impls.try_all_owned()
+    }
+}
+
+/// Requests exactly `N` implementations of an interface. This works the same
+/// way as requesting a [`Vec<Svc<I>>`], except the number of implementations
+/// is part of the type. If a different number of implementations is
+/// provided, then an [`InjectError::WrongCount`] error is returned.
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{
+///     interface, Injector, IntoSingleton, Service, Svc, TypedProvider,
+/// };
+///
+/// trait Stage: Service {}
+/// interface!(dyn Stage = [Parse, Check]);
+///
+/// #[derive(Default)]
+/// struct Parse;
+/// impl Stage for Parse {}
+///
+/// #[derive(Default)]
+/// struct Check;
+/// impl Stage for Check {}
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(Parse::default.singleton().with_interface::<dyn Stage>());
+/// builder.provide(Check::default.singleton().with_interface::<dyn Stage>());
+///
+/// let injector = builder.build();
+/// let _stages: [Svc<dyn Stage>; 2] = injector.get().unwrap();
+/// assert!(injector.get::<[Svc<dyn Stage>; 3]>().is_err());
+/// ```
+impl<I: ?Sized + Interface, const N: usize> Request for [Svc<I>; N] {
+    fn request(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
+        let impls: Vec<Svc<I>> = injector.get_with(info)?;
+        into_array::<I, _, N>(impls)
+    }
+}
+
+/// Requests exactly `N` implementations of an interface as owned service
+/// pointers. See the docs for requesting [`[Svc<I>; N]`](Request) for more
+/// information.
+impl<I: ?Sized + Interface, const N: usize> Request for [Box<I>; N] {
+    fn request(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
+        let impls: Vec<Box<I>> = injector.get_with(info)?;
+        into_array::<I, _, N>(impls)
+    }
+}
+
+fn into_array<I: ?Sized + Interface, T, const N: usize>(
+    impls: Vec<T>,
+) -> InjectResult<[T; N]> {
+    <[T; N]>::try_from(impls).map_err(|impls| InjectError::WrongCount {
+        service_info: ServiceInfo::of::<I>(),
+        expected: N,
+        actual: impls.len(),
+    })
+}
+
+/// Requests all the implementations of an interface as a shared slice. This
+/// works the same way as requesting a [`Vec<Svc<I>>`], except the
+/// implementations are collected into a single service pointer, which can be
+/// cloned cheaply.
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{
+///     interface, Injector, IntoSingleton, Service, Svc, TypedProvider,
+/// };
+///
+/// trait Handler: Service {}
+/// interface!(dyn Handler = [Foo, Bar]);
+///
+/// #[derive(Default)]
+/// struct Foo;
+/// impl Handler for Foo {}
+///
+/// #[derive(Default)]
+/// struct Bar;
+/// impl Handler for Bar {}
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(Foo::default.singleton().with_interface::<dyn Handler>());
+/// builder.provide(Bar::default.singleton().with_interface::<dyn Handler>());
+///
+/// let injector = builder.build();
+/// let handlers: Svc<[Svc<dyn Handler>]> = injector.get().unwrap();
+/// let shared = handlers.clone();
+/// assert_eq!(2, shared.len());
+/// assert!(Svc::ptr_eq(&handlers, &shared));
+/// ```
+impl<I: ?Sized + Interface> Request for Svc<[Svc<I>]> {
+    fn request(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
+        let impls: Vec<Svc<I>> = injector.get_with(info)?;
+        Ok(impls.into())
+    }
+}
+
+/// Requests all the implementations of an interface, keyed by the concrete
+/// type of each implementation. This works the same way as requesting a
+/// [`Vec<Svc<I>>`], except the implementations are sorted by the names of
+/// their types, which don't change between runs. This can be used when the
+/// implementations need to be in a reproducible order. If multiple providers
+/// provide the same type, then only the last of them is included.
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{
+///     interface, Injector, IntoSingleton, Service, ServiceInfo, Svc,
+///     TypedProvider,
+/// };
+/// use std::collections::BTreeMap;
+///
+/// trait Plugin: Service {}
+/// interface!(dyn Plugin = [Zip, Audio]);
+///
+/// #[derive(Default)]
+/// struct Zip;
+/// impl Plugin for Zip {}
+///
+/// #[derive(Default)]
+/// struct Audio;
+/// impl Plugin for Audio {}
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(Zip::default.singleton().with_interface::<dyn Plugin>());
+/// builder.provide(Audio::default.singleton().with_interface::<dyn Plugin>());
+///
+/// let injector = builder.build();
+/// let plugins: BTreeMap<ServiceInfo, Svc<dyn Plugin>> =
+///     injector.get().unwrap();
+/// let names: Vec<_> = plugins.keys().map(|info| info.short_name()).collect();
+/// assert_eq!(vec!["Audio", "Zip"], names);
+/// ```
+impl<I: ?Sized + Interface> Request for BTreeMap<ServiceInfo, Svc<I>> {
+    fn request(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
+        let mut impls: Services<I> = injector.get_with(info)?;
+        impls.iter_with_info().collect()
+    }
+}
+
+/// Requests all the named implementations of an interface, sorted by name.
+/// This includes implementations which were given a name with
+/// [`TypedProvider::with_name()`] and implementations which were registered
+/// under a name with [`InjectorBuilder::provide_named()`]. Implementations
+/// without a name are not included, and if there are no named
+/// implementations, then an empty map is returned. If multiple
+/// implementations share the same name, then an
+/// [`InjectError::DuplicateName`] error is returned.
+///
+/// [`TypedProvider::with_name()`]: crate::TypedProvider::with_name
+/// [`InjectorBuilder::provide_named()`]: crate::InjectorBuilder::provide_named
+impl<I: ?Sized + Interface> Request for BTreeMap<String, Svc<I>> {
+    fn request(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
+        request_named(
+            injector,
+            info,
+            |impls| impls.get_all().collect(),
+            |impls| impls.get_all_named().collect(),
+        )
+    }
+}
+
+/// Requests all the named implementations of an interface. This works the
+/// same way as requesting a [`BTreeMap<String, Svc<I>>`], except the
+/// implementations aren't sorted.
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{
+///     interface, Injector, IntoSingleton, Service, Svc, TypedProvider,
+/// };
+/// use std::collections::HashMap;
+///
+/// trait CommandHandler: Service {}
+/// interface!(dyn CommandHandler = [Ping, Echo]);
+///
+/// #[derive(Default)]
+/// struct Ping;
+/// impl CommandHandler for Ping {}
+///
+/// #[derive(Default)]
+/// struct Echo;
+/// impl CommandHandler for Echo {}
+///
+/// let mut builder = Injector::builder();
+/// builder.provide_named(
+///     "ping",
+///     Ping::default.singleton().with_interface::<dyn CommandHandler>(),
+/// );
+/// builder.provide(
+///     Echo::default
+///         .singleton()
+///         .with_name("echo")
+///         .with_interface::<dyn CommandHandler>(),
+/// );
+///
+/// let injector = builder.build();
+/// let handlers: HashMap<String, Svc<dyn CommandHandler>> =
+///     injector.get().unwrap();
+/// assert!(handlers.contains_key("ping"));
+/// assert!(handlers.contains_key("echo"));
+/// ```
+impl<I, S> Request for HashMap<String, Svc<I>, S>
+where
+    I: ?Sized + Interface,
+    S: BuildHasher + Default,
+{
+    fn request(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
+        request_named(
+            injector,
+            info,
+            |impls| impls.get_all().collect(),
+            |impls| impls.get_all_named().collect(),
+        )
+    }
+}
+
+/// Requests all the named implementations of an interface as owned service
+/// pointers. See [`HashMap<String, Svc<I>>`] for more information. If any
+/// named provider cannot provide an owned service pointer, then an error is
+/// returned instead.
+impl<I, S> Request for HashMap<String, Box<I>, S>
+where
+    I: ?Sized + Interface,
+    S: BuildHasher + Default,
+{
+    fn request(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
+        request_named(
+            injector,
+            info,
+            |impls| impls.get_all_owned().collect(),
+            Services::get_all_named_owned,
+        )
     }
 }
 
+type NamedResults<T> = Vec<(String, InjectResult<T>)>;
+
+/// Requests each named implementation of an interface and collects them,
+/// failing if a name is used more than once.
+fn request_named<I, T, C>(
+    injector: &Injector,
+    info: &RequestInfo,
+    get_all: fn(&mut Services<I>) -> Vec<InjectResult<T>>,
+    get_all_named: fn(&mut Services<I>) -> NamedResults<T>,
+) -> InjectResult<C>
+where
+    I: ?Sized + Interface,
+    C: FromIterator<(String, T)>,
+{
+    let mut named = match injector.get_with::<Services<I>>(info) {
+        Ok(mut impls) => get_all_named(&mut impls),
+        Err(InjectError::MissingProvider { .. }) => Vec::new(),
+        Err(error) => return Err(error),
+    };
+
+    for name in injector.provider_names() {
+        let mut info = info.clone();
+        info.set_provider_name(name);
+        let mut impls = match injector.get_with::<Services<I>>(&info) {
+            Ok(impls) => impls,
+            Err(InjectError::MissingProvider { .. }) => continue,
+            Err(error) => return Err(error),
+        };
+
+        let services = get_all(&mut impls);
+        named.extend(
+            services
+                .into_iter()
+                .map(|service| (name.to_owned(), service)),
+        );
+    }
+
+    let mut seen = HashSet::new();
+    named
+        .into_iter()
+        .map(|(name, service)| {
+            if seen.insert(name.clone()) {
+                Ok((name, service?))
+            } else {
+                Err(InjectError::DuplicateName {
+                    service_info: ServiceInfo::of::<I>(),
+                    name,
+                })
+            }
+        })
+        .collect()
+}
+
 /// Tries to request a service pointer for a service or interface. If no
 /// provider has been registered for it, then returns `None`. This fails if
 /// there are multiple implementations of the given interface.
@@ -175,6 +487,61 @@ impl<I: ?Sized + Interface> Request for Option<Box<I>> {
     }
 }
 
+/// Requests a service pointer for a service or interface, capturing any error
+/// instead of failing the request. This lets a service handle a dependency
+/// which failed to activate, for example by falling back to a default
+/// implementation, while still being able to inspect why it failed. Unlike
+/// [`Option<Svc<T>>`], all errors are captured, not just missing providers.
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{
+///     InjectError, InjectResult, Injector, IntoSingleton, Svc,
+/// };
+///
+/// struct Logger;
+/// struct Server {
+///     logger: Option<Svc<Logger>>,
+/// }
+///
+/// impl Server {
+///     fn new(logger: InjectResult<Svc<Logger>>) -> Self {
+///         // Degrade gracefully if the logger isn't available
+///         match logger {
+///             Ok(logger) => Server {
+///                 logger: Some(logger),
+///             },
+///             Err(InjectError::MissingProvider { .. }) => {
+///                 Server { logger: None }
+///             }
+///             Err(error) => panic!("unexpected error: {}", error),
+///         }
+///     }
+/// }
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(Server::new.singleton());
+///
+/// let injector = builder.build();
+/// let server: Svc<Server> = injector.get().unwrap();
+/// assert!(server.logger.is_none());
+/// ```
+impl<I: ?Sized + Interface> Request for InjectResult<Svc<I>> {
+    fn request(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
+        Ok(injector.get_with(info))
+    }
+}
+
+/// Requests an owned service pointer for a service or interface, capturing
+/// any error instead of failing the request. See the docs for
+/// [`InjectResult<Svc<T>>`] requests for more information.
+impl<I: ?Sized + Interface> Request for InjectResult<Box<I>> {
+    fn request(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
+        Ok(injector.get_with(info))
+    }
+}
+
 macro_rules! impl_tuple_request {
     () => {
         impl_tuple_request!(@impl ());
@@ -195,6 +562,13 @@ macro_rules! impl_tuple_request {
                 let result = ($(injector.get_with::<$type_name>(info)?,)*);
                 Ok(result)
             }
+
+            #[allow(unused_mut)]
+            fn dependencies() -> Vec<ServiceInfo> {
+                let mut dependencies = Vec::new();
+                $(dependencies.extend($type_name::dependencies());)*
+                dependencies
+            }
         }
     };
 }
@@ -0,0 +1,125 @@
+use crate::{
+    InjectError, InjectResult, Injector, Interface, Lazy, Request, RequestInfo,
+    ServiceInfo, Services, Svc,
+};
+
+#[cfg(feature = "arc")]
+type Weak<T> = std::sync::Weak<T>;
+
+#[cfg(feature = "rc")]
+type Weak<T> = std::rc::Weak<T>;
+
+/// Requests a weak service pointer to a service or interface. This can be
+/// used to break reference cycles between services, like an event bus and a
+/// registry of its subscribers which both need to reference each other.
+///
+/// The service must be provided by a provider which holds onto its instance,
+/// like a singleton or constant provider. Otherwise, nothing would keep the
+/// service alive, and the request fails with
+/// [`InjectError::WeakNotSupported`]. Like a [`Svc<T>`] request, exactly one
+/// provider must be registered for the service.
+///
+/// If the service can't be activated yet because it (directly or indirectly)
+/// depends on the service requesting it, then the service is instead
+/// resolved the first time [`WeakSvc::upgrade()`] is called. This means that
+/// two singletons can depend on each other as long as one of them uses a
+/// [`WeakSvc<T>`]. Only cycles through a service which is being activated by
+/// the same request are deferred. Other cycles, including services which are
+/// being activated on another thread, still fail the request.
+///
+/// A deferred [`WeakSvc<T>`] holds onto the injector until
+/// it has been resolved, then releases it. If it is stored by a singleton
+/// and is never upgraded, then the injector is kept alive by that singleton,
+/// so deferred weak pointers should be upgraded at least once, for example
+/// when the application starts.
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{Injector, IntoSingleton, Svc, WeakSvc};
+///
+/// struct EventBus(WeakSvc<Registry>);
+/// struct Registry(Svc<EventBus>);
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(EventBus.singleton());
+/// builder.provide(Registry.singleton());
+///
+/// let injector = builder.build();
+/// let bus: Svc<EventBus> = injector.get().unwrap();
+/// let registry = bus.0.upgrade().unwrap();
+/// assert!(Svc::ptr_eq(&bus, &registry.0));
+/// ```
+pub struct WeakSvc<I: ?Sized + Interface> {
+    inner: WeakState<I>,
+}
+
+enum WeakState<I: ?Sized + Interface> {
+    Resolved(Weak<I>),
+    Deferred(Box<Lazy<Downgraded<I>>>),
+}
+
+impl<I: ?Sized + Interface> WeakSvc<I> {
+    /// Attempts to get a service pointer to the service. This returns `None`
+    /// if the service has been dropped. If the service couldn't be resolved
+    /// when this was requested, then it is resolved now, and `None` is
+    /// returned if that fails. See [`WeakSvc::try_upgrade()`] to get the
+    /// error instead.
+    #[must_use]
+    pub fn upgrade(&self) -> Option<Svc<I>> {
+        self.try_upgrade().ok().flatten()
+    }
+
+    /// Attempts to get a service pointer to the service. This returns
+    /// `Ok(None)` if the service has been dropped. If the service couldn't be
+    /// resolved when this was requested, then it is resolved now, and the
+    /// error is returned if that fails.
+    pub fn try_upgrade(&self) -> InjectResult<Option<Svc<I>>> {
+        match &self.inner {
+            WeakState::Resolved(weak) => Ok(weak.upgrade()),
+            WeakState::Deferred(lazy) => Ok(lazy.get()?.0.upgrade()),
+        }
+    }
+}
+
+/// Requests a weak service pointer. See the docs for [`WeakSvc<T>`].
+impl<I: ?Sized + Interface> Request for WeakSvc<I> {
+    fn request(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
+        let inner = match Downgraded::request(injector, info) {
+            Ok(Downgraded(weak)) => WeakState::Resolved(weak),
+            // The service can be resolved once the services which are being
+            // activated by this request have been activated
+            Err(InjectError::CycleDetected { cycle, .. })
+                if cycle
+                    .iter()
+                    .any(|service| info.service_path().contains(service)) =>
+            {
+                WeakState::Deferred(Box::new(injector.get_with(info)?))
+            }
+            Err(error) => return Err(error),
+        };
+
+        Ok(WeakSvc { inner })
+    }
+
+    fn dependencies() -> Vec<ServiceInfo> {
+        vec![ServiceInfo::of::<I>()]
+    }
+}
+
+/// A weak pointer to a service which is kept alive by its provider.
+struct Downgraded<I: ?Sized + Interface>(Weak<I>);
+
+impl<I: ?Sized + Interface> Request for Downgraded<I> {
+    fn request(injector: &Injector, info: &RequestInfo) -> InjectResult<Self> {
+        let mut services: Services<I> = injector.get_with(info)?;
+        if services.strong_counts().any(|count| count.is_none()) {
+            return Err(InjectError::WeakNotSupported {
+                service_info: ServiceInfo::of::<I>(),
+            });
+        }
+
+        let service = services.get_single()?;
+        Ok(Downgraded(Svc::downgrade(&service)))
+    }
+}
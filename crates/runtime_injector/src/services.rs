@@ -1,19 +1,69 @@
 mod conditional;
 mod constant;
+mod decorated;
+mod description;
+mod disposed;
+mod dyn_factory;
+mod error;
+mod fallback;
 mod fallible;
 mod func;
+mod inspect;
+mod instrumented;
 mod interface;
+mod lifetime;
+mod many;
+mod map_err;
+mod mapped;
+mod memoized;
+mod named;
+mod once;
+mod parameterized;
+mod per_thread;
+mod pooled;
+mod priority;
 mod providers;
+mod scoped;
 mod service;
+mod shared;
 mod singleton;
+#[cfg(feature = "unstable-pointer")]
+mod svc_pointer;
+mod tagged;
 mod transient;
+mod usage;
 
 pub use conditional::*;
 pub use constant::*;
+pub(crate) use decorated::*;
+pub use description::*;
+pub(crate) use disposed::*;
+pub(crate) use dyn_factory::*;
+pub use error::*;
+pub use fallback::*;
 pub use fallible::*;
 pub use func::*;
+pub use inspect::*;
+pub(crate) use instrumented::*;
 pub use interface::*;
+pub use lifetime::*;
+pub use many::*;
+pub use map_err::*;
+pub use mapped::*;
+pub use memoized::*;
+pub use named::*;
+pub use once::*;
+pub(crate) use parameterized::*;
+pub use per_thread::*;
+pub use pooled::*;
+pub use priority::*;
 pub use providers::*;
+pub use scoped::*;
 pub use service::*;
+pub use shared::*;
 pub use singleton::*;
+#[cfg(feature = "unstable-pointer")]
+pub use svc_pointer::*;
+pub use tagged::*;
 pub use transient::*;
+pub(crate) use usage::*;
@@ -1,7 +1,78 @@
 use crate::{
-    InjectError, InjectResult, Injector, RequestInfo, Service, ServiceInfo,
-    Svc, TypedProvider,
+    InjectError, InjectResult, Injector, Interface, Lifetime, RequestInfo,
+    RequestParameter, Service, ServiceInfo, Svc, TypedProvider,
 };
+use std::{marker::PhantomData, time::Duration};
+
+/// A condition which determines whether a [`ConditionalProvider`] provides
+/// its service. This trait is automatically implemented for all functions
+/// which accept an injector and request info and return a [`bool`].
+pub trait Condition: Service {
+    /// Checks whether the condition is met for a request.
+    fn is_met(&self, injector: &Injector, request_info: &RequestInfo) -> bool;
+}
+
+impl<F> Condition for F
+where
+    F: Service + Fn(&Injector, &RequestInfo) -> bool,
+{
+    fn is_met(&self, injector: &Injector, request_info: &RequestInfo) -> bool {
+        self(injector, request_info)
+    }
+}
+
+/// A [`Condition`] which checks the value of a request parameter. See
+/// [`WithCondition::with_condition_param()`] for more information.
+pub struct ParamCondition<T, F>
+where
+    T: RequestParameter,
+    F: Service + Fn(&T) -> bool,
+{
+    key: String,
+    predicate: F,
+    marker: PhantomData<fn(&T) -> bool>,
+}
+
+impl<T, F> Condition for ParamCondition<T, F>
+where
+    T: RequestParameter,
+    F: Service + Fn(&T) -> bool,
+{
+    fn is_met(&self, _injector: &Injector, request_info: &RequestInfo) -> bool {
+        request_info
+            .get_parameter(&self.key)
+            .and_then(|parameter| parameter.downcast_ref::<T>())
+            .is_some_and(|value| (self.predicate)(value))
+    }
+}
+
+/// A [`Condition`] which checks whether any providers are registered for a
+/// service. See [`WithCondition::when_registered()`] for more information.
+pub struct RegisteredCondition<I: ?Sized + Interface> {
+    marker: PhantomData<fn() -> Box<I>>,
+}
+
+impl<I: ?Sized + Interface> Condition for RegisteredCondition<I> {
+    fn is_met(&self, injector: &Injector, request_info: &RequestInfo) -> bool {
+        // Providers which are in use are still registered
+        !matches!(
+            injector.count_providers(ServiceInfo::of::<I>(), request_info),
+            Ok(0)
+        )
+    }
+}
+
+/// A [`Condition`] which checks whether a feature is enabled for a request.
+/// See [`WithCondition::gated_by()`] for more information.
+pub struct FeatureCondition {
+    feature: String,
+}
+
+impl Condition for FeatureCondition {
+    fn is_met(&self, _injector: &Injector, request_info: &RequestInfo) -> bool {
+        request_info.features().is_enabled(&self.feature)
+    }
+}
 
 /// A [`TypedProvider`] which conditionally provides its service. If the
 /// condition is not met, then the provider is skipped during resolution.
@@ -11,7 +82,7 @@ use crate::{
 pub struct ConditionalProvider<P, F>
 where
     P: TypedProvider,
-    F: Service + Fn(&Injector, &RequestInfo) -> bool,
+    F: Condition,
 {
     inner: P,
     condition: F,
@@ -20,7 +91,7 @@ where
 impl<P, F> TypedProvider for ConditionalProvider<P, F>
 where
     P: TypedProvider,
-    F: Service + Fn(&Injector, &RequestInfo) -> bool,
+    F: Condition,
 {
     type Result = P::Result;
 
@@ -30,7 +101,7 @@ where
         injector: &Injector,
         request_info: &RequestInfo,
     ) -> InjectResult<Svc<Self::Result>> {
-        if (self.condition)(injector, request_info) {
+        if self.condition.is_met(injector, request_info) {
             self.inner.provide_typed(injector, request_info)
         } else {
             Err(InjectError::ConditionsNotMet {
@@ -45,7 +116,7 @@ where
         injector: &Injector,
         request_info: &RequestInfo,
     ) -> InjectResult<Box<Self::Result>> {
-        if (self.condition)(injector, request_info) {
+        if self.condition.is_met(injector, request_info) {
             self.inner.provide_owned_typed(injector, request_info)
         } else {
             Err(InjectError::ConditionsNotMet {
@@ -53,6 +124,46 @@ where
             })
         }
     }
+
+    #[inline]
+    fn strong_count_typed(&self) -> Option<usize> {
+        self.inner.strong_count_typed()
+    }
+
+    #[inline]
+    fn name_typed(&self) -> Option<&str> {
+        self.inner.name_typed()
+    }
+
+    #[inline]
+    fn tags_typed(&self) -> Vec<&str> {
+        self.inner.tags_typed()
+    }
+
+    #[inline]
+    fn priority_typed(&self) -> i32 {
+        self.inner.priority_typed()
+    }
+
+    #[inline]
+    fn build_time_typed(&self) -> Option<Duration> {
+        self.inner.build_time_typed()
+    }
+
+    #[inline]
+    fn is_available_typed(
+        &self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> bool {
+        self.condition.is_met(injector, request_info)
+            && self.inner.is_available_typed(injector, request_info)
+    }
+
+    #[inline]
+    fn lifetime_typed(&self) -> Lifetime {
+        self.inner.lifetime_typed()
+    }
 }
 
 /// Defines a conversion into a conditional provider. This trait is
@@ -82,6 +193,124 @@ pub trait WithCondition: TypedProvider {
     fn with_condition<F>(self, condition: F) -> ConditionalProvider<Self, F>
     where
         F: Service + Fn(&Injector, &RequestInfo) -> bool;
+
+    /// Creates a conditional provider which checks the value of a request
+    /// parameter. The provider is skipped if the parameter is not set, if it
+    /// is the wrong type, or if the predicate returns `false`. The predicate
+    /// is only called if the parameter is set to a value of the right type.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{Injector, IntoSingleton, Svc, WithCondition};
+    ///
+    /// #[derive(Default)]
+    /// struct NewUi;
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(
+    ///     NewUi::default
+    ///         .singleton()
+    ///         .with_condition_param::<bool, _>("feature.new_ui", |v| *v),
+    /// );
+    ///
+    /// let injector = builder.build();
+    /// let new_ui: Option<Svc<NewUi>> = injector.get().unwrap();
+    /// assert!(new_ui.is_none());
+    /// ```
+    #[must_use]
+    fn with_condition_param<T, F>(
+        self,
+        key: &str,
+        predicate: F,
+    ) -> ConditionalProvider<Self, ParamCondition<T, F>>
+    where
+        T: RequestParameter,
+        F: Service + Fn(&T) -> bool;
+
+    /// Creates a conditional provider which only provides its service if at
+    /// least one provider is registered for another service or interface.
+    /// The other service is never activated by this check, so its providers
+    /// may still fail or have conditions of their own which aren't met.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     interface, Injector, IntoSingleton, Service, Svc, WithCondition,
+    /// };
+    ///
+    /// trait MetricsSink: Service {}
+    /// interface!(dyn MetricsSink = []);
+    ///
+    /// #[derive(Default)]
+    /// struct MetricsExporter;
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(
+    ///     MetricsExporter::default
+    ///         .singleton()
+    ///         .when_registered::<dyn MetricsSink>(),
+    /// );
+    ///
+    /// // No metrics sink is registered, so the exporter is skipped
+    /// let injector = builder.build();
+    /// let exporter: Option<Svc<MetricsExporter>> = injector.get().unwrap();
+    /// assert!(exporter.is_none());
+    /// ```
+    #[must_use]
+    fn when_registered<I: ?Sized + Interface>(
+        self,
+    ) -> ConditionalProvider<Self, RegisteredCondition<I>>;
+
+    /// Creates a conditional provider which only provides its service if a
+    /// feature is enabled for the request. Features are usually enabled for
+    /// every request through the injector's root request info, and can be
+    /// toggled at startup without recompiling the application. See
+    /// [`FeatureSet`](crate::FeatureSet) for more information.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     interface, Injector, IntoSingleton, Service, Svc, TypedProvider,
+    ///     WithCondition,
+    /// };
+    ///
+    /// trait Pipeline: Service {}
+    /// interface!(dyn Pipeline = [OldPipeline, NewPipeline]);
+    ///
+    /// #[derive(Default)]
+    /// struct OldPipeline;
+    /// impl Pipeline for OldPipeline {}
+    ///
+    /// #[derive(Default)]
+    /// struct NewPipeline;
+    /// impl Pipeline for NewPipeline {}
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(
+    ///     NewPipeline::default
+    ///         .singleton()
+    ///         .gated_by("new_pipeline")
+    ///         .with_interface::<dyn Pipeline>(),
+    /// );
+    /// builder.provide(
+    ///     OldPipeline::default
+    ///         .singleton()
+    ///         .with_interface::<dyn Pipeline>(),
+    /// );
+    /// builder.root_info_mut().features_mut().enable("new_pipeline");
+    ///
+    /// let injector = builder.build();
+    /// let pipelines: Vec<Svc<dyn Pipeline>> = injector.get().unwrap();
+    /// assert_eq!(2, pipelines.len());
+    /// ```
+    #[must_use]
+    fn gated_by(
+        self,
+        feature: &str,
+    ) -> ConditionalProvider<Self, FeatureCondition>;
 }
 
 impl<P> WithCondition for P
@@ -98,4 +327,49 @@ where
             inner: self,
         }
     }
+
+    #[inline]
+    fn with_condition_param<T, F>(
+        self,
+        key: &str,
+        predicate: F,
+    ) -> ConditionalProvider<Self, ParamCondition<T, F>>
+    where
+        T: RequestParameter,
+        F: Service + Fn(&T) -> bool,
+    {
+        ConditionalProvider {
+            condition: ParamCondition {
+                key: key.to_owned(),
+                predicate,
+                marker: PhantomData,
+            },
+            inner: self,
+        }
+    }
+
+    #[inline]
+    fn when_registered<I: ?Sized + Interface>(
+        self,
+    ) -> ConditionalProvider<Self, RegisteredCondition<I>> {
+        ConditionalProvider {
+            condition: RegisteredCondition {
+                marker: PhantomData,
+            },
+            inner: self,
+        }
+    }
+
+    #[inline]
+    fn gated_by(
+        self,
+        feature: &str,
+    ) -> ConditionalProvider<Self, FeatureCondition> {
+        ConditionalProvider {
+            condition: FeatureCondition {
+                feature: feature.to_owned(),
+            },
+            inner: self,
+        }
+    }
 }
@@ -1,4 +1,7 @@
-use crate::{InjectResult, Injector, RequestInfo, Service, Svc, TypedProvider};
+use crate::{
+    InjectResult, Injector, Lifetime, RequestInfo, Service, Svc, SvcLock,
+    TypedProvider,
+};
 
 /// A provider which returns a constant, predetermined value. Note that this is
 /// technically a singleton service in that it does not recreate the value each
@@ -21,6 +24,13 @@ where
             result: Svc::new(value),
         }
     }
+
+    /// Creates a new [`ConstantProvider`] which provides an existing service
+    /// pointer.
+    #[must_use]
+    pub fn from_svc(result: Svc<R>) -> Self {
+        ConstantProvider { result }
+    }
 }
 
 impl<R> TypedProvider for ConstantProvider<R>
@@ -36,6 +46,14 @@ where
     ) -> InjectResult<Svc<Self::Result>> {
         Ok(self.result.clone())
     }
+
+    fn strong_count_typed(&self) -> Option<usize> {
+        Some(Svc::strong_count(&self.result))
+    }
+
+    fn lifetime_typed(&self) -> Lifetime {
+        Lifetime::Constant
+    }
 }
 
 impl<T: Service> From<T> for ConstantProvider<T> {
@@ -44,6 +62,62 @@ impl<T: Service> From<T> for ConstantProvider<T> {
     }
 }
 
+/// A provider which returns a constant, predetermined value that can be
+/// cloned. This works the same way as a [`ConstantProvider`], except owned
+/// service pointers can be provided as well by cloning the value. See
+/// [`constant_cloneable()`] for more information.
+pub struct CloneableConstantProvider<R>
+where
+    R: Service + Clone,
+{
+    result: Svc<R>,
+}
+
+impl<R> CloneableConstantProvider<R>
+where
+    R: Service + Clone,
+{
+    /// Creates a new [`CloneableConstantProvider`] using a predetermined
+    /// value.
+    #[must_use]
+    pub fn new(value: R) -> Self {
+        CloneableConstantProvider {
+            result: Svc::new(value),
+        }
+    }
+}
+
+impl<R> TypedProvider for CloneableConstantProvider<R>
+where
+    R: Service + Clone,
+{
+    type Result = R;
+
+    fn provide_typed(
+        &mut self,
+        _injector: &Injector,
+        _request_info: &RequestInfo,
+    ) -> InjectResult<Svc<Self::Result>> {
+        Ok(self.result.clone())
+    }
+
+    fn provide_owned_typed(
+        &mut self,
+        _injector: &Injector,
+        _request_info: &RequestInfo,
+    ) -> InjectResult<Box<Self::Result>> {
+        Ok(Box::new(R::clone(&self.result)))
+    }
+
+    fn strong_count_typed(&self) -> Option<usize> {
+        Some(Svc::strong_count(&self.result))
+    }
+
+    fn lifetime_typed(&self) -> Lifetime {
+        Lifetime::Constant
+    }
+}
+
 /// Create a provider from a constant value.
 ///
 /// While the service itself will never be exposed through a mutable reference,
@@ -98,3 +172,96 @@ impl<T: Service> From<T> for ConstantProvider<T> {
 pub fn constant<T: Service>(value: T) -> ConstantProvider<T> {
     ConstantProvider::new(value)
 }
+
+/// Create a provider from a constant value which can be cloned. Requests for
+/// service pointers return the same instance, like [`constant()`], while
+/// requests for owned service pointers, like [`Box<T>`], return a clone of
+/// the value. Constant providers created with [`constant()`] can't provide
+/// owned service pointers.
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{constant_cloneable, Injector, Svc};
+///
+/// #[derive(Clone)]
+/// struct Config {
+///     port: u16,
+/// }
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(constant_cloneable(Config { port: 8080 }));
+///
+/// let injector = builder.build();
+/// let shared: Svc<Config> = injector.get().unwrap();
+/// let mut owned: Box<Config> = injector.get().unwrap();
+/// owned.port = 80;
+///
+/// assert_eq!(8080, shared.port);
+/// assert_eq!(80, owned.port);
+/// ```
+pub fn constant_cloneable<T: Service + Clone>(
+    value: T,
+) -> CloneableConstantProvider<T> {
+    CloneableConstantProvider::new(value)
+}
+
+/// Create a provider from an existing service pointer. Unlike [`constant()`],
+/// the value is not wrapped in a new [`Svc<T>`]. Instead, each request
+/// returns a clone of the given pointer. This can be used to share an
+/// instance between multiple injectors.
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{constant_svc, Injector, IntoSingleton, Svc};
+///
+/// #[derive(Default)]
+/// struct Foo;
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(Foo::default.singleton());
+/// let injector1 = builder.build();
+/// let foo1: Svc<Foo> = injector1.get().unwrap();
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(constant_svc(foo1.clone()));
+/// let injector2 = builder.build();
+/// let foo2: Svc<Foo> = injector2.get().unwrap();
+///
+/// assert!(Svc::ptr_eq(&foo1, &foo2));
+/// ```
+pub fn constant_svc<T: Service>(value: Svc<T>) -> ConstantProvider<T> {
+    ConstantProvider::from_svc(value)
+}
+
+/// Create a provider from a constant value which can be mutated. The value is
+/// wrapped in a [`SvcLock<T>`] and can be requested as a [`SvcMut<T>`], which
+/// hides whether the lock is a [`Mutex<T>`](std::sync::Mutex) or a
+/// [`RefCell<T>`](std::cell::RefCell) from the services which use it.
+///
+/// [`SvcMut<T>`]: crate::SvcMut
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{constant_mut, Injector, SvcMut};
+///
+/// struct Counter(i32);
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(constant_mut(Counter(0)));
+///
+/// let injector = builder.build();
+/// let counter: SvcMut<Counter> = injector.get().unwrap();
+/// counter.with(|counter| counter.0 += 1);
+///
+/// let counter: SvcMut<Counter> = injector.get().unwrap();
+/// assert_eq!(1, counter.with(|counter| counter.0));
+/// ```
+pub fn constant_mut<T: Service>(value: T) -> ConstantProvider<SvcLock<T>>
+where
+    SvcLock<T>: Service,
+{
+    constant(SvcLock::new(value))
+}
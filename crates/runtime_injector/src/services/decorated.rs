@@ -0,0 +1,234 @@
+use crate::{
+    DynSvc, InjectError, InjectResult, Injector, Interface, Lifetime,
+    OwnedDynSvc, Provider, ProviderMap, RequestInfo, Service, ServiceInfo, Svc,
+};
+use std::{marker::PhantomData, time::Duration};
+
+/// A service which has been decorated. Since the decorated service may not be
+/// one of the implementations the interface knows how to downcast to, the
+/// service pointer is stored as-is instead.
+struct Decorated<I: ?Sized + Interface>(Svc<I>);
+
+/// An owned service which is stored as-is for the same reason as
+/// [`Decorated`].
+struct DecoratedOwned<I: ?Sized + Interface>(Box<I>);
+
+/// Stores a service pointer of an interface as a dynamic service pointer
+/// without needing to know its concrete type.
+pub(crate) fn wrap_service<I: ?Sized + Interface>(service: Svc<I>) -> DynSvc {
+    // Concrete services are stored as-is so they can still be downcast
+    I::try_into_dyn(service)
+        .unwrap_or_else(|service| Svc::new(Decorated(service)))
+}
+
+/// Stores an owned service of an interface as an owned dynamic service
+/// without needing to know its concrete type.
+pub(crate) fn wrap_owned_service<I: ?Sized + Interface>(
+    service: Box<I>,
+) -> OwnedDynSvc {
+    I::try_into_dyn_owned(service)
+        .unwrap_or_else(|service| Box::new(DecoratedOwned(service)))
+}
+
+/// Downcasts a dynamic service pointer into a service pointer of an
+/// interface, including services which have been decorated.
+pub(crate) fn downcast_service<I: ?Sized + Interface>(
+    service: DynSvc,
+) -> InjectResult<Svc<I>> {
+    match service.downcast::<Decorated<I>>() {
+        Ok(decorated) => Ok(decorated.0.clone()),
+        Err(service) => I::downcast(service),
+    }
+}
+
+/// Downcasts an owned dynamic service into an owned service of an interface,
+/// including services which were stored as-is.
+pub(crate) fn downcast_owned_service<I: ?Sized + Interface>(
+    service: OwnedDynSvc,
+) -> InjectResult<Box<I>> {
+    match service.downcast::<DecoratedOwned<I>>() {
+        Ok(decorated) => Ok(decorated.0),
+        Err(service) => I::downcast_owned(service),
+    }
+}
+
+/// Wraps each provider of a service with a decorator.
+pub(crate) struct ProviderDecorator {
+    service_info: ServiceInfo,
+    wrapper: Box<dyn WrapProvider>,
+}
+
+impl ProviderDecorator {
+    pub fn new<I, F>(decorator: F) -> Self
+    where
+        I: ?Sized + Interface,
+        F: Service
+            + Fn(Svc<I>, &Injector, &RequestInfo) -> InjectResult<Svc<I>>,
+    {
+        let decorator = Svc::new(decorator);
+        let wrapper = move |inner| {
+            Box::new(DecoratedProvider::<I, F> {
+                inner,
+                decorator: decorator.clone(),
+                cached: Vec::new(),
+                marker: PhantomData,
+            }) as Box<dyn Provider>
+        };
+
+        ProviderDecorator {
+            service_info: ServiceInfo::of::<I>(),
+            wrapper: Box::new(wrapper),
+        }
+    }
+
+    /// Decorates each provider of the service in a provider map.
+    pub fn apply(&self, provider_map: &mut ProviderMap) {
+        if let Some(Some(providers)) = provider_map.get_mut(&self.service_info)
+        {
+            *providers = std::mem::take(providers)
+                .into_iter()
+                .map(|provider| self.wrapper.wrap(provider))
+                .collect();
+        }
+    }
+}
+
+trait WrapProvider: Service {
+    fn wrap(&self, provider: Box<dyn Provider>) -> Box<dyn Provider>;
+}
+
+impl<F> WrapProvider for F
+where
+    F: Service + Fn(Box<dyn Provider>) -> Box<dyn Provider>,
+{
+    fn wrap(&self, provider: Box<dyn Provider>) -> Box<dyn Provider> {
+        self(provider)
+    }
+}
+
+/// Decorates the services created by another provider. If the inner provider
+/// stores its instances, then each instance is only decorated once.
+struct DecoratedProvider<I, F>
+where
+    I: ?Sized + Interface,
+    F: Service + Fn(Svc<I>, &Injector, &RequestInfo) -> InjectResult<Svc<I>>,
+{
+    inner: Box<dyn Provider>,
+    decorator: Svc<F>,
+    cached: Vec<(DynSvc, DynSvc)>,
+    marker: PhantomData<fn() -> I>,
+}
+
+impl<I, F> DecoratedProvider<I, F>
+where
+    I: ?Sized + Interface,
+    F: Service + Fn(Svc<I>, &Injector, &RequestInfo) -> InjectResult<Svc<I>>,
+{
+    fn decorate(
+        &mut self,
+        service: DynSvc,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<DynSvc> {
+        if let Some((_, decorated)) = self
+            .cached
+            .iter()
+            .find(|(original, _)| Svc::ptr_eq(original, &service))
+        {
+            return Ok(decorated.clone());
+        }
+
+        let decorated = (self.decorator)(
+            downcast_service(service.clone())?,
+            injector,
+            request_info,
+        )?;
+        let decorated = wrap_service(decorated);
+        if self.inner.strong_count().is_some() {
+            self.cached.push((service, decorated.clone()));
+        }
+
+        Ok(decorated)
+    }
+}
+
+impl<I, F> Provider for DecoratedProvider<I, F>
+where
+    I: ?Sized + Interface,
+    F: Service + Fn(Svc<I>, &Injector, &RequestInfo) -> InjectResult<Svc<I>>,
+{
+    fn result(&self) -> ServiceInfo {
+        self.inner.result()
+    }
+
+    fn provide(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<DynSvc> {
+        let service = self.inner.provide(injector, request_info)?;
+        self.decorate(service, injector, request_info)
+    }
+
+    fn provide_owned(
+        &mut self,
+        _injector: &Injector,
+        _request_info: &RequestInfo,
+    ) -> InjectResult<OwnedDynSvc> {
+        Err(InjectError::OwnedNotSupported {
+            service_info: self.result(),
+        })
+    }
+
+    fn provide_all(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Vec<DynSvc>> {
+        self.inner
+            .provide_all(injector, request_info)?
+            .into_iter()
+            .map(|service| self.decorate(service, injector, request_info))
+            .collect()
+    }
+
+    fn strong_count(&self) -> Option<usize> {
+        self.inner.strong_count()
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.inner.name()
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        self.inner.tags()
+    }
+
+    fn priority(&self) -> i32 {
+        self.inner.priority()
+    }
+
+    fn build_time(&self) -> Option<Duration> {
+        self.inner.build_time()
+    }
+
+    fn dependencies(&self) -> Vec<ServiceInfo> {
+        self.inner.dependencies()
+    }
+
+    fn is_available(
+        &self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> bool {
+        self.inner.is_available(injector, request_info)
+    }
+
+    fn lifetime(&self) -> Lifetime {
+        self.inner.lifetime()
+    }
+
+    fn implementation(&self) -> ServiceInfo {
+        self.inner.implementation()
+    }
+}
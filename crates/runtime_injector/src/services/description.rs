@@ -0,0 +1,30 @@
+use crate::{Lifetime, ServiceInfo};
+
+/// Describes a provider registered in an injector. See
+/// [`Injector::describe()`](crate::Injector::describe) for more information.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ServiceDescription {
+    /// The concrete type of the service created by the provider.
+    pub service_info: ServiceInfo,
+    /// How long the instances created by the provider live.
+    pub lifetime: Lifetime,
+    /// The interface the provider is registered as, if the service is not
+    /// registered as its concrete type.
+    pub interface: Option<ServiceInfo>,
+}
+
+/// Describes a provider registered for a service or interface, including the
+/// name and tags it was registered with. See
+/// [`Injector::provider_metadata()`](crate::Injector::provider_metadata) for
+/// more information.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ProviderMeta {
+    /// The concrete type of the service created by the provider.
+    pub service_info: ServiceInfo,
+    /// The name the provider was registered under, if any.
+    pub name: Option<String>,
+    /// The tags assigned to the provider.
+    pub tags: Vec<String>,
+    /// How long the instances created by the provider live.
+    pub lifetime: Lifetime,
+}
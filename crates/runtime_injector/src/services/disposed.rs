@@ -0,0 +1,272 @@
+use crate::{
+    downcast_service, DynSvc, InjectResult, Injector, Interface, Lifetime,
+    MapContainer, MapContainerEx, OwnedDynSvc, Provider, ProviderMap,
+    RequestInfo, Service, ServiceInfo, Svc,
+};
+use std::{marker::PhantomData, time::Duration};
+
+/// The instances which should be disposed of when an injector is dropped,
+/// in the order they were created.
+#[derive(Clone)]
+pub(crate) struct DisposalQueue {
+    pending: MapContainer<Vec<Box<dyn PendingDisposal>>>,
+}
+
+impl DisposalQueue {
+    pub fn new() -> Self {
+        DisposalQueue {
+            pending: MapContainerEx::new(Vec::new()),
+        }
+    }
+
+    fn push(&self, disposal: Box<dyn PendingDisposal>) {
+        self.pending
+            .with_inner_mut(|pending| pending.push(disposal));
+    }
+
+    /// Disposes of each instance in the queue in the reverse order that they
+    /// were created.
+    fn dispose_all(&self) {
+        // Disposers may use the queue, so it must not be borrowed while they
+        // are running
+        let pending = self.pending.with_inner_mut(std::mem::take);
+        for disposal in pending.into_iter().rev() {
+            disposal.dispose();
+        }
+    }
+}
+
+impl Default for DisposalQueue {
+    fn default() -> Self {
+        DisposalQueue::new()
+    }
+}
+
+/// Disposes of the instances in a queue when dropped. Each injector which
+/// shares providers holds a pointer to the same guard so that instances are
+/// only disposed of once the last of those injectors is dropped.
+pub(crate) struct DisposeOnDrop(DisposalQueue);
+
+impl DisposeOnDrop {
+    pub fn new(queue: DisposalQueue) -> Self {
+        DisposeOnDrop(queue)
+    }
+}
+
+impl Default for DisposeOnDrop {
+    fn default() -> Self {
+        DisposeOnDrop::new(DisposalQueue::new())
+    }
+}
+
+impl Drop for DisposeOnDrop {
+    fn drop(&mut self) {
+        self.0.dispose_all();
+    }
+}
+
+trait PendingDisposal: Service {
+    fn dispose(self: Box<Self>);
+}
+
+struct Pending<I, F>
+where
+    I: ?Sized + Interface,
+    F: Service + Fn(Svc<I>),
+{
+    service: Svc<I>,
+    disposer: Svc<F>,
+}
+
+impl<I, F> PendingDisposal for Pending<I, F>
+where
+    I: ?Sized + Interface,
+    F: Service + Fn(Svc<I>),
+{
+    fn dispose(self: Box<Self>) {
+        (self.disposer)(self.service);
+    }
+}
+
+/// Wraps each provider of a service so that the instances it stores are
+/// disposed of when the injector is dropped.
+pub(crate) struct ProviderDisposer {
+    service_info: ServiceInfo,
+    wrapper: Box<dyn WrapProvider>,
+}
+
+impl ProviderDisposer {
+    pub fn new<I, F>(disposer: F) -> Self
+    where
+        I: ?Sized + Interface,
+        F: Service + Fn(Svc<I>),
+    {
+        let disposer = Svc::new(disposer);
+        let wrapper = move |inner, queue: &DisposalQueue| {
+            Box::new(DisposingProvider::<I, F> {
+                inner,
+                disposer: disposer.clone(),
+                queue: queue.clone(),
+                tracked: Vec::new(),
+                marker: PhantomData,
+            }) as Box<dyn Provider>
+        };
+
+        ProviderDisposer {
+            service_info: ServiceInfo::of::<I>(),
+            wrapper: Box::new(wrapper),
+        }
+    }
+
+    /// Wraps each provider of the service in a provider map.
+    pub fn apply(&self, provider_map: &mut ProviderMap, queue: &DisposalQueue) {
+        if let Some(Some(providers)) = provider_map.get_mut(&self.service_info)
+        {
+            *providers = std::mem::take(providers)
+                .into_iter()
+                .map(|provider| self.wrapper.wrap(provider, queue))
+                .collect();
+        }
+    }
+}
+
+trait WrapProvider: Service {
+    fn wrap(
+        &self,
+        provider: Box<dyn Provider>,
+        queue: &DisposalQueue,
+    ) -> Box<dyn Provider>;
+}
+
+impl<F> WrapProvider for F
+where
+    F: Service + Fn(Box<dyn Provider>, &DisposalQueue) -> Box<dyn Provider>,
+{
+    fn wrap(
+        &self,
+        provider: Box<dyn Provider>,
+        queue: &DisposalQueue,
+    ) -> Box<dyn Provider> {
+        self(provider, queue)
+    }
+}
+
+/// Queues the instances stored by another provider to be disposed of. Only
+/// providers which store their instances, like singletons, have their
+/// instances disposed of.
+struct DisposingProvider<I, F>
+where
+    I: ?Sized + Interface,
+    F: Service + Fn(Svc<I>),
+{
+    inner: Box<dyn Provider>,
+    disposer: Svc<F>,
+    queue: DisposalQueue,
+    tracked: Vec<DynSvc>,
+    marker: PhantomData<fn() -> I>,
+}
+
+impl<I, F> DisposingProvider<I, F>
+where
+    I: ?Sized + Interface,
+    F: Service + Fn(Svc<I>),
+{
+    fn track(&mut self, service: &DynSvc) -> InjectResult<()> {
+        if self.inner.strong_count().is_none()
+            || self
+                .tracked
+                .iter()
+                .any(|tracked| Svc::ptr_eq(tracked, service))
+        {
+            return Ok(());
+        }
+
+        self.queue.push(Box::new(Pending {
+            service: downcast_service::<I>(service.clone())?,
+            disposer: self.disposer.clone(),
+        }));
+        self.tracked.push(service.clone());
+        Ok(())
+    }
+}
+
+impl<I, F> Provider for DisposingProvider<I, F>
+where
+    I: ?Sized + Interface,
+    F: Service + Fn(Svc<I>),
+{
+    fn result(&self) -> ServiceInfo {
+        self.inner.result()
+    }
+
+    fn provide(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<DynSvc> {
+        let service = self.inner.provide(injector, request_info)?;
+        self.track(&service)?;
+        Ok(service)
+    }
+
+    fn provide_owned(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<OwnedDynSvc> {
+        self.inner.provide_owned(injector, request_info)
+    }
+
+    fn provide_all(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Vec<DynSvc>> {
+        let services = self.inner.provide_all(injector, request_info)?;
+        for service in &services {
+            self.track(service)?;
+        }
+
+        Ok(services)
+    }
+
+    fn strong_count(&self) -> Option<usize> {
+        self.inner.strong_count()
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.inner.name()
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        self.inner.tags()
+    }
+
+    fn priority(&self) -> i32 {
+        self.inner.priority()
+    }
+
+    fn build_time(&self) -> Option<Duration> {
+        self.inner.build_time()
+    }
+
+    fn dependencies(&self) -> Vec<ServiceInfo> {
+        self.inner.dependencies()
+    }
+
+    fn is_available(
+        &self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> bool {
+        self.inner.is_available(injector, request_info)
+    }
+
+    fn lifetime(&self) -> Lifetime {
+        self.inner.lifetime()
+    }
+
+    fn implementation(&self) -> ServiceInfo {
+        self.inner.implementation()
+    }
+}
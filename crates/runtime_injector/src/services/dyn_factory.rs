@@ -0,0 +1,72 @@
+use crate::{
+    wrap_owned_service, wrap_service, DynSvc, InjectResult, Injector,
+    Interface, Lifetime, OwnedDynSvc, Provider, RequestInfo, ServiceFactory,
+    ServiceInfo, Svc,
+};
+use std::marker::PhantomData;
+
+/// Provides an interface using a factory which returns the interface's trait
+/// object directly. See [`InjectorBuilder::provide_dyn()`] for more
+/// information.
+///
+/// [`InjectorBuilder::provide_dyn()`]: crate::InjectorBuilder::provide_dyn
+pub(crate) struct DynFactoryProvider<I, D, F>
+where
+    I: ?Sized + Interface,
+    D: 'static,
+    F: ServiceFactory<D, Result = Box<I>>,
+{
+    factory: F,
+    marker: PhantomData<fn(D) -> Box<I>>,
+}
+
+impl<I, D, F> DynFactoryProvider<I, D, F>
+where
+    I: ?Sized + Interface,
+    D: 'static,
+    F: ServiceFactory<D, Result = Box<I>>,
+{
+    pub fn new(factory: F) -> Self {
+        DynFactoryProvider {
+            factory,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<I, D, F> Provider for DynFactoryProvider<I, D, F>
+where
+    I: ?Sized + Interface,
+    D: 'static,
+    F: ServiceFactory<D, Result = Box<I>>,
+{
+    fn result(&self) -> ServiceInfo {
+        ServiceInfo::of::<I>()
+    }
+
+    fn provide(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<DynSvc> {
+        let service = self.factory.invoke(injector, request_info)?;
+        Ok(wrap_service::<I>(Svc::from(service)))
+    }
+
+    fn provide_owned(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<OwnedDynSvc> {
+        let service = self.factory.invoke(injector, request_info)?;
+        Ok(wrap_owned_service::<I>(service))
+    }
+
+    fn dependencies(&self) -> Vec<ServiceInfo> {
+        self.factory.dependencies()
+    }
+
+    fn lifetime(&self) -> Lifetime {
+        Lifetime::Transient
+    }
+}
@@ -0,0 +1,102 @@
+use crate::{
+    DynSvc, InjectError, InjectResult, Injector, Interface, OwnedDynSvc,
+    Provider, RequestInfo, Service, ServiceInfo,
+};
+use std::{error::Error, marker::PhantomData};
+
+/// A provider which always fails to provide its service with a predetermined
+/// reason. This can be used to document that a service is intentionally
+/// unavailable, for example in certain configurations.
+///
+/// Unlike a missing provider, requesting a service from this provider returns
+/// an [`InjectError::ActivationFailed`] containing the error created by the
+/// provider's error factory. Unlike a conditional provider, this provider is
+/// never skipped. The error factory is invoked each time the service is
+/// requested.
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{InjectError, Injector, Svc};
+///
+/// struct GpuRenderer;
+///
+/// let mut builder = Injector::builder();
+/// builder.provide_error::<GpuRenderer, _>(|| {
+///     "GPU rendering is not supported on this platform".into()
+/// });
+///
+/// let injector = builder.build();
+/// match injector.get::<Svc<GpuRenderer>>() {
+///     Err(InjectError::ActivationFailed { inner, .. }) => assert_eq!(
+///         "GPU rendering is not supported on this platform",
+///         inner.to_string()
+///     ),
+///     Err(error) => Err(error).unwrap(),
+///     Ok(_) => unreachable!("the service is unavailable"),
+/// }
+/// ```
+pub struct ErrorProvider<I, F>
+where
+    I: ?Sized + Interface,
+    F: Service + Fn() -> Box<dyn Error>,
+{
+    factory: F,
+    marker: PhantomData<fn() -> I>,
+}
+
+impl<I, F> ErrorProvider<I, F>
+where
+    I: ?Sized + Interface,
+    F: Service + Fn() -> Box<dyn Error>,
+{
+    /// Creates a new [`ErrorProvider`] using an error factory.
+    #[must_use]
+    pub fn new(factory: F) -> Self {
+        ErrorProvider {
+            factory,
+            marker: PhantomData,
+        }
+    }
+
+    fn error(&self) -> InjectError {
+        InjectError::ActivationFailed {
+            service_info: ServiceInfo::of::<I>(),
+            inner: (self.factory)(),
+        }
+    }
+}
+
+impl<I, F> Provider for ErrorProvider<I, F>
+where
+    I: ?Sized + Interface,
+    F: Service + Fn() -> Box<dyn Error>,
+{
+    fn result(&self) -> ServiceInfo {
+        ServiceInfo::of::<I>()
+    }
+
+    fn provide(
+        &mut self,
+        _injector: &Injector,
+        _request_info: &RequestInfo,
+    ) -> InjectResult<DynSvc> {
+        Err(self.error())
+    }
+
+    fn provide_owned(
+        &mut self,
+        _injector: &Injector,
+        _request_info: &RequestInfo,
+    ) -> InjectResult<OwnedDynSvc> {
+        Err(self.error())
+    }
+
+    fn is_available(
+        &self,
+        _injector: &Injector,
+        _request_info: &RequestInfo,
+    ) -> bool {
+        false
+    }
+}
@@ -0,0 +1,108 @@
+use crate::{
+    InjectError, InjectResult, Injector, Lifetime, RequestInfo, ServiceInfo,
+    Svc, TypedProvider,
+};
+use std::time::Duration;
+
+/// Provides a service using another provider if the first provider fails to
+/// activate its service. See [`TypedProvider::or_else()`] for more
+/// information.
+pub struct FallbackProvider<P, F>
+where
+    P: TypedProvider,
+    F: TypedProvider<Result = P::Result>,
+{
+    primary: P,
+    fallback: F,
+}
+
+impl<P, F> FallbackProvider<P, F>
+where
+    P: TypedProvider,
+    F: TypedProvider<Result = P::Result>,
+{
+    pub(crate) fn new(primary: P, fallback: F) -> Self {
+        FallbackProvider { primary, fallback }
+    }
+}
+
+impl<P, F> TypedProvider for FallbackProvider<P, F>
+where
+    P: TypedProvider,
+    F: TypedProvider<Result = P::Result>,
+{
+    type Result = P::Result;
+
+    fn provide_typed(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Svc<Self::Result>> {
+        match self.primary.provide_typed(injector, request_info) {
+            Err(InjectError::ActivationFailed { .. }) => {
+                self.fallback.provide_typed(injector, request_info)
+            }
+            result => result,
+        }
+    }
+
+    fn provide_owned_typed(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Box<Self::Result>> {
+        match self.primary.provide_owned_typed(injector, request_info) {
+            Err(InjectError::ActivationFailed { .. }) => {
+                self.fallback.provide_owned_typed(injector, request_info)
+            }
+            result => result,
+        }
+    }
+
+    fn strong_count_typed(&self) -> Option<usize> {
+        match (
+            self.primary.strong_count_typed(),
+            self.fallback.strong_count_typed(),
+        ) {
+            (Some(primary), Some(fallback)) => Some(primary + fallback),
+            (primary, fallback) => primary.or(fallback),
+        }
+    }
+
+    fn name_typed(&self) -> Option<&str> {
+        self.primary.name_typed()
+    }
+
+    fn tags_typed(&self) -> Vec<&str> {
+        self.primary.tags_typed()
+    }
+
+    fn priority_typed(&self) -> i32 {
+        self.primary.priority_typed()
+    }
+
+    fn build_time_typed(&self) -> Option<Duration> {
+        self.primary.build_time_typed()
+    }
+
+    fn dependencies_typed(&self) -> Vec<ServiceInfo> {
+        // The fallback may be activated any time the primary fails, so its
+        // dependencies must be registered as well
+        let mut dependencies = self.primary.dependencies_typed();
+        dependencies.extend(self.fallback.dependencies_typed());
+        dependencies
+    }
+
+    fn is_available_typed(
+        &self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> bool {
+        self.primary.is_available_typed(injector, request_info)
+            || self.fallback.is_available_typed(injector, request_info)
+    }
+
+    fn lifetime_typed(&self) -> Lifetime {
+        self.primary.lifetime_typed()
+    }
+}
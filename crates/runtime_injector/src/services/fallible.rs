@@ -9,7 +9,7 @@ use std::{error::Error, marker::PhantomData};
 /// [`InjectError::ActivationFailed`] is returned as an error.
 pub struct FallibleServiceFactory<D, R, E, F>
 where
-    D: Service,
+    D: 'static,
     R: Service,
     E: Service + Error,
     F: ServiceFactory<D, Result = Result<R, E>>,
@@ -20,7 +20,7 @@ where
 
 impl<D, R, E, F> ServiceFactory<D> for FallibleServiceFactory<D, R, E, F>
 where
-    D: Service,
+    D: 'static,
     R: Service,
     E: Service + Error,
     F: ServiceFactory<D, Result = Result<R, E>>,
@@ -41,6 +41,67 @@ where
             }),
         }
     }
+
+    fn dependencies(&self) -> Vec<ServiceInfo> {
+        self.inner.dependencies()
+    }
+}
+
+/// A service factory that may fail with any error which can be converted
+/// into a boxed error. See [`InjectorBuilder::provide_fallible()`] for more
+/// information.
+///
+/// [`InjectorBuilder::provide_fallible()`]: crate::InjectorBuilder::provide_fallible
+pub(crate) struct BoxedFallibleServiceFactory<D, R, E, F>
+where
+    D: 'static,
+    R: Service,
+    E: Service + Into<Box<dyn Error + Send + Sync>>,
+    F: ServiceFactory<D, Result = Result<R, E>>,
+{
+    inner: F,
+    marker: PhantomData<fn(D) -> Result<R, E>>,
+}
+
+impl<D, R, E, F> BoxedFallibleServiceFactory<D, R, E, F>
+where
+    D: 'static,
+    R: Service,
+    E: Service + Into<Box<dyn Error + Send + Sync>>,
+    F: ServiceFactory<D, Result = Result<R, E>>,
+{
+    pub fn new(inner: F) -> Self {
+        BoxedFallibleServiceFactory {
+            inner,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<D, R, E, F> ServiceFactory<D> for BoxedFallibleServiceFactory<D, R, E, F>
+where
+    D: 'static,
+    R: Service,
+    E: Service + Into<Box<dyn Error + Send + Sync>>,
+    F: ServiceFactory<D, Result = Result<R, E>>,
+{
+    type Result = R;
+
+    fn invoke(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Self::Result> {
+        let result = self.inner.invoke(injector, request_info)?;
+        result.map_err(|error| InjectError::ActivationFailed {
+            service_info: ServiceInfo::of::<R>(),
+            inner: error.into(),
+        })
+    }
+
+    fn dependencies(&self) -> Vec<ServiceInfo> {
+        self.inner.dependencies()
+    }
 }
 
 /// Defines a conversion into a fallible service factory. This trait is
@@ -49,7 +110,7 @@ where
 /// [`Service`].
 pub trait IntoFallible<D, R, E, F>
 where
-    D: Service,
+    D: 'static,
     R: Service,
     E: Service + Error,
     F: ServiceFactory<D, Result = Result<R, E>>,
@@ -104,7 +165,7 @@ where
 
 impl<D, R, E, F> IntoFallible<D, R, E, F> for F
 where
-    D: Service,
+    D: 'static,
     R: Service,
     E: Service + Error,
     F: ServiceFactory<D, Result = Result<R, E>>,
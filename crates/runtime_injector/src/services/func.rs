@@ -35,6 +35,12 @@ pub trait ServiceFactory<D>: Service {
         injector: &Injector,
         request_info: &RequestInfo,
     ) -> InjectResult<Self::Result>;
+
+    /// Gets the services which must be registered to invoke this service
+    /// factory. See [`Request::dependencies()`] for more information.
+    fn dependencies(&self) -> Vec<ServiceInfo> {
+        Vec::new()
+    }
 }
 
 macro_rules! impl_provider_function {
@@ -68,6 +74,30 @@ macro_rules! impl_provider_function {
                             return Err($crate::InjectError::MissingDependency {
                                 dependency_info: service_info,
                                 service_info: $crate::ServiceInfo::of::<R>(),
+                                inner: None,
+                            })
+                        },
+                        // Errors from activating dependencies are wrapped so
+                        // the full chain of services is kept
+                        Err($crate::InjectError::MissingDependency {
+                            service_info,
+                            dependency_info,
+                            inner,
+                        }) => {
+                            return Err($crate::InjectError::MissingDependency {
+                                dependency_info: service_info,
+                                service_info: $crate::ServiceInfo::of::<R>(),
+                                inner: Some(Box::new($crate::InjectError::MissingDependency {
+                                    service_info,
+                                    dependency_info,
+                                    inner,
+                                })),
+                            })
+                        },
+                        Err(error @ $crate::InjectError::ActivationFailed { .. }) => {
+                            return Err($crate::InjectError::ActivationFailed {
+                                service_info: $crate::ServiceInfo::of::<R>(),
+                                inner: Box::new(error),
                             })
                         },
                         Err(error) => return Err(error),
@@ -75,6 +105,13 @@ macro_rules! impl_provider_function {
                 ),*);
                 Ok(result)
             }
+
+            #[allow(unused_mut)]
+            fn dependencies(&self) -> Vec<ServiceInfo> {
+                let mut dependencies = Vec::new();
+                $(dependencies.extend(<$type_name as Request>::dependencies());)*
+                dependencies
+            }
         }
     };
 }
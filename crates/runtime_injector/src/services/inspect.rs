@@ -0,0 +1,89 @@
+use crate::{
+    InjectResult, Injector, Lifetime, RequestInfo, Service, ServiceInfo, Svc,
+    TypedProvider,
+};
+use std::time::Duration;
+
+/// Calls a function with each service provided by another provider. See
+/// [`TypedProvider::inspect()`] for more information.
+pub struct InspectProvider<P, F>
+where
+    P: TypedProvider,
+    F: Service + Fn(&Svc<P::Result>, &RequestInfo),
+{
+    inner: P,
+    inspector: F,
+}
+
+impl<P, F> InspectProvider<P, F>
+where
+    P: TypedProvider,
+    F: Service + Fn(&Svc<P::Result>, &RequestInfo),
+{
+    pub(crate) fn new(inner: P, inspector: F) -> Self {
+        InspectProvider { inner, inspector }
+    }
+}
+
+impl<P, F> TypedProvider for InspectProvider<P, F>
+where
+    P: TypedProvider,
+    F: Service + Fn(&Svc<P::Result>, &RequestInfo),
+{
+    type Result = P::Result;
+
+    fn provide_typed(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Svc<Self::Result>> {
+        let service = self.inner.provide_typed(injector, request_info)?;
+        (self.inspector)(&service, request_info);
+        Ok(service)
+    }
+
+    fn provide_owned_typed(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Box<Self::Result>> {
+        // Owned services can't be shared with the inspector
+        self.inner.provide_owned_typed(injector, request_info)
+    }
+
+    fn strong_count_typed(&self) -> Option<usize> {
+        self.inner.strong_count_typed()
+    }
+
+    fn name_typed(&self) -> Option<&str> {
+        self.inner.name_typed()
+    }
+
+    fn tags_typed(&self) -> Vec<&str> {
+        self.inner.tags_typed()
+    }
+
+    fn priority_typed(&self) -> i32 {
+        self.inner.priority_typed()
+    }
+
+    fn build_time_typed(&self) -> Option<Duration> {
+        self.inner.build_time_typed()
+    }
+
+    fn dependencies_typed(&self) -> Vec<ServiceInfo> {
+        self.inner.dependencies_typed()
+    }
+
+    fn is_available_typed(
+        &self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> bool {
+        self.inner.is_available_typed(injector, request_info)
+    }
+
+    fn lifetime_typed(&self) -> Lifetime {
+        self.inner.lifetime_typed()
+    }
+}
@@ -0,0 +1,169 @@
+use crate::{
+    DynSvc, InjectResult, Injector, Lifetime, OwnedDynSvc, Provider,
+    ProviderMap, RequestInfo, Service, ServiceInfo, Svc,
+};
+use std::time::{Duration, Instant};
+
+/// A callback which is invoked each time a provider activates a service. See
+/// [`InjectorBuilder::on_resolve()`] for more information.
+///
+/// [`InjectorBuilder::on_resolve()`]: crate::InjectorBuilder::on_resolve
+trait ResolveCallback: Service {
+    fn call(
+        &self,
+        service_info: &ServiceInfo,
+        elapsed: Duration,
+        result: &InjectResult<()>,
+    );
+}
+
+impl<F> ResolveCallback for F
+where
+    F: Service + Fn(&ServiceInfo, Duration, &InjectResult<()>),
+{
+    fn call(
+        &self,
+        service_info: &ServiceInfo,
+        elapsed: Duration,
+        result: &InjectResult<()>,
+    ) {
+        self(service_info, elapsed, result);
+    }
+}
+
+/// Wraps each provider so that its activations are timed and reported to a
+/// callback.
+#[derive(Clone)]
+pub(crate) struct ResolveHook {
+    callback: Svc<dyn ResolveCallback>,
+}
+
+impl ResolveHook {
+    pub fn new<F>(callback: F) -> Self
+    where
+        F: Service + Fn(&ServiceInfo, Duration, &InjectResult<()>),
+    {
+        ResolveHook {
+            callback: Svc::new(callback),
+        }
+    }
+
+    /// Instruments each provider in a provider map.
+    pub fn apply(&self, provider_map: &mut ProviderMap) {
+        for providers in provider_map.values_mut().flatten() {
+            *providers = std::mem::take(providers)
+                .into_iter()
+                .map(|inner| {
+                    Box::new(InstrumentedProvider {
+                        inner,
+                        hook: self.clone(),
+                    }) as Box<dyn Provider>
+                })
+                .collect();
+        }
+    }
+}
+
+/// Reports the activations of another provider to a resolve hook.
+struct InstrumentedProvider {
+    inner: Box<dyn Provider>,
+    hook: ResolveHook,
+}
+
+impl InstrumentedProvider {
+    fn report<T>(
+        &self,
+        started: Instant,
+        result: InjectResult<T>,
+    ) -> InjectResult<T> {
+        let elapsed = started.elapsed();
+        let service_info = self.inner.result();
+        match result {
+            Ok(service) => {
+                self.hook.callback.call(&service_info, elapsed, &Ok(()));
+                Ok(service)
+            }
+            Err(error) => {
+                let result = Err(error);
+                self.hook.callback.call(&service_info, elapsed, &result);
+                result.map(|()| unreachable!())
+            }
+        }
+    }
+}
+
+impl Provider for InstrumentedProvider {
+    fn result(&self) -> ServiceInfo {
+        self.inner.result()
+    }
+
+    fn provide(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<DynSvc> {
+        let started = Instant::now();
+        let result = self.inner.provide(injector, request_info);
+        self.report(started, result)
+    }
+
+    fn provide_owned(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<OwnedDynSvc> {
+        let started = Instant::now();
+        let result = self.inner.provide_owned(injector, request_info);
+        self.report(started, result)
+    }
+
+    fn provide_all(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Vec<DynSvc>> {
+        let started = Instant::now();
+        let result = self.inner.provide_all(injector, request_info);
+        self.report(started, result)
+    }
+
+    fn strong_count(&self) -> Option<usize> {
+        self.inner.strong_count()
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.inner.name()
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        self.inner.tags()
+    }
+
+    fn priority(&self) -> i32 {
+        self.inner.priority()
+    }
+
+    fn build_time(&self) -> Option<Duration> {
+        self.inner.build_time()
+    }
+
+    fn dependencies(&self) -> Vec<ServiceInfo> {
+        self.inner.dependencies()
+    }
+
+    fn is_available(
+        &self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> bool {
+        self.inner.is_available(injector, request_info)
+    }
+
+    fn lifetime(&self) -> Lifetime {
+        self.inner.lifetime()
+    }
+
+    fn implementation(&self) -> ServiceInfo {
+        self.inner.implementation()
+    }
+}
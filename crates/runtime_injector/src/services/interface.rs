@@ -29,6 +29,31 @@ pub trait Interface: Service {
     /// Downcasts an owned dynamic service pointer into an owned service
     /// pointer of this interface type.
     fn downcast_owned(service: OwnedDynSvc) -> InjectResult<Box<Self>>;
+
+    /// Erases the type of a service pointer of this interface type if the
+    /// interface is a concrete service type. Pointers to trait interfaces
+    /// can't be erased without knowing the concrete type of the service, so
+    /// they are returned as-is.
+    ///
+    /// ## Errors
+    ///
+    /// Returns the pointer if it can't be erased.
+    fn try_into_dyn(service: Svc<Self>) -> Result<DynSvc, Svc<Self>> {
+        Err(service)
+    }
+
+    /// Erases the type of an owned service pointer of this interface type if
+    /// the interface is a concrete service type. See
+    /// [`Interface::try_into_dyn()`] for more information.
+    ///
+    /// ## Errors
+    ///
+    /// Returns the pointer if it can't be erased.
+    fn try_into_dyn_owned(
+        service: Box<Self>,
+    ) -> Result<OwnedDynSvc, Box<Self>> {
+        Err(service)
+    }
 }
 
 impl<T: Service> Interface for T {
@@ -47,6 +72,16 @@ impl<T: Service> Interface for T {
                 service_info: ServiceInfo::of::<Self>(),
             })
     }
+
+    fn try_into_dyn(service: Svc<Self>) -> Result<DynSvc, Svc<Self>> {
+        Ok(service)
+    }
+
+    fn try_into_dyn_owned(
+        service: Box<Self>,
+    ) -> Result<OwnedDynSvc, Box<Self>> {
+        Ok(service)
+    }
 }
 
 /// Marker trait that indicates that a type is an interface for another type.
@@ -92,6 +127,41 @@ impl<T: Service> InterfaceFor<T> for T {}
 ///     ],
 /// };
 /// ```
+///
+/// Requesting every implementation of an interface can be verbose. Adding
+/// `with collection(Alias, Ext::method)` after the list of implementations
+/// also generates a type alias for `Vec<Svc<I>>` and an extension trait for
+/// [`Injector`](crate::Injector) which requests it. A visibility can be
+/// given before the alias, and applies to both the alias and the trait.
+///
+/// ```
+/// use runtime_injector::{
+///     interface, Injector, IntoSingleton, Service, TypedProvider,
+/// };
+///
+/// trait Plugin: Service {}
+///
+/// #[derive(Default)]
+/// struct Logging;
+/// impl Plugin for Logging {}
+///
+/// #[derive(Default)]
+/// struct Metrics;
+/// impl Plugin for Metrics {}
+///
+/// interface! {
+///     dyn Plugin = [Logging, Metrics]
+///         with collection(pub Plugins, InjectorPlugins::plugins),
+/// };
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(Logging::default.singleton().with_interface::<dyn Plugin>());
+/// builder.provide(Metrics::default.singleton().with_interface::<dyn Plugin>());
+///
+/// let injector = builder.build();
+/// let plugins: Plugins = injector.plugins().unwrap();
+/// assert_eq!(2, plugins.len());
+/// ```
 #[macro_export]
 macro_rules! interface {
     {
@@ -100,12 +170,19 @@ macro_rules! interface {
                 $($(#[$($attr:meta),*])* $impl:ty),*
                 $(,)?
             ]
+            $(
+                with collection(
+                    $collection_vis:vis $collection:ident,
+                    $collection_ext:ident::$collection_fn:ident
+                    $(,)?
+                )
+            )?
         ),*
         $(,)?
     } => {
         $(
             impl $crate::Interface for $interface {
-                #[allow(unused_assignments)]
+                #[allow(unused_assignments, unused_mut, unused_variables)]
                 fn downcast(mut service: $crate::DynSvc) -> $crate::InjectResult<$crate::Svc<Self>> {
                     $(
                         $(#[$($attr),*])*
@@ -118,7 +195,7 @@ macro_rules! interface {
                     Err($crate::InjectError::MissingProvider { service_info: $crate::ServiceInfo::of::<Self>() })
                 }
 
-                #[allow(unused_assignments)]
+                #[allow(unused_assignments, unused_mut, unused_variables)]
                 fn downcast_owned(mut service: $crate::OwnedDynSvc) -> $crate::InjectResult<::std::boxed::Box<Self>> {
                     $(
                         $(#[$($attr),*])*
@@ -136,6 +213,23 @@ macro_rules! interface {
                 $(#[$($attr),*])*
                 impl $crate::InterfaceFor<$impl> for $interface {}
             )*
+
+            $(
+                #[doc = concat!("All implementations of `", stringify!($interface), "`.")]
+                $collection_vis type $collection = ::std::vec::Vec<$crate::Svc<$interface>>;
+
+                #[doc = concat!("Requests all implementations of `", stringify!($interface), "` from an injector.")]
+                $collection_vis trait $collection_ext {
+                    #[doc = concat!("Requests all implementations of `", stringify!($interface), "`.")]
+                    fn $collection_fn(&self) -> $crate::InjectResult<$collection>;
+                }
+
+                impl $collection_ext for $crate::Injector {
+                    fn $collection_fn(&self) -> $crate::InjectResult<$collection> {
+                        self.get()
+                    }
+                }
+            )?
         )*
     };
 }
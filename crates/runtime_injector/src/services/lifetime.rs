@@ -0,0 +1,54 @@
+use std::fmt::{Display, Formatter};
+
+/// Describes how long the instances created by a provider live. See
+/// [`Provider::lifetime()`](crate::Provider::lifetime) for more information.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Lifetime {
+    /// A new instance is created each time the service is requested.
+    Transient,
+
+    /// A single instance is created the first time the service is requested
+    /// and reused for each future request.
+    Singleton,
+
+    /// A single instance is created within each scope. See
+    /// [`IntoScoped`](crate::IntoScoped) for more information.
+    Scoped,
+
+    /// A single instance is created on each thread. See
+    /// [`IntoThreadLocal`](crate::IntoThreadLocal) for more information.
+    PerThread,
+
+    /// A bounded set of instances is reused between requests. See
+    /// [`IntoPooled`](crate::IntoPooled) for more information.
+    Pooled,
+
+    /// A single instance is created for each value of one of the service's
+    /// arguments. See [`IntoMemoized`](crate::IntoMemoized) for more
+    /// information.
+    Memoized,
+
+    /// The instance was created before being registered and is reused for
+    /// each request.
+    Constant,
+
+    /// The provider manages its instances in some other way. This is the
+    /// default for custom providers.
+    Custom,
+}
+
+impl Display for Lifetime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Lifetime::Transient => "transient",
+            Lifetime::Singleton => "singleton",
+            Lifetime::Scoped => "scoped",
+            Lifetime::PerThread => "per-thread",
+            Lifetime::Pooled => "pooled",
+            Lifetime::Memoized => "memoized",
+            Lifetime::Constant => "constant",
+            Lifetime::Custom => "custom",
+        };
+        f.write_str(name)
+    }
+}
@@ -0,0 +1,156 @@
+use crate::{
+    DynSvc, InjectError, InjectResult, Injector, Lifetime, Provider,
+    RequestInfo, Service, ServiceInfo, Svc,
+};
+use std::marker::PhantomData;
+
+/// A service provider which creates several instances of a service at once
+/// from a single factory. Each instance is treated as a separate
+/// implementation of the service, so requesting [`Services<R>`] or
+/// [`Vec<Svc<R>>`] yields all of them. This is useful when the number of
+/// instances is data-driven, like one instance per entry in a config.
+///
+/// Transient providers create a new batch of instances each time the service
+/// is requested, while singleton providers create the batch only once and
+/// reuse it for each future request. Requesting a single [`Svc<R>`] only
+/// succeeds if the batch contains exactly one instance. Owned service
+/// pointers are not supported.
+///
+/// Since the instances are only known once the factory has been called,
+/// [`Services::len()`] counts this provider once regardless of how many
+/// instances it creates.
+///
+/// [`Services<R>`]: crate::Services
+/// [`Services::len()`]: crate::Services::len
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{constant, Injector, ManyProvider, Services, Svc};
+///
+/// struct Config {
+///     endpoints: Vec<String>,
+/// }
+///
+/// struct Client(String);
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(constant(Config {
+///     endpoints: vec!["a.example".to_owned(), "b.example".to_owned()],
+/// }));
+/// builder.provide(ManyProvider::singleton(|injector, request_info| {
+///     let config: Svc<Config> = injector.get_with(request_info)?;
+///     Ok(config.endpoints.iter().cloned().map(Client).collect())
+/// }));
+///
+/// let injector = builder.build();
+/// let clients: Vec<Svc<Client>> = injector.get().unwrap();
+/// assert_eq!(2, clients.len());
+/// ```
+pub struct ManyProvider<R, F>
+where
+    R: Service,
+    F: Service + Fn(&Injector, &RequestInfo) -> InjectResult<Vec<R>>,
+{
+    factory: F,
+    cached: Option<Vec<Svc<R>>>,
+    singleton: bool,
+    marker: PhantomData<fn() -> R>,
+}
+
+impl<R, F> ManyProvider<R, F>
+where
+    R: Service,
+    F: Service + Fn(&Injector, &RequestInfo) -> InjectResult<Vec<R>>,
+{
+    /// Creates a provider which calls its factory each time the service is
+    /// requested.
+    #[must_use]
+    pub fn transient(factory: F) -> Self {
+        ManyProvider {
+            factory,
+            cached: None,
+            singleton: false,
+            marker: PhantomData,
+        }
+    }
+
+    /// Creates a provider which calls its factory only the first time the
+    /// service is requested, then reuses the same instances for each future
+    /// request.
+    #[must_use]
+    pub fn singleton(factory: F) -> Self {
+        ManyProvider {
+            factory,
+            cached: None,
+            singleton: true,
+            marker: PhantomData,
+        }
+    }
+
+    fn provide_batch(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Vec<Svc<R>>> {
+        if let Some(ref cached) = self.cached {
+            return Ok(cached.clone());
+        }
+
+        let request_info = request_info.with_request(ServiceInfo::of::<R>());
+        let batch: Vec<_> = (self.factory)(injector, &request_info)?
+            .into_iter()
+            .map(Svc::new)
+            .collect();
+        if self.singleton {
+            self.cached = Some(batch.clone());
+        }
+
+        Ok(batch)
+    }
+}
+
+impl<R, F> Provider for ManyProvider<R, F>
+where
+    R: Service,
+    F: Service + Fn(&Injector, &RequestInfo) -> InjectResult<Vec<R>>,
+{
+    fn result(&self) -> ServiceInfo {
+        ServiceInfo::of::<R>()
+    }
+
+    fn provide(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<DynSvc> {
+        let mut batch = self.provide_batch(injector, request_info)?;
+        match batch.len() {
+            0 => Err(InjectError::ConditionsNotMet {
+                service_info: ServiceInfo::of::<R>(),
+            }),
+            1 => Ok(batch.remove(0) as DynSvc),
+            providers => Err(InjectError::MultipleProviders {
+                service_info: ServiceInfo::of::<R>(),
+                providers,
+            }),
+        }
+    }
+
+    fn provide_all(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Vec<DynSvc>> {
+        let batch = self.provide_batch(injector, request_info)?;
+        Ok(batch.into_iter().map(|service| service as DynSvc).collect())
+    }
+
+    fn lifetime(&self) -> Lifetime {
+        if self.singleton {
+            Lifetime::Singleton
+        } else {
+            Lifetime::Transient
+        }
+    }
+}
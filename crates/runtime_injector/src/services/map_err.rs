@@ -0,0 +1,90 @@
+use crate::{
+    InjectError, InjectResult, Injector, Lifetime, RequestInfo, Service,
+    ServiceInfo, Svc, TypedProvider,
+};
+use std::time::Duration;
+
+/// Replaces the errors returned by another provider. See
+/// [`TypedProvider::map_err()`] for more information.
+pub struct MapErrProvider<P, F>
+where
+    P: TypedProvider,
+    F: Service + Fn(InjectError) -> InjectError,
+{
+    inner: P,
+    mapper: F,
+}
+
+impl<P, F> MapErrProvider<P, F>
+where
+    P: TypedProvider,
+    F: Service + Fn(InjectError) -> InjectError,
+{
+    pub(crate) fn new(inner: P, mapper: F) -> Self {
+        MapErrProvider { inner, mapper }
+    }
+}
+
+impl<P, F> TypedProvider for MapErrProvider<P, F>
+where
+    P: TypedProvider,
+    F: Service + Fn(InjectError) -> InjectError,
+{
+    type Result = P::Result;
+
+    fn provide_typed(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Svc<Self::Result>> {
+        self.inner
+            .provide_typed(injector, request_info)
+            .map_err(&self.mapper)
+    }
+
+    fn provide_owned_typed(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Box<Self::Result>> {
+        self.inner
+            .provide_owned_typed(injector, request_info)
+            .map_err(&self.mapper)
+    }
+
+    fn strong_count_typed(&self) -> Option<usize> {
+        self.inner.strong_count_typed()
+    }
+
+    fn name_typed(&self) -> Option<&str> {
+        self.inner.name_typed()
+    }
+
+    fn tags_typed(&self) -> Vec<&str> {
+        self.inner.tags_typed()
+    }
+
+    fn priority_typed(&self) -> i32 {
+        self.inner.priority_typed()
+    }
+
+    fn build_time_typed(&self) -> Option<Duration> {
+        self.inner.build_time_typed()
+    }
+
+    fn dependencies_typed(&self) -> Vec<ServiceInfo> {
+        self.inner.dependencies_typed()
+    }
+
+    fn is_available_typed(
+        &self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> bool {
+        self.inner.is_available_typed(injector, request_info)
+    }
+
+    fn lifetime_typed(&self) -> Lifetime {
+        self.inner.lifetime_typed()
+    }
+}
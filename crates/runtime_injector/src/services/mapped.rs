@@ -0,0 +1,88 @@
+use crate::{
+    InjectResult, Injector, Lifetime, RequestInfo, Service, ServiceInfo, Svc,
+    TypedProvider,
+};
+use std::marker::PhantomData;
+
+/// Provides a service derived from the service created by another provider.
+/// See [`TypedProvider::map_result()`] for more information.
+pub struct MappedProvider<P, U, F>
+where
+    P: TypedProvider,
+    U: Service,
+    F: Service + Fn(Svc<P::Result>, &Injector) -> InjectResult<U>,
+{
+    inner: P,
+    mapper: F,
+    cached: Option<(Svc<P::Result>, Svc<U>)>,
+    marker: PhantomData<fn() -> U>,
+}
+
+impl<P, U, F> MappedProvider<P, U, F>
+where
+    P: TypedProvider,
+    U: Service,
+    F: Service + Fn(Svc<P::Result>, &Injector) -> InjectResult<U>,
+{
+    pub(crate) fn new(inner: P, mapper: F) -> Self {
+        MappedProvider {
+            inner,
+            mapper,
+            cached: None,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<P, U, F> TypedProvider for MappedProvider<P, U, F>
+where
+    P: TypedProvider,
+    U: Service,
+    F: Service + Fn(Svc<P::Result>, &Injector) -> InjectResult<U>,
+{
+    type Result = U;
+
+    fn provide_typed(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Svc<Self::Result>> {
+        let source = self.inner.provide_typed(injector, request_info)?;
+        if let Some((cached_source, cached)) = &self.cached {
+            if Svc::ptr_eq(cached_source, &source) {
+                return Ok(cached.clone());
+            }
+        }
+
+        let result = Svc::new((self.mapper)(source.clone(), injector)?);
+        if self.inner.strong_count_typed().is_some() {
+            self.cached = Some((source, result.clone()));
+        }
+
+        Ok(result)
+    }
+
+    fn strong_count_typed(&self) -> Option<usize> {
+        self.inner.strong_count_typed().map(|_| {
+            self.cached
+                .as_ref()
+                .map_or(0, |(_, cached)| Svc::strong_count(cached))
+        })
+    }
+
+    fn dependencies_typed(&self) -> Vec<ServiceInfo> {
+        self.inner.dependencies_typed()
+    }
+
+    fn is_available_typed(
+        &self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> bool {
+        self.inner.is_available_typed(injector, request_info)
+    }
+
+    fn lifetime_typed(&self) -> Lifetime {
+        self.inner.lifetime_typed()
+    }
+}
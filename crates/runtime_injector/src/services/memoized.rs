@@ -0,0 +1,220 @@
+use crate::{
+    Arg, AsAny, InjectResult, Injector, Lifetime, Request, RequestInfo,
+    Service, ServiceFactory, ServiceInfo, Svc, TypedProvider,
+};
+use std::{collections::HashMap, hash::Hash, marker::PhantomData};
+
+/// A service provider which creates a single instance of the service for each
+/// value of one of its arguments. Requests made with an argument value which
+/// has been seen before reuse the instance created for that value, while
+/// requests made with a new value create a new instance. This sits between
+/// singletons and transients, and is useful for services like per-tenant
+/// configuration which are identified by an [`Arg<T>`].
+///
+/// By default, an instance is kept for every distinct argument value that
+/// the service is requested with, so the memory used by the provider grows
+/// with the number of distinct values. If the set of values isn't bounded,
+/// then limit the number of instances with
+/// [`MemoizedProvider::max_entries()`].
+pub struct MemoizedProvider<D, R, F, T>
+where
+    R: Service,
+    F: ServiceFactory<D, Result = R>,
+    T: Service + AsAny + Clone + Eq + Hash,
+{
+    factory: F,
+    max_entries: Option<usize>,
+    instances: HashMap<T, (Svc<R>, usize)>,
+    uses: usize,
+    marker: PhantomData<fn(D) -> R>,
+}
+
+impl<D, R, F, T> MemoizedProvider<D, R, F, T>
+where
+    R: Service,
+    F: ServiceFactory<D, Result = R>,
+    T: Service + AsAny + Clone + Eq + Hash,
+{
+    /// Creates a new [`MemoizedProvider`] using a service factory.
+    #[must_use]
+    pub fn new(func: F) -> Self {
+        MemoizedProvider {
+            factory: func,
+            max_entries: None,
+            instances: HashMap::new(),
+            uses: 0,
+            marker: PhantomData,
+        }
+    }
+
+    /// Limits the number of instances kept by the provider. When an instance
+    /// is created for a new argument value while the provider is full, the
+    /// least recently requested instance is forgotten. Pointers to forgotten
+    /// instances remain valid, but later requests with the same argument
+    /// value create a new instance.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `max` is zero.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     Arg, Injector, IntoMemoized, RequestInfo, Svc, WithArg,
+    /// };
+    ///
+    /// struct TenantConfig(Arg<u32>);
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(TenantConfig.memoized_by_arg::<u32>().max_entries(1));
+    ///
+    /// let injector = builder.build();
+    /// let mut tenant1 = RequestInfo::new();
+    /// tenant1.with_arg::<TenantConfig, u32>(1);
+    /// let mut tenant2 = RequestInfo::new();
+    /// tenant2.with_arg::<TenantConfig, u32>(2);
+    ///
+    /// let config1: Svc<TenantConfig> = injector.get_with(&tenant1).unwrap();
+    /// let _config2: Svc<TenantConfig> = injector.get_with(&tenant2).unwrap();
+    ///
+    /// // The first tenant's config was forgotten to make room for the second
+    /// let config1_again: Svc<TenantConfig> =
+    ///     injector.get_with(&tenant1).unwrap();
+    /// assert!(!Svc::ptr_eq(&config1, &config1_again));
+    /// ```
+    #[must_use]
+    pub fn max_entries(mut self, max: usize) -> Self {
+        assert!(
+            max > 0,
+            "a memoized provider must hold at least one instance"
+        );
+
+        self.max_entries = Some(max);
+        self
+    }
+
+    /// Forgets the least recently requested instance.
+    fn evict(&mut self) {
+        let oldest = self
+            .instances
+            .iter()
+            .min_by_key(|&(_, &(_, last_used))| last_used)
+            .map(|(key, _)| key.clone());
+        if let Some(oldest) = oldest {
+            self.instances.remove(&oldest);
+        }
+    }
+}
+
+impl<D, R, F, T> TypedProvider for MemoizedProvider<D, R, F, T>
+where
+    D: 'static,
+    R: Service,
+    F: ServiceFactory<D, Result = R>,
+    T: Service + AsAny + Clone + Eq + Hash,
+{
+    type Result = R;
+
+    fn provide_typed(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Svc<Self::Result>> {
+        // Arguments are looked up by the service they are passed to
+        let arg_info = request_info.with_request(ServiceInfo::of::<R>());
+        let key = Arg::into_inner(Arg::<T>::request(injector, &arg_info)?);
+
+        self.uses += 1;
+        if let Some((service, last_used)) = self.instances.get_mut(&key) {
+            *last_used = self.uses;
+            return Ok(service.clone());
+        }
+
+        let result = Svc::new(self.factory.invoke(injector, request_info)?);
+        if self
+            .max_entries
+            .is_some_and(|max| self.instances.len() >= max)
+        {
+            self.evict();
+        }
+
+        self.instances.insert(key, (result.clone(), self.uses));
+        Ok(result)
+    }
+
+    fn strong_count_typed(&self) -> Option<usize> {
+        Some(
+            self.instances
+                .values()
+                .map(|(service, _)| Svc::strong_count(service))
+                .sum(),
+        )
+    }
+
+    fn dependencies_typed(&self) -> Vec<ServiceInfo> {
+        self.factory.dependencies()
+    }
+
+    fn lifetime_typed(&self) -> Lifetime {
+        Lifetime::Memoized
+    }
+}
+
+/// Defines a conversion into a memoized provider. This trait is automatically
+/// implemented for all service factories.
+pub trait IntoMemoized<D, R, F>
+where
+    R: Service,
+    F: ServiceFactory<D, Result = R>,
+{
+    /// Creates a memoized provider. Memoized providers create one instance of
+    /// their service for each distinct value of the service's [`Arg<T>`],
+    /// and reuse that instance for future requests with the same value. See
+    /// [`MemoizedProvider`] for more information.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     Arg, Injector, IntoMemoized, RequestInfo, Svc, WithArg,
+    /// };
+    ///
+    /// struct TenantConfig(Arg<String>);
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(TenantConfig.memoized_by_arg::<String>());
+    ///
+    /// let injector = builder.build();
+    /// let mut tenant1 = RequestInfo::new();
+    /// tenant1.with_arg::<TenantConfig, String>("tenant1".to_owned());
+    /// let mut tenant2 = RequestInfo::new();
+    /// tenant2.with_arg::<TenantConfig, String>("tenant2".to_owned());
+    ///
+    /// let config1: Svc<TenantConfig> = injector.get_with(&tenant1).unwrap();
+    /// let config2: Svc<TenantConfig> = injector.get_with(&tenant2).unwrap();
+    /// let config1_again: Svc<TenantConfig> =
+    ///     injector.get_with(&tenant1).unwrap();
+    ///
+    /// assert_eq!("tenant1", *config1.0);
+    /// assert!(!Svc::ptr_eq(&config1, &config2));
+    /// assert!(Svc::ptr_eq(&config1, &config1_again));
+    /// ```
+    #[must_use]
+    fn memoized_by_arg<T>(self) -> MemoizedProvider<D, R, F, T>
+    where
+        T: Service + AsAny + Clone + Eq + Hash;
+}
+
+impl<D, R, F> IntoMemoized<D, R, F> for F
+where
+    R: Service,
+    F: ServiceFactory<D, Result = R>,
+{
+    fn memoized_by_arg<T>(self) -> MemoizedProvider<D, R, F, T>
+    where
+        T: Service + AsAny + Clone + Eq + Hash,
+    {
+        MemoizedProvider::new(self)
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{
+    InjectResult, Injector, Lifetime, RequestInfo, ServiceInfo, Svc,
+    TypedProvider,
+};
+use std::time::Duration;
+
+/// Provides a service under a name. The service can still be requested
+/// normally, but it can also be looked up by its name. See
+/// [`TypedProvider::with_name()`] for more information.
+pub struct NamedProvider<P>
+where
+    P: TypedProvider,
+{
+    inner: P,
+    name: String,
+}
+
+impl<P> NamedProvider<P>
+where
+    P: TypedProvider,
+{
+    pub(crate) fn new(inner: P, name: String) -> Self {
+        NamedProvider { inner, name }
+    }
+}
+
+impl<P> TypedProvider for NamedProvider<P>
+where
+    P: TypedProvider,
+{
+    type Result = P::Result;
+
+    #[inline]
+    fn provide_typed(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Svc<Self::Result>> {
+        self.inner.provide_typed(injector, request_info)
+    }
+
+    #[inline]
+    fn provide_owned_typed(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Box<Self::Result>> {
+        self.inner.provide_owned_typed(injector, request_info)
+    }
+
+    #[inline]
+    fn strong_count_typed(&self) -> Option<usize> {
+        self.inner.strong_count_typed()
+    }
+
+    #[inline]
+    fn name_typed(&self) -> Option<&str> {
+        Some(&self.name)
+    }
+
+    #[inline]
+    fn tags_typed(&self) -> Vec<&str> {
+        self.inner.tags_typed()
+    }
+
+    #[inline]
+    fn priority_typed(&self) -> i32 {
+        self.inner.priority_typed()
+    }
+
+    #[inline]
+    fn build_time_typed(&self) -> Option<Duration> {
+        self.inner.build_time_typed()
+    }
+
+    #[inline]
+    fn dependencies_typed(&self) -> Vec<ServiceInfo> {
+        self.inner.dependencies_typed()
+    }
+
+    #[inline]
+    fn is_available_typed(
+        &self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> bool {
+        self.inner.is_available_typed(injector, request_info)
+    }
+
+    #[inline]
+    fn lifetime_typed(&self) -> Lifetime {
+        self.inner.lifetime_typed()
+    }
+}
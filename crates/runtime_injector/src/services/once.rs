@@ -0,0 +1,86 @@
+use crate::{
+    InjectError, InjectResult, Injector, RequestInfo, Service, ServiceInfo,
+    Svc, TypedProvider,
+};
+
+/// A provider which yields its value exactly once. The first request for the
+/// service moves the value out of the provider, and all future requests fail
+/// with [`InjectError::AlreadyConsumed`].
+///
+/// This is intended to be used with owned requests ([`Box<T>`]), which give
+/// the requester ownership of the value. If the service is requested as a
+/// [`Svc<T>`] instead, the value is still consumed and moved into the
+/// returned service pointer.
+pub struct OnceProvider<R>
+where
+    R: Service,
+{
+    value: Option<R>,
+}
+
+impl<R> OnceProvider<R>
+where
+    R: Service,
+{
+    /// Creates a new [`OnceProvider`] using the value it will provide.
+    #[must_use]
+    pub fn new(value: R) -> Self {
+        OnceProvider { value: Some(value) }
+    }
+
+    fn take(&mut self) -> InjectResult<R> {
+        self.value.take().ok_or(InjectError::AlreadyConsumed {
+            service_info: ServiceInfo::of::<R>(),
+        })
+    }
+}
+
+impl<R> TypedProvider for OnceProvider<R>
+where
+    R: Service,
+{
+    type Result = R;
+
+    fn provide_typed(
+        &mut self,
+        _injector: &Injector,
+        _request_info: &RequestInfo,
+    ) -> InjectResult<Svc<Self::Result>> {
+        self.take().map(Svc::new)
+    }
+
+    fn provide_owned_typed(
+        &mut self,
+        _injector: &Injector,
+        _request_info: &RequestInfo,
+    ) -> InjectResult<Box<Self::Result>> {
+        self.take().map(Box::new)
+    }
+}
+
+/// Create a provider which yields a value only once. This is useful for
+/// one-shot resources, like a token which should only be consumed a single
+/// time. See [`OnceProvider`] for more information.
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{once, InjectError, Injector};
+///
+/// struct StartupToken(String);
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(once(StartupToken("secret".to_owned())));
+///
+/// let injector = builder.build();
+/// let token: Box<StartupToken> = injector.get().unwrap();
+/// assert_eq!("secret", token.0);
+///
+/// match injector.get::<Box<StartupToken>>() {
+///     Err(InjectError::AlreadyConsumed { .. }) => {}
+///     _ => unreachable!("the token was already consumed"),
+/// }
+/// ```
+pub fn once<T: Service>(value: T) -> OnceProvider<T> {
+    OnceProvider::new(value)
+}
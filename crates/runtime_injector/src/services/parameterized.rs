@@ -0,0 +1,120 @@
+use crate::{
+    DynSvc, InjectResult, Injector, Lifetime, OwnedDynSvc, Provider,
+    ProviderMap, RequestInfo, RequestParameter, ServiceInfo, Svc,
+};
+use std::{collections::HashMap, time::Duration};
+
+/// The request parameters set by a module.
+pub(crate) type Parameters = HashMap<String, Box<dyn RequestParameter>>;
+
+/// Wraps each provider in a provider map so that the given parameters are
+/// added to each request made to them.
+pub(crate) fn parameterize(
+    provider_map: &mut ProviderMap,
+    parameters: &Svc<Parameters>,
+) {
+    for providers in provider_map.values_mut().flatten() {
+        *providers = std::mem::take(providers)
+            .into_iter()
+            .map(|inner| {
+                Box::new(ParameterizedProvider {
+                    inner,
+                    parameters: parameters.clone(),
+                }) as Box<dyn Provider>
+            })
+            .collect();
+    }
+}
+
+/// Adds request parameters to each request made to another provider. This is
+/// used to give providers from a module the parameters that were set by that
+/// module, even if another module sets different values for them.
+struct ParameterizedProvider {
+    inner: Box<dyn Provider>,
+    parameters: Svc<Parameters>,
+}
+
+impl ParameterizedProvider {
+    fn request_info(&self, request_info: &RequestInfo) -> RequestInfo {
+        let mut request_info = request_info.clone();
+        for (key, value) in self.parameters.iter() {
+            drop(request_info.insert_parameter_boxed(key, value.clone()));
+        }
+
+        request_info
+    }
+}
+
+impl Provider for ParameterizedProvider {
+    fn result(&self) -> ServiceInfo {
+        self.inner.result()
+    }
+
+    fn provide(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<DynSvc> {
+        let request_info = self.request_info(request_info);
+        self.inner.provide(injector, &request_info)
+    }
+
+    fn provide_owned(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<OwnedDynSvc> {
+        let request_info = self.request_info(request_info);
+        self.inner.provide_owned(injector, &request_info)
+    }
+
+    fn provide_all(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Vec<DynSvc>> {
+        let request_info = self.request_info(request_info);
+        self.inner.provide_all(injector, &request_info)
+    }
+
+    fn strong_count(&self) -> Option<usize> {
+        self.inner.strong_count()
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.inner.name()
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        self.inner.tags()
+    }
+
+    fn priority(&self) -> i32 {
+        self.inner.priority()
+    }
+
+    fn build_time(&self) -> Option<Duration> {
+        self.inner.build_time()
+    }
+
+    fn dependencies(&self) -> Vec<ServiceInfo> {
+        self.inner.dependencies()
+    }
+
+    fn is_available(
+        &self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> bool {
+        let request_info = self.request_info(request_info);
+        self.inner.is_available(injector, &request_info)
+    }
+
+    fn lifetime(&self) -> Lifetime {
+        self.inner.lifetime()
+    }
+
+    fn implementation(&self) -> ServiceInfo {
+        self.inner.implementation()
+    }
+}
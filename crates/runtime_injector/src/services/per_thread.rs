@@ -0,0 +1,187 @@
+use crate::{
+    DynSvc, InjectResult, Injector, Lifetime, MapContainer, MapContainerEx,
+    RequestInfo, Service, ServiceFactory, ServiceInfo, Svc, TypedProvider,
+};
+use std::{
+    collections::HashMap,
+    marker::PhantomData,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// The instances created by per-thread providers on a single thread, keyed
+/// by the ID of the provider that created them.
+type ThreadInstances = HashMap<usize, DynSvc>;
+
+#[cfg(feature = "arc")]
+type WeakInstances = std::sync::Weak<std::sync::Mutex<ThreadInstances>>;
+
+#[cfg(feature = "rc")]
+type WeakInstances = std::rc::Weak<std::cell::RefCell<ThreadInstances>>;
+
+thread_local! {
+    /// The instances created by each per-thread provider on this thread.
+    static INSTANCES: MapContainer<ThreadInstances> =
+        MapContainerEx::new(HashMap::new());
+}
+
+/// A service provider that creates a single instance of the service for each
+/// thread. The service is created during its first request on a thread, and
+/// any subsequent requests on the same thread return service pointers to the
+/// same instance. Requests made from different threads get different
+/// instances, even though they share the same provider.
+///
+/// Each instance is stored by the thread which created it, and is dropped
+/// once that thread exits or the provider is dropped, whichever happens
+/// first. The provider only keeps track of the threads which are still
+/// running, so spawning many short-lived threads doesn't leak instances.
+/// Additionally, a singleton which depends on a per-thread service will keep
+/// using the instance from the thread it was created on.
+///
+/// With the "arc" feature, all services must be [`Send`] and [`Sync`], so
+/// this can't be used to give each thread its own instance of a type which
+/// can't be sent between threads. The "rc" feature lifts this requirement,
+/// but the injector can then only be used from a single thread.
+pub struct ThreadLocalProvider<D, R, F>
+where
+    R: Service,
+    F: ServiceFactory<D, Result = R>,
+{
+    factory: F,
+    id: usize,
+    threads: Vec<WeakInstances>,
+    marker: PhantomData<fn(D) -> R>,
+}
+
+impl<D, R, F> ThreadLocalProvider<D, R, F>
+where
+    R: Service,
+    F: ServiceFactory<D, Result = R>,
+{
+    /// Creates a new [`ThreadLocalProvider`] using a service factory.
+    #[must_use]
+    pub fn new(func: F) -> Self {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+        ThreadLocalProvider {
+            factory: func,
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            threads: Vec::new(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<D, R, F> TypedProvider for ThreadLocalProvider<D, R, F>
+where
+    D: 'static,
+    R: Service,
+    F: ServiceFactory<D, Result = R>,
+{
+    type Result = R;
+
+    fn provide_typed(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Svc<Self::Result>> {
+        // The factory may request other per-thread services, so the instances
+        // must not be borrowed while it is running
+        let id = self.id;
+        let instances = INSTANCES.with(Clone::clone);
+        let existing =
+            instances.with_inner(|instances| instances.get(&id).cloned());
+        if let Some(service) = existing {
+            // Should never fail since only this provider uses its ID
+            return Ok(service.downcast().unwrap_or_else(|_| {
+                unreachable!("per-thread service has wrong type")
+            }));
+        }
+
+        let service = Svc::new(self.factory.invoke(injector, request_info)?);
+        let result: DynSvc = service.clone();
+        instances.with_inner_mut(|instances| instances.insert(id, result));
+
+        // Forget the threads which have exited
+        self.threads.retain(|thread| thread.strong_count() > 0);
+        self.threads.push(Svc::downgrade(&instances));
+        Ok(service)
+    }
+
+    fn dependencies_typed(&self) -> Vec<ServiceInfo> {
+        self.factory.dependencies()
+    }
+
+    fn lifetime_typed(&self) -> Lifetime {
+        Lifetime::PerThread
+    }
+}
+
+impl<D, R, F> Drop for ThreadLocalProvider<D, R, F>
+where
+    R: Service,
+    F: ServiceFactory<D, Result = R>,
+{
+    fn drop(&mut self) {
+        let id = self.id;
+        for instances in
+            self.threads.drain(..).filter_map(|thread| thread.upgrade())
+        {
+            // Dropping the instance may drop other per-thread providers, so it
+            // must not be dropped while the instances are borrowed
+            let instance =
+                instances.with_inner_mut(|instances| instances.remove(&id));
+            drop(instance);
+        }
+    }
+}
+
+/// Defines a conversion into a per-thread provider. This trait is
+/// automatically implemented for all service factories.
+pub trait IntoThreadLocal<D, R, F>
+where
+    R: Service,
+    F: ServiceFactory<D, Result = R>,
+{
+    /// Creates a per-thread provider. Per-thread providers create their
+    /// values once per thread (when first requested on that thread) and reuse
+    /// that value for each future request on the same thread.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{Injector, IntoThreadLocal, Svc};
+    ///
+    /// #[derive(Default)]
+    /// struct Foo;
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Foo::default.per_thread());
+    ///
+    /// let injector = builder.build();
+    /// let foo1: Svc<Foo> = injector.get().unwrap();
+    /// let foo2: Svc<Foo> = injector.get().unwrap();
+    /// assert!(Svc::ptr_eq(&foo1, &foo2));
+    /// ```
+    #[must_use]
+    fn per_thread(self) -> ThreadLocalProvider<D, R, F>;
+}
+
+impl<D, R, F> IntoThreadLocal<D, R, F> for F
+where
+    R: Service,
+    F: ServiceFactory<D, Result = R>,
+{
+    fn per_thread(self) -> ThreadLocalProvider<D, R, F> {
+        ThreadLocalProvider::new(self)
+    }
+}
+
+impl<D, R, F> From<F> for ThreadLocalProvider<D, R, F>
+where
+    R: Service,
+    F: ServiceFactory<D, Result = R>,
+{
+    fn from(func: F) -> Self {
+        func.per_thread()
+    }
+}
@@ -0,0 +1,305 @@
+use crate::{
+    InjectError, InjectResult, Injector, Lifetime, MapContainer,
+    MapContainerEx, RequestInfo, Service, ServiceFactory, ServiceInfo, Svc,
+    TypedProvider,
+};
+use std::{collections::VecDeque, marker::PhantomData, ops::Deref};
+
+/// What a [`PooledProvider`] does when its service is requested while every
+/// instance in the pool is checked out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PoolExhausted {
+    /// Creates a new instance which is not added to the pool. The instance is
+    /// dropped once it is no longer used, like a transient service.
+    CreateOverCap,
+
+    /// Fails the request with [`InjectError::PoolExhausted`].
+    Error,
+}
+
+/// The instances in a pool which aren't checked out, and how many instances
+/// are checked out.
+struct Pool<R> {
+    // Instances are checked in at the back, so the front is the least
+    // recently used
+    idle: VecDeque<Box<R>>,
+    checked_out: usize,
+}
+
+/// An instance checked out from a [`PooledProvider`]. The instance is checked
+/// back in once this is dropped. Since this is provided by a pooled provider
+/// instead of the instance itself, the instance is checked in once the last
+/// service pointer to it is dropped.
+pub struct Pooled<R: Service> {
+    instance: Option<Box<R>>,
+    // Instances created over the pool's cap aren't checked back in
+    pool: Option<MapContainer<Pool<R>>>,
+}
+
+impl<R: Service> Pooled<R> {
+    /// Whether the instance will be checked back in to its pool once it is
+    /// dropped. This is `false` for instances created while the pool was
+    /// exhausted with [`PoolExhausted::CreateOverCap`].
+    #[must_use]
+    pub fn is_pooled(pooled: &Self) -> bool {
+        pooled.pool.is_some()
+    }
+}
+
+impl<R: Service> Deref for Pooled<R> {
+    type Target = R;
+
+    fn deref(&self) -> &Self::Target {
+        // Should never panic since the instance is only taken when dropped
+        self.instance.as_ref().unwrap()
+    }
+}
+
+impl<R: Service> Drop for Pooled<R> {
+    fn drop(&mut self) {
+        if let (Some(instance), Some(pool)) =
+            (self.instance.take(), self.pool.take())
+        {
+            pool.with_inner_mut(|pool| {
+                pool.checked_out -= 1;
+                pool.idle.push_back(instance);
+            });
+        }
+    }
+}
+
+/// A service provider that reuses a bounded set of instances of the service.
+/// New instances are created until the pool is full, after which the least
+/// recently used instance which isn't checked out is provided. This sits
+/// between a singleton, which shares one instance, and a transient service,
+/// which creates a new instance for each request.
+///
+/// The provided service is a [`Pooled<R>`] guard rather than the instance
+/// itself, so the service should be requested as `Svc<Pooled<R>>` or
+/// `Box<Pooled<R>>`. The instance is checked out until the guard is dropped,
+/// then it is checked back in to the pool. A guard which is stored by
+/// another service, like a singleton, is never checked back in.
+///
+/// The pool is shared between the provider and the guards it creates. With
+/// the "arc" feature, the pool is behind a mutex, so guards can be dropped on
+/// any thread. The mutex is only locked while checking instances out or in,
+/// and never while creating an instance.
+///
+/// By default, a new instance is created if every instance is checked out.
+/// See [`PooledProvider::when_exhausted()`] to configure this.
+pub struct PooledProvider<D, R, F>
+where
+    R: Service,
+    F: ServiceFactory<D, Result = R>,
+{
+    factory: F,
+    max: usize,
+    when_exhausted: PoolExhausted,
+    pool: MapContainer<Pool<R>>,
+    marker: PhantomData<fn(D) -> R>,
+}
+
+impl<D, R, F> PooledProvider<D, R, F>
+where
+    R: Service,
+    F: ServiceFactory<D, Result = R>,
+{
+    /// Creates a new [`PooledProvider`] using a service factory. The pool
+    /// holds at most `max` instances.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `max` is zero.
+    #[must_use]
+    pub fn new(func: F, max: usize) -> Self {
+        assert!(max > 0, "a pool must be able to hold at least one instance");
+
+        PooledProvider {
+            factory: func,
+            max,
+            when_exhausted: PoolExhausted::CreateOverCap,
+            pool: MapContainerEx::new(Pool {
+                idle: VecDeque::new(),
+                checked_out: 0,
+            }),
+            marker: PhantomData,
+        }
+    }
+
+    /// Sets what happens when the service is requested while every instance
+    /// in the pool is checked out.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     InjectError, Injector, IntoPooled, PoolExhausted, Pooled, Svc,
+    /// };
+    ///
+    /// #[derive(Default)]
+    /// struct Connection(u32);
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(
+    ///     Connection::default
+    ///         .pooled(1)
+    ///         .when_exhausted(PoolExhausted::Error),
+    /// );
+    ///
+    /// let injector = builder.build();
+    /// let connection: Svc<Pooled<Connection>> = injector.get().unwrap();
+    /// assert!(matches!(
+    ///     injector.get::<Svc<Pooled<Connection>>>(),
+    ///     Err(InjectError::PoolExhausted { max: 1, .. })
+    /// ));
+    ///
+    /// // Dropping the connection checks it back in
+    /// drop(connection);
+    /// let _connection: Svc<Pooled<Connection>> = injector.get().unwrap();
+    /// ```
+    #[must_use]
+    pub fn when_exhausted(mut self, when_exhausted: PoolExhausted) -> Self {
+        self.when_exhausted = when_exhausted;
+        self
+    }
+
+    /// Checks out the least recently used instance which isn't checked out,
+    /// creating a new instance if needed.
+    fn check_out(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Pooled<R>> {
+        let max = self.max;
+        let (instance, reserved) = self.pool.with_inner_mut(|pool| {
+            if let Some(instance) = pool.idle.pop_front() {
+                pool.checked_out += 1;
+                (Some(instance), true)
+            } else if pool.checked_out < max {
+                // Reserve a place in the pool for the new instance
+                pool.checked_out += 1;
+                (None, true)
+            } else {
+                (None, false)
+            }
+        });
+
+        if let Some(instance) = instance {
+            return Ok(Pooled {
+                instance: Some(instance),
+                pool: Some(self.pool.clone()),
+            });
+        }
+
+        if reserved {
+            return match self.factory.invoke(injector, request_info) {
+                Ok(instance) => Ok(Pooled {
+                    instance: Some(Box::new(instance)),
+                    pool: Some(self.pool.clone()),
+                }),
+                Err(error) => {
+                    self.pool.with_inner_mut(|pool| pool.checked_out -= 1);
+                    Err(error)
+                }
+            };
+        }
+
+        match self.when_exhausted {
+            PoolExhausted::CreateOverCap => {
+                let instance = self.factory.invoke(injector, request_info)?;
+                Ok(Pooled {
+                    instance: Some(Box::new(instance)),
+                    pool: None,
+                })
+            }
+            PoolExhausted::Error => Err(InjectError::PoolExhausted {
+                service_info: ServiceInfo::of::<R>(),
+                max: self.max,
+            }),
+        }
+    }
+}
+
+impl<D, R, F> TypedProvider for PooledProvider<D, R, F>
+where
+    D: 'static,
+    R: Service,
+    F: ServiceFactory<D, Result = R>,
+{
+    type Result = Pooled<R>;
+
+    fn provide_typed(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Svc<Self::Result>> {
+        self.check_out(injector, request_info).map(Svc::new)
+    }
+
+    fn provide_owned_typed(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Box<Self::Result>> {
+        self.check_out(injector, request_info).map(Box::new)
+    }
+
+    fn dependencies_typed(&self) -> Vec<ServiceInfo> {
+        self.factory.dependencies()
+    }
+
+    fn lifetime_typed(&self) -> Lifetime {
+        Lifetime::Pooled
+    }
+}
+
+/// Defines a conversion into a pooled provider. This trait is automatically
+/// implemented for all service factories.
+pub trait IntoPooled<D, R, F>
+where
+    R: Service,
+    F: ServiceFactory<D, Result = R>,
+{
+    /// Creates a pooled provider. Pooled providers reuse up to `max`
+    /// instances of their service, creating new instances until the pool is
+    /// full. See [`PooledProvider`] for more information.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{Injector, IntoPooled, Pooled, Svc};
+    ///
+    /// #[derive(Default)]
+    /// struct Connection(u32);
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Connection::default.pooled(2));
+    ///
+    /// let injector = builder.build();
+    /// let connection1: Svc<Pooled<Connection>> = injector.get().unwrap();
+    /// let connection2: Svc<Pooled<Connection>> = injector.get().unwrap();
+    /// let first: *const Connection = &**connection1;
+    /// assert_ne!(first, &**connection2 as *const Connection);
+    ///
+    /// // Checked in connections are reused
+    /// drop(connection1);
+    /// let connection3: Svc<Pooled<Connection>> = injector.get().unwrap();
+    /// assert_eq!(first, &**connection3 as *const Connection);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `max` is zero.
+    #[must_use]
+    fn pooled(self, max: usize) -> PooledProvider<D, R, F>;
+}
+
+impl<D, R, F> IntoPooled<D, R, F> for F
+where
+    R: Service,
+    F: ServiceFactory<D, Result = R>,
+{
+    fn pooled(self, max: usize) -> PooledProvider<D, R, F> {
+        PooledProvider::new(self, max)
+    }
+}
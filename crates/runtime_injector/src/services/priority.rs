@@ -0,0 +1,94 @@
+use crate::{
+    InjectResult, Injector, Lifetime, RequestInfo, ServiceInfo, Svc,
+    TypedProvider,
+};
+use std::time::Duration;
+
+/// Provides a service with a priority. Providers with a higher priority are
+/// preferred over other providers of the same service. See
+/// [`TypedProvider::with_priority()`] for more information.
+pub struct PrioritizedProvider<P>
+where
+    P: TypedProvider,
+{
+    inner: P,
+    priority: i32,
+}
+
+impl<P> PrioritizedProvider<P>
+where
+    P: TypedProvider,
+{
+    pub(crate) fn new(inner: P, priority: i32) -> Self {
+        PrioritizedProvider { inner, priority }
+    }
+}
+
+impl<P> TypedProvider for PrioritizedProvider<P>
+where
+    P: TypedProvider,
+{
+    type Result = P::Result;
+
+    #[inline]
+    fn provide_typed(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Svc<Self::Result>> {
+        self.inner.provide_typed(injector, request_info)
+    }
+
+    #[inline]
+    fn provide_owned_typed(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Box<Self::Result>> {
+        self.inner.provide_owned_typed(injector, request_info)
+    }
+
+    #[inline]
+    fn strong_count_typed(&self) -> Option<usize> {
+        self.inner.strong_count_typed()
+    }
+
+    #[inline]
+    fn name_typed(&self) -> Option<&str> {
+        self.inner.name_typed()
+    }
+
+    #[inline]
+    fn tags_typed(&self) -> Vec<&str> {
+        self.inner.tags_typed()
+    }
+
+    #[inline]
+    fn priority_typed(&self) -> i32 {
+        self.priority
+    }
+
+    #[inline]
+    fn build_time_typed(&self) -> Option<Duration> {
+        self.inner.build_time_typed()
+    }
+
+    #[inline]
+    fn dependencies_typed(&self) -> Vec<ServiceInfo> {
+        self.inner.dependencies_typed()
+    }
+
+    #[inline]
+    fn is_available_typed(
+        &self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> bool {
+        self.inner.is_available_typed(injector, request_info)
+    }
+
+    #[inline]
+    fn lifetime_typed(&self) -> Lifetime {
+        self.inner.lifetime_typed()
+    }
+}
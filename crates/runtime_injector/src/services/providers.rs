@@ -1,8 +1,11 @@
-use std::marker::PhantomData;
+use std::{marker::PhantomData, time::Duration};
 
 use crate::{
-    DynSvc, InjectError, InjectResult, Injector, Interface, InterfaceFor,
-    OwnedDynSvc, RequestInfo, Service, ServiceInfo, Svc,
+    AlsoAsProvider, DynSvc, FallbackProvider, InjectError, InjectResult,
+    Injector, InspectProvider, Interface, InterfaceFor, InterfacesProvider,
+    Lifetime, MapErrProvider, MappedProvider, NamedProvider, OwnedDynSvc,
+    PrioritizedProvider, RequestInfo, Service, ServiceInfo, Svc,
+    TaggedProvider,
 };
 
 /// Weakly typed service provider.
@@ -32,6 +35,99 @@ pub trait Provider: Service {
             service_info: self.result(),
         })
     }
+
+    /// Provides every instance of the service this provider can create. Most
+    /// providers create exactly one instance, but some may create several at
+    /// once, like a [`ManyProvider`](crate::ManyProvider).
+    fn provide_all(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Vec<DynSvc>> {
+        self.provide(injector, request_info)
+            .map(|service| vec![service])
+    }
+
+    /// Takes any additional providers which should be registered alongside
+    /// this provider. These are collected once when this provider is added to
+    /// an [`InjectorBuilder`](crate::InjectorBuilder) or
+    /// [`Module`](crate::Module).
+    fn take_aliases(&mut self) -> Vec<Box<dyn Provider>> {
+        Vec::new()
+    }
+
+    /// Gets the number of strong references to the service instance stored
+    /// by this provider, or `None` if the provider doesn't store an instance
+    /// of its service. See [`RefCount`](crate::RefCount) for more
+    /// information.
+    fn strong_count(&self) -> Option<usize> {
+        None
+    }
+
+    /// Gets the name this provider's service was registered under, if any.
+    /// See [`TypedProvider::with_name()`] for more information.
+    fn name(&self) -> Option<&str> {
+        None
+    }
+
+    /// Gets the tags this provider's service was registered with. See
+    /// [`TypedProvider::with_tag()`] for more information.
+    fn tags(&self) -> Vec<&str> {
+        Vec::new()
+    }
+
+    /// Gets the priority of this provider. Providers with a higher priority
+    /// are preferred over other providers for the same service. See
+    /// [`TypedProvider::with_priority()`] for more information.
+    fn priority(&self) -> i32 {
+        0
+    }
+
+    /// Gets how long this provider took to create its stored instance, if it
+    /// has one and its creation was timed. See
+    /// [`InjectorBuilder::time_singletons()`] for more information.
+    ///
+    /// [`InjectorBuilder::time_singletons()`]: crate::InjectorBuilder::time_singletons
+    fn build_time(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Gets the services which must be registered for this provider to
+    /// provide its service. See [`InjectorBuilder::validate()`] for more
+    /// information.
+    ///
+    /// [`InjectorBuilder::validate()`]: crate::InjectorBuilder::validate
+    fn dependencies(&self) -> Vec<ServiceInfo> {
+        Vec::new()
+    }
+
+    /// Checks whether this provider would provide its service for a request
+    /// without activating it. Providers which can't tell without activating
+    /// the service should return `true`. This is used to count the
+    /// implementations of a service which are available, like with
+    /// [`Services::count_available()`](crate::Services::count_available).
+    fn is_available(
+        &self,
+        _injector: &Injector,
+        _request_info: &RequestInfo,
+    ) -> bool {
+        true
+    }
+
+    /// Gets how long the instances created by this provider live. This is
+    /// only used to describe the provider, and does not affect how it
+    /// creates its instances.
+    fn lifetime(&self) -> Lifetime {
+        Lifetime::Custom
+    }
+
+    /// The [`ServiceInfo`] which describes the concrete type of the service
+    /// created by this provider. For most providers, this is the same as
+    /// [`Provider::result()`]. For providers of an interface, this is the
+    /// implementation of that interface.
+    fn implementation(&self) -> ServiceInfo {
+        self.result()
+    }
 }
 
 impl<T> Provider for T
@@ -59,6 +155,42 @@ where
         let result = self.provide_owned_typed(injector, request_info)?;
         Ok(result as OwnedDynSvc)
     }
+
+    fn strong_count(&self) -> Option<usize> {
+        self.strong_count_typed()
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name_typed()
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        self.tags_typed()
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority_typed()
+    }
+
+    fn build_time(&self) -> Option<Duration> {
+        self.build_time_typed()
+    }
+
+    fn dependencies(&self) -> Vec<ServiceInfo> {
+        self.dependencies_typed()
+    }
+
+    fn is_available(
+        &self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> bool {
+        self.is_available_typed(injector, request_info)
+    }
+
+    fn lifetime(&self) -> Lifetime {
+        self.lifetime_typed()
+    }
 }
 
 /// A strongly-typed service provider.
@@ -122,6 +254,61 @@ pub trait TypedProvider: Sized + Provider {
         })
     }
 
+    /// Gets the number of strong references to the service instance stored
+    /// by this provider, or `None` if the provider doesn't store an instance
+    /// of its service. See [`Provider::strong_count()`] for more information.
+    fn strong_count_typed(&self) -> Option<usize> {
+        None
+    }
+
+    /// Gets the name this provider's service was registered under, if any.
+    /// See [`Provider::name()`] for more information.
+    fn name_typed(&self) -> Option<&str> {
+        None
+    }
+
+    /// Gets the tags this provider's service was registered with. See
+    /// [`Provider::tags()`] for more information.
+    fn tags_typed(&self) -> Vec<&str> {
+        Vec::new()
+    }
+
+    /// Gets the priority of this provider. See [`Provider::priority()`] for
+    /// more information.
+    fn priority_typed(&self) -> i32 {
+        0
+    }
+
+    /// Gets how long this provider took to create its stored instance. See
+    /// [`Provider::build_time()`] for more information.
+    fn build_time_typed(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Gets the services which must be registered for this provider to
+    /// provide its service. See [`Provider::dependencies()`] for more
+    /// information.
+    fn dependencies_typed(&self) -> Vec<ServiceInfo> {
+        Vec::new()
+    }
+
+    /// Checks whether this provider would provide its service for a request
+    /// without activating it. See [`Provider::is_available()`] for more
+    /// information.
+    fn is_available_typed(
+        &self,
+        _injector: &Injector,
+        _request_info: &RequestInfo,
+    ) -> bool {
+        true
+    }
+
+    /// Gets how long the instances created by this provider live. See
+    /// [`Provider::lifetime()`] for more information.
+    fn lifetime_typed(&self) -> Lifetime {
+        Lifetime::Custom
+    }
+
     /// Provides this service as an implementation of a particular interface.
     /// Rather than requesting this service with its concrete type, it can
     /// instead be requested by its interface type.
@@ -166,6 +353,477 @@ pub trait TypedProvider: Sized + Provider {
             marker: PhantomData,
         }
     }
+
+    /// Provides this service as itself, and additionally as an
+    /// implementation of another interface. Unlike
+    /// [`with_interface()`](TypedProvider::with_interface), the service can
+    /// still be requested with its concrete type, and all registrations share
+    /// the same provider. This means a singleton is only ever created once,
+    /// regardless of which registration it's requested through.
+    ///
+    /// This is useful for cross-cutting concerns, like collecting every
+    /// service which needs to be initialized.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     interface, Injector, IntoSingleton, Service, Services, Svc,
+    ///     TypedProvider,
+    /// };
+    ///
+    /// trait Initializable: Service {
+    ///     fn init(&self);
+    /// }
+    ///
+    /// interface!(dyn Initializable = [Database, Cache]);
+    ///
+    /// #[derive(Default)]
+    /// struct Database;
+    /// impl Initializable for Database {
+    ///     fn init(&self) {}
+    /// }
+    ///
+    /// #[derive(Default)]
+    /// struct Cache;
+    /// impl Initializable for Cache {
+    ///     fn init(&self) {}
+    /// }
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(
+    ///     Database::default
+    ///         .singleton()
+    ///         .also_as::<dyn Initializable>(),
+    /// );
+    /// builder.provide(Cache::default.singleton().also_as::<dyn Initializable>());
+    ///
+    /// let injector = builder.build();
+    /// let mut initializables: Services<dyn Initializable> =
+    ///     injector.get().unwrap();
+    /// for initializable in initializables.get_all() {
+    ///     initializable.unwrap().init();
+    /// }
+    ///
+    /// // The services can still be requested by their concrete types
+    /// let _database: Svc<Database> = injector.get().unwrap();
+    /// ```
+    fn also_as<I: ?Sized + InterfaceFor<Self::Result>>(
+        self,
+    ) -> AlsoAsProvider<Self> {
+        AlsoAsProvider::new(self).also_as::<I>()
+    }
+
+    /// Provides this service only as implementations of one or more
+    /// interfaces. Like [`with_interface()`](TypedProvider::with_interface),
+    /// the service can't be requested with its concrete type, but additional
+    /// interfaces can be added with [`InterfacesProvider::also_as()`]. All
+    /// registrations share the same provider, so a singleton is only ever
+    /// created once and each interface refers to the same instance.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     interface, Injector, IntoSingleton, Service, Svc, TypedProvider,
+    /// };
+    ///
+    /// trait Reader: Service {}
+    /// trait Writer: Service {}
+    /// interface!(dyn Reader = [File], dyn Writer = [File]);
+    ///
+    /// #[derive(Default)]
+    /// struct File;
+    /// impl Reader for File {}
+    /// impl Writer for File {}
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(
+    ///     File::default
+    ///         .singleton()
+    ///         .with_interfaces::<dyn Reader>()
+    ///         .also_as::<dyn Writer>(),
+    /// );
+    ///
+    /// let injector = builder.build();
+    /// let reader: Svc<dyn Reader> = injector.get().unwrap();
+    /// let writer: Svc<dyn Writer> = injector.get().unwrap();
+    /// assert_eq!(
+    ///     Svc::as_ptr(&reader).cast::<()>(),
+    ///     Svc::as_ptr(&writer).cast::<()>()
+    /// );
+    ///
+    /// // It can't be requested through its original type
+    /// assert!(injector.get::<Svc<File>>().is_err());
+    /// ```
+    fn with_interfaces<I: ?Sized + InterfaceFor<Self::Result>>(
+        self,
+    ) -> InterfacesProvider<I, Self> {
+        InterfacesProvider::new(self)
+    }
+
+    /// Provides this service under a name. The service can still be
+    /// requested normally, but it can additionally be requested by its name
+    /// with [`Injector::get_named()`], or along with the other named
+    /// implementations of its interface, for example as a
+    /// [`BTreeMap<String, Svc<T>>`](std::collections::BTreeMap).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     interface, Injector, IntoSingleton, Service, Svc, TypedProvider,
+    /// };
+    /// use std::collections::BTreeMap;
+    ///
+    /// trait Cache: Service {}
+    /// interface!(dyn Cache = [RedisCache, MemoryCache]);
+    ///
+    /// #[derive(Default)]
+    /// struct RedisCache;
+    /// impl Cache for RedisCache {}
+    ///
+    /// #[derive(Default)]
+    /// struct MemoryCache;
+    /// impl Cache for MemoryCache {}
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(
+    ///     RedisCache::default
+    ///         .singleton()
+    ///         .with_name("redis")
+    ///         .with_interface::<dyn Cache>(),
+    /// );
+    /// builder.provide(
+    ///     MemoryCache::default
+    ///         .singleton()
+    ///         .with_name("memory")
+    ///         .with_interface::<dyn Cache>(),
+    /// );
+    ///
+    /// let injector = builder.build();
+    /// let caches: BTreeMap<String, Svc<dyn Cache>> = injector.get().unwrap();
+    /// let names: Vec<_> = caches.keys().map(String::as_str).collect();
+    /// assert_eq!(vec!["memory", "redis"], names);
+    /// ```
+    fn with_name(self, name: impl Into<String>) -> NamedProvider<Self> {
+        NamedProvider::new(self, name.into())
+    }
+
+    /// Provides this service with a tag. The service can still be requested
+    /// normally, but it can additionally be requested along with every other
+    /// implementation of its interface which has the same tag using
+    /// [`Injector::get_tagged()`]. Unlike names, any number of providers can
+    /// share a tag, and a provider can have any number of tags.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     interface, Injector, IntoSingleton, Service, Svc, TypedProvider,
+    /// };
+    ///
+    /// trait Task: Service {}
+    /// interface!(dyn Task = [Migrate, Warmup, Flush]);
+    ///
+    /// #[derive(Default)]
+    /// struct Migrate;
+    /// impl Task for Migrate {}
+    ///
+    /// #[derive(Default)]
+    /// struct Warmup;
+    /// impl Task for Warmup {}
+    ///
+    /// #[derive(Default)]
+    /// struct Flush;
+    /// impl Task for Flush {}
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(
+    ///     Migrate::default
+    ///         .singleton()
+    ///         .with_tag("startup")
+    ///         .with_interface::<dyn Task>(),
+    /// );
+    /// builder.provide(
+    ///     Warmup::default
+    ///         .singleton()
+    ///         .with_tag("startup")
+    ///         .with_interface::<dyn Task>(),
+    /// );
+    /// builder.provide(
+    ///     Flush::default
+    ///         .singleton()
+    ///         .with_tag("shutdown")
+    ///         .with_interface::<dyn Task>(),
+    /// );
+    ///
+    /// let injector = builder.build();
+    /// let startup: Vec<Svc<dyn Task>> =
+    ///     injector.get_tagged("startup").unwrap();
+    /// assert_eq!(2, startup.len());
+    /// ```
+    fn with_tag(self, tag: impl Into<String>) -> TaggedProvider<Self> {
+        TaggedProvider::new(self, tag.into())
+    }
+
+    /// Sets the priority of this provider. Providers without a priority have
+    /// a priority of zero. When a single implementation of a service is
+    /// requested, like with [`Svc<T>`], and the service has multiple
+    /// providers, the provider with the highest priority is used instead of
+    /// failing. The request still fails if multiple providers share the
+    /// highest priority. Requests for every implementation of a service,
+    /// like [`Services<T>`](crate::Services), return the implementations in
+    /// descending order of priority, and providers with the same priority
+    /// are ordered by when they were registered.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     interface, Injector, IntoSingleton, Service, Svc, TypedProvider,
+    /// };
+    ///
+    /// trait Cache: Service {
+    ///     fn name(&self) -> &'static str;
+    /// }
+    /// interface!(dyn Cache = [MemoryCache, RedisCache]);
+    ///
+    /// #[derive(Default)]
+    /// struct MemoryCache;
+    /// impl Cache for MemoryCache {
+    ///     fn name(&self) -> &'static str {
+    ///         "memory"
+    ///     }
+    /// }
+    ///
+    /// #[derive(Default)]
+    /// struct RedisCache;
+    /// impl Cache for RedisCache {
+    ///     fn name(&self) -> &'static str {
+    ///         "redis"
+    ///     }
+    /// }
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(
+    ///     MemoryCache::default
+    ///         .singleton()
+    ///         .with_interface::<dyn Cache>(),
+    /// );
+    /// builder.provide(
+    ///     RedisCache::default
+    ///         .singleton()
+    ///         .with_priority(10)
+    ///         .with_interface::<dyn Cache>(),
+    /// );
+    ///
+    /// let injector = builder.build();
+    /// let cache: Svc<dyn Cache> = injector.get().unwrap();
+    /// assert_eq!("redis", cache.name());
+    ///
+    /// let caches: Vec<Svc<dyn Cache>> = injector.get().unwrap();
+    /// let names: Vec<_> = caches.iter().map(|cache| cache.name()).collect();
+    /// assert_eq!(vec!["redis", "memory"], names);
+    /// ```
+    fn with_priority(self, priority: i32) -> PrioritizedProvider<Self> {
+        PrioritizedProvider::new(self, priority)
+    }
+
+    /// Provides a service derived from this provider's service. The derived
+    /// service is registered as its own type and can be requested
+    /// independently. If this provider stores its instance, like a
+    /// singleton, then the mapping only runs once and the derived instance is
+    /// reused. Otherwise, the mapping runs each time the derived service is
+    /// requested.
+    ///
+    /// The original service is only created to be mapped, and is not
+    /// registered on its own. To request both services, register the original
+    /// service separately and depend on it from the derived service's
+    /// factory instead.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{Injector, IntoSingleton, Svc, TypedProvider};
+    ///
+    /// #[derive(Default)]
+    /// struct Config {
+    ///     port: u16,
+    /// }
+    ///
+    /// struct Address(String);
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Config::default.singleton().map_result(
+    ///     |config: Svc<Config>, _| Ok(Address(format!("0.0.0.0:{}", config.port))),
+    /// ));
+    ///
+    /// let injector = builder.build();
+    /// let address1: Svc<Address> = injector.get().unwrap();
+    /// let address2: Svc<Address> = injector.get().unwrap();
+    /// assert_eq!("0.0.0.0:0", address1.0);
+    /// assert!(Svc::ptr_eq(&address1, &address2));
+    /// ```
+    fn map_result<U, F>(self, mapper: F) -> MappedProvider<Self, U, F>
+    where
+        U: Service,
+        F: Service + Fn(Svc<Self::Result>, &Injector) -> InjectResult<U>,
+    {
+        MappedProvider::new(self, mapper)
+    }
+
+    /// Replaces the errors returned by this provider. The mapping only runs
+    /// when this provider fails to provide its service, and can be used to
+    /// add context to an error or replace it with a friendlier one before it
+    /// propagates to the caller.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     InjectError, Injector, IntoFallible, IntoTransient, ServiceInfo,
+    ///     Svc, TypedProvider,
+    /// };
+    ///
+    /// struct Database;
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(
+    ///     (|| -> Result<Database, std::fmt::Error> { Err(std::fmt::Error) })
+    ///         .fallible()
+    ///         .transient()
+    ///         .map_err(|error| InjectError::ActivationFailed {
+    ///             service_info: ServiceInfo::of::<Database>(),
+    ///             inner: format!("is the database running? ({})", error)
+    ///                 .into(),
+    ///         }),
+    /// );
+    ///
+    /// let injector = builder.build();
+    /// match injector.get::<Svc<Database>>() {
+    ///     Err(InjectError::ActivationFailed { inner, .. }) => {
+    ///         assert!(inner.to_string().starts_with("is the database running?"));
+    ///     }
+    ///     Err(error) => Err(error).unwrap(),
+    ///     Ok(_) => unreachable!("activation should have failed"),
+    /// }
+    /// ```
+    fn map_err<F>(self, mapper: F) -> MapErrProvider<Self, F>
+    where
+        F: Service + Fn(InjectError) -> InjectError,
+    {
+        MapErrProvider::new(self, mapper)
+    }
+
+    /// Calls a function each time this provider successfully provides its
+    /// service, then returns the same service unchanged. This is useful for
+    /// logging when services are provided, or for checking how often a
+    /// service is created. If this provider stores its instance, like a
+    /// singleton, then the function is called for each request, even though
+    /// the same instance is returned each time.
+    ///
+    /// The function is not called when an owned service is requested, since
+    /// the owned service can't be shared with it.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     interface, Injector, IntoSingleton, Service, Svc, TypedProvider,
+    /// };
+    /// use std::sync::{
+    ///     atomic::{AtomicUsize, Ordering},
+    ///     Arc,
+    /// };
+    ///
+    /// trait Foo: Service {}
+    /// interface!(dyn Foo = [Bar]);
+    ///
+    /// #[derive(Default)]
+    /// struct Bar;
+    /// impl Foo for Bar {}
+    ///
+    /// let requests = Arc::new(AtomicUsize::new(0));
+    /// let mut builder = Injector::builder();
+    /// builder.provide({
+    ///     let requests = requests.clone();
+    ///     Bar::default
+    ///         .singleton()
+    ///         .inspect(move |_bar, _request_info| {
+    ///             requests.fetch_add(1, Ordering::Relaxed);
+    ///         })
+    ///         .with_interface::<dyn Foo>()
+    /// });
+    ///
+    /// let injector = builder.build();
+    /// let _foo1: Svc<dyn Foo> = injector.get().unwrap();
+    /// let _foo2: Svc<dyn Foo> = injector.get().unwrap();
+    /// assert_eq!(2, requests.load(Ordering::Relaxed));
+    /// ```
+    fn inspect<F>(self, inspector: F) -> InspectProvider<Self, F>
+    where
+        F: Service + Fn(&Svc<Self::Result>, &RequestInfo),
+    {
+        InspectProvider::new(self, inspector)
+    }
+
+    /// Provides this service using another provider if this provider fails
+    /// to activate it. Only [`InjectError::ActivationFailed`] errors cause
+    /// the fallback to be used. Other errors, like missing dependencies or
+    /// unmet conditions, are returned as-is.
+    ///
+    /// If this provider stores its instance, like a singleton, then once it
+    /// successfully activates its service, that instance is reused and the
+    /// fallback is never attempted again.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{
+    ///     Injector, IntoFallible, IntoSingleton, Svc, TypedProvider,
+    /// };
+    /// use std::fmt::{self, Display, Formatter};
+    ///
+    /// #[derive(Debug)]
+    /// struct ConnectionError;
+    ///
+    /// impl Display for ConnectionError {
+    ///     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    ///         write!(f, "failed to connect")
+    ///     }
+    /// }
+    ///
+    /// impl std::error::Error for ConnectionError {}
+    ///
+    /// struct Database(&'static str);
+    ///
+    /// fn connect_remote() -> Result<Database, ConnectionError> {
+    ///     Err(ConnectionError)
+    /// }
+    ///
+    /// fn connect_local() -> Database {
+    ///     Database("local")
+    /// }
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(
+    ///     connect_remote
+    ///         .fallible()
+    ///         .singleton()
+    ///         .or_else(connect_local.singleton()),
+    /// );
+    ///
+    /// let injector = builder.build();
+    /// let database: Svc<Database> = injector.get().unwrap();
+    /// assert_eq!("local", database.0);
+    /// ```
+    fn or_else<F>(self, fallback: F) -> FallbackProvider<Self, F>
+    where
+        F: TypedProvider<Result = Self::Result>,
+    {
+        FallbackProvider::new(self, fallback)
+    }
 }
 
 /// Provides a service as an implementation of an interface. See
@@ -179,6 +837,26 @@ where
     marker: PhantomData<fn() -> I>,
 }
 
+impl<I, P> InterfaceProvider<I, P>
+where
+    P: TypedProvider,
+    I: ?Sized + InterfaceFor<P::Result>,
+{
+    /// Provides this service under a name. This is equivalent to calling
+    /// [`TypedProvider::with_name()`] before providing the service as an
+    /// implementation of its interface.
+    #[must_use]
+    pub fn with_name(
+        self,
+        name: impl Into<String>,
+    ) -> InterfaceProvider<I, NamedProvider<P>> {
+        InterfaceProvider {
+            inner: self.inner.with_name(name),
+            marker: PhantomData,
+        }
+    }
+}
+
 impl<I, P> Provider for InterfaceProvider<I, P>
 where
     P: TypedProvider,
@@ -203,4 +881,44 @@ where
     ) -> InjectResult<OwnedDynSvc> {
         self.inner.provide_owned(injector, request_info)
     }
+
+    fn strong_count(&self) -> Option<usize> {
+        self.inner.strong_count_typed()
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.inner.name_typed()
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        self.inner.tags_typed()
+    }
+
+    fn priority(&self) -> i32 {
+        self.inner.priority_typed()
+    }
+
+    fn build_time(&self) -> Option<Duration> {
+        self.inner.build_time_typed()
+    }
+
+    fn dependencies(&self) -> Vec<ServiceInfo> {
+        self.inner.dependencies_typed()
+    }
+
+    fn is_available(
+        &self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> bool {
+        self.inner.is_available_typed(injector, request_info)
+    }
+
+    fn lifetime(&self) -> Lifetime {
+        self.inner.lifetime_typed()
+    }
+
+    fn implementation(&self) -> ServiceInfo {
+        ServiceInfo::of::<P::Result>()
+    }
 }
@@ -0,0 +1,172 @@
+use crate::{
+    DynSvc, InjectResult, Injector, Lifetime, MapContainer, MapContainerEx,
+    RequestInfo, Service, ServiceFactory, ServiceInfo, Svc, TypedProvider,
+};
+use std::{
+    collections::HashMap,
+    marker::PhantomData,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Storage for the instances of scoped services created within a scope.
+#[derive(Clone)]
+pub(crate) struct ScopeStorage {
+    instances: MapContainer<HashMap<usize, DynSvc>>,
+}
+
+impl ScopeStorage {
+    pub fn new() -> Self {
+        ScopeStorage {
+            instances: MapContainerEx::new(HashMap::new()),
+        }
+    }
+
+    fn get(&self, id: usize) -> Option<DynSvc> {
+        self.instances
+            .with_inner(|instances| instances.get(&id).cloned())
+    }
+
+    /// Stores an instance, returning the instance that was already stored
+    /// under the same ID if there is one.
+    fn get_or_insert(&self, id: usize, instance: DynSvc) -> DynSvc {
+        self.instances.with_inner_mut(|instances| {
+            instances.entry(id).or_insert(instance).clone()
+        })
+    }
+}
+
+impl Default for ScopeStorage {
+    fn default() -> Self {
+        ScopeStorage::new()
+    }
+}
+
+/// A service provider that creates a single instance of the service for each
+/// scope. The service is created during its first request within a scope, and
+/// any subsequent requests within the same scope return service pointers to
+/// the same instance. Each injector created with
+/// [`Injector::create_scope()`] is a new scope. An injector created by a
+/// builder has its own root scope.
+///
+/// Scoped services can depend on singletons, and those singletons are shared
+/// with every other scope. However, a singleton which depends on a scoped
+/// service will keep using the instance from the scope it was created in.
+pub struct ScopedProvider<D, R, F>
+where
+    R: Service,
+    F: ServiceFactory<D, Result = R>,
+{
+    factory: F,
+    id: usize,
+    marker: PhantomData<fn(D) -> R>,
+}
+
+impl<D, R, F> ScopedProvider<D, R, F>
+where
+    R: Service,
+    F: ServiceFactory<D, Result = R>,
+{
+    /// Creates a new [`ScopedProvider`] using a service factory.
+    #[must_use]
+    pub fn new(func: F) -> Self {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+        ScopedProvider {
+            factory: func,
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<D, R, F> TypedProvider for ScopedProvider<D, R, F>
+where
+    D: 'static,
+    R: Service,
+    F: ServiceFactory<D, Result = R>,
+{
+    type Result = R;
+
+    fn provide_typed(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Svc<Self::Result>> {
+        let scope = injector.scope();
+        let service = if let Some(service) = scope.get(self.id) {
+            service
+        } else {
+            let result = self.factory.invoke(injector, request_info)?;
+            scope.get_or_insert(self.id, Svc::new(result))
+        };
+
+        // Should never fail since only this provider uses its ID
+        Ok(service
+            .downcast()
+            .unwrap_or_else(|_| unreachable!("scoped service has wrong type")))
+    }
+
+    fn dependencies_typed(&self) -> Vec<ServiceInfo> {
+        self.factory.dependencies()
+    }
+
+    fn lifetime_typed(&self) -> Lifetime {
+        Lifetime::Scoped
+    }
+}
+
+/// Defines a conversion into a scoped provider. This trait is automatically
+/// implemented for all service factories.
+pub trait IntoScoped<D, R, F>
+where
+    R: Service,
+    F: ServiceFactory<D, Result = R>,
+{
+    /// Creates a scoped provider. Scoped providers create their values once
+    /// per scope (when first requested within that scope) and reuse that
+    /// value for each future request within the same scope.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{Injector, IntoScoped, Svc};
+    ///
+    /// #[derive(Default)]
+    /// struct Foo;
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(Foo::default.scoped());
+    ///
+    /// let injector = builder.build();
+    /// let scope1 = injector.create_scope().unwrap();
+    /// let foo1: Svc<Foo> = scope1.get().unwrap();
+    /// let foo2: Svc<Foo> = scope1.get().unwrap();
+    /// assert!(Svc::ptr_eq(&foo1, &foo2));
+    ///
+    /// let scope2 = injector.create_scope().unwrap();
+    /// let foo3: Svc<Foo> = scope2.get().unwrap();
+    /// assert!(!Svc::ptr_eq(&foo1, &foo3));
+    /// ```
+    #[must_use]
+    fn scoped(self) -> ScopedProvider<D, R, F>;
+}
+
+impl<D, R, F> IntoScoped<D, R, F> for F
+where
+    R: Service,
+    F: ServiceFactory<D, Result = R>,
+{
+    fn scoped(self) -> ScopedProvider<D, R, F> {
+        ScopedProvider::new(self)
+    }
+}
+
+impl<D, R, F> From<F> for ScopedProvider<D, R, F>
+where
+    R: Service,
+    F: ServiceFactory<D, Result = R>,
+{
+    fn from(func: F) -> Self {
+        func.scoped()
+    }
+}
@@ -1,5 +1,7 @@
 use std::{
     any::{Any, TypeId},
+    borrow::Cow,
+    cmp::Ordering,
     error::Error,
     fmt::{Display, Formatter},
 };
@@ -103,11 +105,88 @@ impl ServiceInfo {
         self.id
     }
 
-    /// Gets the type name of this service.
+    /// Gets the type name of this service. This is the fully qualified name
+    /// of the type, including its module path.
     #[must_use]
     pub fn name(&self) -> &'static str {
         self.name
     }
+
+    /// Gets the type name of this service without the module path of the
+    /// outermost type. Generic arguments keep their module paths so they can
+    /// still be told apart. This is easier to read in logs and error
+    /// messages, but unlike [`ServiceInfo::name()`], it may be the same for
+    /// different types.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{Service, ServiceInfo};
+    ///
+    /// trait Plugin: Service {}
+    ///
+    /// assert_eq!(
+    ///     "Vec<alloc::string::String>",
+    ///     ServiceInfo::of::<Vec<String>>().short_name()
+    /// );
+    /// assert_eq!("dyn Plugin", ServiceInfo::of::<dyn Plugin>().short_name());
+    /// assert_eq!("i32", ServiceInfo::of::<i32>().short_name());
+    ///
+    /// // Types that don't start with a path are unchanged
+    /// assert_eq!(
+    ///     "&std::path::Path",
+    ///     ServiceInfo::of::<&'static std::path::Path>().short_name()
+    /// );
+    /// ```
+    #[must_use]
+    pub fn short_name(&self) -> Cow<'static, str> {
+        let (prefix, name) = match self.name.strip_prefix("dyn ") {
+            Some(name) => ("dyn ", name),
+            None => ("", self.name),
+        };
+
+        // Only the path of the outermost type is trimmed. Tuples, arrays,
+        // references and other types that don't start with a path are kept
+        // as they are.
+        let path_end = name.find(['<', ' ', '(']).unwrap_or(name.len());
+        let path = &name[..path_end];
+        let is_path = path.starts_with(|c: char| c.is_alphabetic() || c == '_')
+            && path
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == ':');
+        if !is_path {
+            return Cow::Borrowed(self.name);
+        }
+
+        let start = match path.rfind("::") {
+            Some(index) => index + 2,
+            None => return Cow::Borrowed(self.name),
+        };
+
+        if prefix.is_empty() {
+            Cow::Borrowed(&name[start..])
+        } else {
+            Cow::Owned(format!("{prefix}{}", &name[start..]))
+        }
+    }
+}
+
+/// Services are ordered by their type names, then by their [`TypeId`]s if
+/// their names are the same. Unlike a [`TypeId`], a type's name is the same
+/// each time a program runs, so the order of services with different names
+/// is stable between runs and can be used for reproducible output.
+impl PartialOrd for ServiceInfo {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ServiceInfo {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.name
+            .cmp(other.name)
+            .then_with(|| self.id.cmp(&other.id))
+    }
 }
 
 /// An error that has occurred during creation of a service.
@@ -124,8 +203,14 @@ pub enum InjectError {
         /// The service that was requested.
         service_info: ServiceInfo,
 
-        /// The dependency that is missing a provider.
+        /// The dependency that is missing a provider, or that is missing a
+        /// dependency of its own.
         dependency_info: ServiceInfo,
+
+        /// The error returned when requesting the dependency, if the
+        /// dependency itself is missing a dependency. This is the source of
+        /// this error.
+        inner: Option<Box<InjectError>>,
     },
 
     /// A cycle was detected during activation of a service.
@@ -161,6 +246,17 @@ pub enum InjectError {
         providers: usize,
     },
 
+    /// A fixed number of implementations of a service was requested, but a
+    /// different number of implementations was provided.
+    WrongCount {
+        /// The service that was requested.
+        service_info: ServiceInfo,
+        /// The number of implementations that were requested.
+        expected: usize,
+        /// The number of implementations that were provided.
+        actual: usize,
+    },
+
     /// The registered provider can't provide an owned variant of the requested
     /// service.
     OwnedNotSupported {
@@ -168,6 +264,39 @@ pub enum InjectError {
         service_info: ServiceInfo,
     },
 
+    /// The registered provider doesn't store an instance of the requested
+    /// service, so there is no reference count to report. See
+    /// [`RefCount`](crate::RefCount) for more information.
+    RefCountNotSupported {
+        /// The service that was requested.
+        service_info: ServiceInfo,
+    },
+
+    /// The registered provider doesn't store an instance of the requested
+    /// service, so a weak pointer to it can't be kept alive. See
+    /// [`WeakSvc`](crate::WeakSvc) for more information.
+    WeakNotSupported {
+        /// The service that was requested.
+        service_info: ServiceInfo,
+    },
+
+    /// The registered provider can only provide its service once, and it has
+    /// already been provided. See [`OnceProvider`](crate::OnceProvider) for
+    /// more information.
+    AlreadyConsumed {
+        /// The service that was requested.
+        service_info: ServiceInfo,
+    },
+
+    /// Multiple implementations of the requested service were registered
+    /// under the same name.
+    DuplicateName {
+        /// The service that was requested.
+        service_info: ServiceInfo,
+        /// The name which was used more than once.
+        name: String,
+    },
+
     /// This provider's conditions for providing its service have not and it
     /// should be ignored.
     ///
@@ -180,7 +309,9 @@ pub enum InjectError {
         service_info: ServiceInfo,
     },
 
-    /// An error occurred during activation of a service.
+    /// An error occurred during activation of a service. If the service
+    /// could not be activated because one of its dependencies failed to
+    /// activate, then the inner error is the dependency's error.
     ActivationFailed {
         /// The service that was requested.
         service_info: ServiceInfo,
@@ -188,6 +319,56 @@ pub enum InjectError {
         inner: Box<dyn Error + 'static>,
     },
 
+    /// A required argument was not provided for a service. See
+    /// [`Module::require_arg()`](crate::Module::require_arg) for more
+    /// information.
+    MissingArgument {
+        /// The service which requires the argument.
+        service_info: ServiceInfo,
+        /// The type of the argument.
+        arg_info: ServiceInfo,
+    },
+
+    /// A service was requested too deeply within a chain of dependencies. See
+    /// [`InjectorBuilder::set_max_depth()`](crate::InjectorBuilder::set_max_depth)
+    /// for more information.
+    MaxDepthExceeded {
+        /// The service that was requested.
+        service_info: ServiceInfo,
+        /// The depth the service was requested at.
+        depth: usize,
+    },
+
+    /// A request was cancelled before the service was activated. See
+    /// [`RequestInfo::set_cancellation()`](crate::RequestInfo::set_cancellation)
+    /// for more information.
+    Cancelled {
+        /// The service that was requested.
+        service_info: ServiceInfo,
+    },
+
+    /// A pooled service was requested while every instance in its pool was
+    /// checked out. See [`PoolExhausted`](crate::PoolExhausted) for more
+    /// information.
+    PoolExhausted {
+        /// The service that was requested.
+        service_info: ServiceInfo,
+        /// The maximum number of instances in the pool.
+        max: usize,
+    },
+
+    /// A request middleware continued a request, then returned `Ok(())` even
+    /// though the request failed. The request isn't performed again, since
+    /// that could activate its services twice. See
+    /// [`InjectorBuilder::add_request_middleware()`](crate::InjectorBuilder::add_request_middleware)
+    /// for more information.
+    SuppressedByMiddleware,
+
+    /// A scope or child injector was created from an injector which has been
+    /// sealed. See [`Injector::seal()`](crate::Injector::seal) for more
+    /// information.
+    Sealed,
+
     /// An unexpected error has occurred. This is usually caused by a bug in
     /// the library itself.
     InternalError(String),
@@ -196,6 +377,9 @@ pub enum InjectError {
 impl Error for InjectError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
+            InjectError::MissingDependency {
+                inner: Some(inner), ..
+            } => Some(inner.as_ref()),
             InjectError::ActivationFailed { inner, .. } => Some(inner.as_ref()),
             _ => None,
         }
@@ -205,14 +389,112 @@ impl Error for InjectError {
 impl Display for InjectError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "an error occurred during injection: ")?;
+        self.fmt_message(f)?;
+
+        // The alternate format includes the full chain of sources. Nested
+        // injection errors are written without repeating the prefix.
+        if f.alternate() {
+            let mut source = self.source();
+            while let Some(error) = source {
+                write!(f, ": ")?;
+                match error.downcast_ref::<InjectError>() {
+                    Some(error) => error.fmt_message(f)?,
+                    None => write!(f, "{error}")?,
+                }
+                source = error.source();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl InjectError {
+    /// Checks whether the error was caused by a missing provider, either for
+    /// the requested service or for one of its dependencies.
+    #[must_use]
+    pub fn is_missing(&self) -> bool {
+        matches!(
+            self,
+            InjectError::MissingProvider { .. }
+                | InjectError::MissingDependency { .. }
+        )
+    }
+
+    /// Checks whether the requested service has no provider. This is only
+    /// `true` for [`InjectError::MissingProvider`], and not for missing
+    /// dependencies of the requested service. See [`InjectError::is_missing()`]
+    /// for a check which includes both.
+    #[must_use]
+    pub fn is_missing_provider(&self) -> bool {
+        matches!(self, InjectError::MissingProvider { .. })
+    }
+
+    /// Checks whether a cycle was detected while activating the service.
+    #[must_use]
+    pub fn is_cycle(&self) -> bool {
+        matches!(self, InjectError::CycleDetected { .. })
+    }
+
+    /// Checks whether an error occurred during activation of the service.
+    #[must_use]
+    pub fn is_activation_failed(&self) -> bool {
+        matches!(self, InjectError::ActivationFailed { .. })
+    }
+
+    /// Checks whether the conditions for providing the service were not met.
+    #[must_use]
+    pub fn is_conditions_not_met(&self) -> bool {
+        matches!(self, InjectError::ConditionsNotMet { .. })
+    }
+
+    /// Gets the error which was returned while activating the service, if
+    /// the error is an [`InjectError::ActivationFailed`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use runtime_injector::{Injector, IntoFallible, IntoTransient, Svc};
+    /// use std::num::ParseIntError;
+    ///
+    /// struct Port(u16);
+    ///
+    /// let mut builder = Injector::builder();
+    /// builder.provide(
+    ///     (|| "eighty".parse().map(Port)).fallible().transient(),
+    /// );
+    ///
+    /// let injector = builder.build();
+    /// let error = injector.get::<Svc<Port>>().err().unwrap();
+    /// assert!(error.is_activation_failed());
+    ///
+    /// let source = error.as_activation_source().unwrap();
+    /// assert!(source.downcast_ref::<ParseIntError>().is_some());
+    /// ```
+    #[must_use]
+    pub fn as_activation_source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InjectError::ActivationFailed { inner, .. } => Some(inner.as_ref()),
+            _ => None,
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn fmt_message(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             InjectError::MissingProvider { service_info } => {
                 write!(f, "{} has no provider", service_info.name())
             }
             InjectError::MissingDependency {
                 service_info,
+                dependency_info,
                 ..
-            } => write!(f, "{} is missing a dependency", service_info.name()),
+            } => write!(
+                f,
+                "{} is missing a dependency on {}",
+                service_info.name(),
+                dependency_info.name()
+            ),
             InjectError::CycleDetected {
                 service_info,
                 cycle,
@@ -250,6 +532,27 @@ impl Display for InjectError {
                 "the registered provider can't provide an owned variant of {}",
                 service_info.name()
             ),
+            InjectError::RefCountNotSupported { service_info } => write!(
+                f,
+                "the registered provider for {} doesn't store an instance of it",
+                service_info.name()
+            ),
+            InjectError::WeakNotSupported { service_info } => write!(
+                f,
+                "the registered provider for {} doesn't store an instance of it, so it can't be weakly referenced",
+                service_info.name()
+            ),
+            InjectError::AlreadyConsumed { service_info } => write!(
+                f,
+                "{} can only be provided once and has already been consumed",
+                service_info.name()
+            ),
+            InjectError::DuplicateName { service_info, name } => write!(
+                f,
+                "multiple implementations of {} are named {:?}",
+                service_info.name(),
+                name
+            ),
             InjectError::ConditionsNotMet { service_info } => {
                 write!(
                     f,
@@ -260,8 +563,46 @@ impl Display for InjectError {
             InjectError::ActivationFailed { service_info, .. } => {
                 write!(f, "an error occurred during activation of {}", service_info.name())
             },
+            InjectError::MissingArgument { service_info, arg_info } => {
+                write!(
+                    f,
+                    "{} requires an argument of type {}, but none was provided",
+                    service_info.name(),
+                    arg_info.name()
+                )
+            },
+            InjectError::WrongCount { service_info, expected, actual } => {
+                write!(
+                    f,
+                    "expected {expected} implementations of {}, but {actual} were provided",
+                    service_info.name()
+                )
+            },
+            InjectError::MaxDepthExceeded { service_info, depth } => {
+                write!(
+                    f,
+                    "{} was requested at a depth of {depth}, which exceeds the maximum depth",
+                    service_info.name()
+                )
+            },
+            InjectError::Cancelled { service_info } => {
+                write!(f, "the request for {} was cancelled", service_info.name())
+            },
+            InjectError::PoolExhausted { service_info, max } => {
+                write!(
+                    f,
+                    "all {max} pooled instances of {} are in use",
+                    service_info.name()
+                )
+            },
+            InjectError::SuppressedByMiddleware => {
+                write!(f, "a request middleware ignored the error returned by the request")
+            },
+            InjectError::Sealed => {
+                write!(f, "the injector has been sealed and can't create new scopes or child injectors")
+            },
             InjectError::InternalError(message) => {
-                write!(f, "an unexpected error occurred (please report this): {}", message)
+                write!(f, "an unexpected error occurred (please report this): {message}")
             },
         }
     }
@@ -273,7 +614,7 @@ fn fmt_cycle(cycle: &[ServiceInfo]) -> String {
         if !joined.is_empty() {
             joined.push_str(" -> ");
         }
-        joined.push_str(item.name());
+        joined.push_str(&item.short_name());
     }
     joined
 }
@@ -0,0 +1,321 @@
+use crate::{
+    DynSvc, InjectError, InjectResult, Injector, InterfaceFor,
+    InterfaceProvider, Lifetime, MapContainer, MapContainerEx, OwnedDynSvc,
+    Provider, RequestInfo, ServiceInfo, Svc, TypedProvider,
+};
+use std::time::Duration;
+
+/// A handle to a provider which can be registered several times while still
+/// sharing its state, such as a cached singleton instance.
+///
+/// If the provider is already being used to activate its service when it is
+/// used again, a cycle is reported instead. The provider's name and tags are
+/// copied out of it so they can be borrowed without locking the provider, and
+/// so is its priority.
+pub(crate) struct SharedProvider<P: TypedProvider> {
+    inner: MapContainer<P>,
+    name: Option<String>,
+    tags: Vec<String>,
+    priority: i32,
+}
+
+impl<P: TypedProvider> SharedProvider<P> {
+    pub fn new(provider: P) -> Self {
+        SharedProvider {
+            name: provider.name_typed().map(ToOwned::to_owned),
+            tags: provider.tags_typed().into_iter().map(Into::into).collect(),
+            priority: provider.priority_typed(),
+            inner: MapContainerEx::new(provider),
+        }
+    }
+
+    fn cycle_detected() -> InjectError {
+        let service_info = ServiceInfo::of::<P::Result>();
+        InjectError::CycleDetected {
+            service_info,
+            cycle: vec![service_info],
+        }
+    }
+}
+
+impl<P: TypedProvider> Clone for SharedProvider<P> {
+    fn clone(&self) -> Self {
+        SharedProvider {
+            inner: self.inner.clone(),
+            name: self.name.clone(),
+            tags: self.tags.clone(),
+            priority: self.priority,
+        }
+    }
+}
+
+impl<P: TypedProvider> TypedProvider for SharedProvider<P> {
+    type Result = P::Result;
+
+    fn provide_typed(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Svc<Self::Result>> {
+        self.inner
+            .try_with_inner_mut(|inner| {
+                inner.provide_typed(injector, request_info)
+            })
+            .unwrap_or_else(|| Err(Self::cycle_detected()))
+    }
+
+    fn provide_owned_typed(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Box<Self::Result>> {
+        self.inner
+            .try_with_inner_mut(|inner| {
+                inner.provide_owned_typed(injector, request_info)
+            })
+            .unwrap_or_else(|| Err(Self::cycle_detected()))
+    }
+
+    fn strong_count_typed(&self) -> Option<usize> {
+        self.inner
+            .try_with_inner_mut(|inner| inner.strong_count_typed())
+            .flatten()
+    }
+
+    fn name_typed(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn tags_typed(&self) -> Vec<&str> {
+        self.tags.iter().map(String::as_str).collect()
+    }
+
+    fn priority_typed(&self) -> i32 {
+        self.priority
+    }
+
+    fn build_time_typed(&self) -> Option<Duration> {
+        self.inner
+            .try_with_inner_mut(|inner| inner.build_time_typed())
+            .flatten()
+    }
+
+    fn dependencies_typed(&self) -> Vec<ServiceInfo> {
+        self.inner
+            .try_with_inner_mut(|inner| inner.dependencies_typed())
+            .unwrap_or_default()
+    }
+
+    fn is_available_typed(
+        &self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> bool {
+        self.inner
+            .try_with_inner_mut(|inner| {
+                inner.is_available_typed(injector, request_info)
+            })
+            .unwrap_or(true)
+    }
+
+    fn lifetime_typed(&self) -> Lifetime {
+        self.inner
+            .try_with_inner_mut(|inner| inner.lifetime_typed())
+            .unwrap_or(Lifetime::Custom)
+    }
+}
+
+/// Provides a service as itself and as an implementation of one or more
+/// additional interfaces. See [`TypedProvider::also_as()`] for more
+/// information.
+pub struct AlsoAsProvider<P: TypedProvider> {
+    inner: SharedProvider<P>,
+    aliases: Vec<Box<dyn Provider>>,
+}
+
+impl<P: TypedProvider> AlsoAsProvider<P> {
+    pub(crate) fn new(provider: P) -> Self {
+        AlsoAsProvider {
+            inner: SharedProvider::new(provider),
+            aliases: Vec::new(),
+        }
+    }
+
+    /// Additionally provides this service as an implementation of another
+    /// interface. See [`TypedProvider::also_as()`] for more information.
+    #[must_use]
+    pub fn also_as<I: ?Sized + InterfaceFor<P::Result>>(mut self) -> Self {
+        self.aliases
+            .push(Box::new(self.inner.clone().with_interface::<I>()));
+        self
+    }
+}
+
+impl<P: TypedProvider> Provider for AlsoAsProvider<P> {
+    fn result(&self) -> ServiceInfo {
+        ServiceInfo::of::<P::Result>()
+    }
+
+    fn provide(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<DynSvc> {
+        self.inner.provide(injector, request_info)
+    }
+
+    fn provide_owned(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<OwnedDynSvc> {
+        self.inner.provide_owned(injector, request_info)
+    }
+
+    fn take_aliases(&mut self) -> Vec<Box<dyn Provider>> {
+        std::mem::take(&mut self.aliases)
+    }
+
+    fn strong_count(&self) -> Option<usize> {
+        self.inner.strong_count_typed()
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.inner.name_typed()
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        self.inner.tags_typed()
+    }
+
+    fn priority(&self) -> i32 {
+        self.inner.priority_typed()
+    }
+
+    fn build_time(&self) -> Option<Duration> {
+        self.inner.build_time_typed()
+    }
+
+    fn dependencies(&self) -> Vec<ServiceInfo> {
+        self.inner.dependencies_typed()
+    }
+
+    fn is_available(
+        &self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> bool {
+        self.inner.is_available_typed(injector, request_info)
+    }
+
+    fn lifetime(&self) -> Lifetime {
+        self.inner.lifetime_typed()
+    }
+}
+
+/// Provides a service only as an implementation of one or more interfaces.
+/// See [`TypedProvider::with_interfaces()`] for more information.
+pub struct InterfacesProvider<I, P>
+where
+    P: TypedProvider,
+    I: ?Sized + InterfaceFor<P::Result>,
+{
+    inner: InterfaceProvider<I, SharedProvider<P>>,
+    shared: SharedProvider<P>,
+    aliases: Vec<Box<dyn Provider>>,
+}
+
+impl<I, P> InterfacesProvider<I, P>
+where
+    P: TypedProvider,
+    I: ?Sized + InterfaceFor<P::Result>,
+{
+    pub(crate) fn new(provider: P) -> Self {
+        let shared = SharedProvider::new(provider);
+        InterfacesProvider {
+            inner: shared.clone().with_interface(),
+            shared,
+            aliases: Vec::new(),
+        }
+    }
+
+    /// Additionally provides this service as an implementation of another
+    /// interface. See [`TypedProvider::with_interfaces()`] for more
+    /// information.
+    #[must_use]
+    pub fn also_as<J: ?Sized + InterfaceFor<P::Result>>(mut self) -> Self {
+        self.aliases
+            .push(Box::new(self.shared.clone().with_interface::<J>()));
+        self
+    }
+}
+
+impl<I, P> Provider for InterfacesProvider<I, P>
+where
+    P: TypedProvider,
+    I: ?Sized + InterfaceFor<P::Result>,
+{
+    fn result(&self) -> ServiceInfo {
+        self.inner.result()
+    }
+
+    fn provide(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<DynSvc> {
+        self.inner.provide(injector, request_info)
+    }
+
+    fn provide_owned(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<OwnedDynSvc> {
+        self.inner.provide_owned(injector, request_info)
+    }
+
+    fn take_aliases(&mut self) -> Vec<Box<dyn Provider>> {
+        std::mem::take(&mut self.aliases)
+    }
+
+    fn strong_count(&self) -> Option<usize> {
+        self.inner.strong_count()
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.inner.name()
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        self.inner.tags()
+    }
+
+    fn priority(&self) -> i32 {
+        self.inner.priority()
+    }
+
+    fn build_time(&self) -> Option<Duration> {
+        self.inner.build_time()
+    }
+
+    fn dependencies(&self) -> Vec<ServiceInfo> {
+        self.inner.dependencies()
+    }
+
+    fn is_available(
+        &self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> bool {
+        self.inner.is_available(injector, request_info)
+    }
+
+    fn lifetime(&self) -> Lifetime {
+        self.inner.lifetime()
+    }
+
+    fn implementation(&self) -> ServiceInfo {
+        self.inner.implementation()
+    }
+}
@@ -1,12 +1,23 @@
 use crate::{
-    InjectResult, Injector, RequestInfo, Service, ServiceFactory, Svc,
-    TypedProvider,
+    InjectResult, Injector, Lifetime, RequestInfo, Service, ServiceFactory,
+    ServiceInfo, Svc, TypedProvider,
+};
+use std::{
+    marker::PhantomData,
+    time::{Duration, Instant},
 };
-use std::marker::PhantomData;
 
 /// A service provider that only creates a single instance of the service.
 /// The service is created only during its first request. Any subsequent
 /// requests return service pointers to the same service.
+///
+/// The instance is stored directly in the provider rather than behind its
+/// own lock. Requests already have exclusive access to a service's providers
+/// while resolving it, so the factory runs at most once without any
+/// additional synchronization. As a result, requests for the service made
+/// from other threads while it is being resolved fail with
+/// [`InjectError::CycleDetected`](crate::InjectError::CycleDetected) rather
+/// than waiting for the instance to be created.
 pub struct SingletonProvider<D, R, F>
 where
     R: Service,
@@ -14,6 +25,7 @@ where
 {
     factory: F,
     result: Option<Svc<R>>,
+    build_time: Option<Duration>,
     marker: PhantomData<fn(D) -> R>,
 }
 
@@ -28,6 +40,7 @@ where
         SingletonProvider {
             factory: func,
             result: None,
+            build_time: None,
             marker: PhantomData,
         }
     }
@@ -35,7 +48,7 @@ where
 
 impl<D, R, F> TypedProvider for SingletonProvider<D, R, F>
 where
-    D: Service,
+    D: 'static,
     R: Service,
     F: ServiceFactory<D, Result = R>,
 {
@@ -50,11 +63,29 @@ where
             return Ok(service.clone());
         }
 
+        let start = injector.times_singletons().then(Instant::now);
         let result = self.factory.invoke(injector, request_info)?;
         let result = Svc::new(result);
         self.result = Some(result.clone());
+        self.build_time = start.map(|start| start.elapsed());
         Ok(result)
     }
+
+    fn strong_count_typed(&self) -> Option<usize> {
+        Some(self.result.as_ref().map_or(0, Svc::strong_count))
+    }
+
+    fn build_time_typed(&self) -> Option<Duration> {
+        self.build_time
+    }
+
+    fn dependencies_typed(&self) -> Vec<ServiceInfo> {
+        self.factory.dependencies()
+    }
+
+    fn lifetime_typed(&self) -> Lifetime {
+        Lifetime::Singleton
+    }
 }
 
 /// Defines a conversion into a singleton provider. This trait is automatically
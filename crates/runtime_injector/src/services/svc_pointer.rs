@@ -0,0 +1,182 @@
+use crate::{
+    InjectResult, Injector, Lifetime, RequestInfo, Service, Svc, TypedProvider,
+};
+use std::{any::Any, ops::Deref, rc::Rc, sync::Arc};
+
+/// Abstracts over the reference-counted pointer used to hold services.
+///
+/// This is an experimental prototype for choosing the pointer type with a
+/// type parameter rather than with the "arc" and "rc" features, which would
+/// allow both pointer types to be used in the same build. Currently, only
+/// [`PointerConstantProvider`] is generic over the pointer type, and the rest
+/// of the crate still uses [`Svc<T>`]. This is only available with the
+/// "unstable-pointer" feature, and may change in any release.
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{ArcPointer, PointerService, RcPointer, SvcPointer};
+///
+/// // Both pointer types can be used regardless of the enabled features
+/// let shared = ArcPointer::new(5i32);
+/// let local = RcPointer::new(5i32);
+///
+/// let shared = <i32 as PointerService<ArcPointer>>::into_dyn(shared);
+/// let local = <i32 as PointerService<RcPointer>>::into_dyn(local);
+///
+/// let shared = <i32 as PointerService<ArcPointer>>::downcast(shared).unwrap();
+/// let local = <i32 as PointerService<RcPointer>>::downcast(local).unwrap();
+/// assert_eq!(*shared, *local);
+/// ```
+pub trait SvcPointer: 'static {
+    /// The pointer type which holds a service.
+    type Svc<T: ?Sized + 'static>: Clone + Deref<Target = T>;
+
+    /// The pointer type which holds a service whose type has been erased.
+    type DynSvc: Clone;
+
+    /// Creates a new service pointer.
+    fn new<T: 'static>(value: T) -> Self::Svc<T>;
+}
+
+/// A service which can be held by the pointer type `P`. This converts the
+/// service's pointers to and from pointers with the service's type erased.
+/// See [`SvcPointer`] for more information.
+pub trait PointerService<P: SvcPointer>: Any {
+    /// Erases the type of a service pointer.
+    fn into_dyn(service: P::Svc<Self>) -> P::DynSvc;
+
+    /// Downcasts a service pointer with its type erased back into a pointer
+    /// to this service. If the pointer holds a different type of service,
+    /// then it is returned as-is.
+    ///
+    /// ## Errors
+    ///
+    /// Returns the pointer if it doesn't hold this type of service.
+    fn downcast(service: P::DynSvc) -> Result<P::Svc<Self>, P::DynSvc>;
+}
+
+/// Holds services in an [`Arc<T>`]. Services held by this pointer type must
+/// be [`Send`] + [`Sync`].
+pub struct ArcPointer;
+
+impl SvcPointer for ArcPointer {
+    type Svc<T: ?Sized + 'static> = Arc<T>;
+    type DynSvc = Arc<dyn Any + Send + Sync>;
+
+    fn new<T: 'static>(value: T) -> Self::Svc<T> {
+        Arc::new(value)
+    }
+}
+
+impl<T: Any + Send + Sync> PointerService<ArcPointer> for T {
+    fn into_dyn(service: Arc<T>) -> Arc<dyn Any + Send + Sync> {
+        service
+    }
+
+    fn downcast(
+        service: Arc<dyn Any + Send + Sync>,
+    ) -> Result<Arc<T>, Arc<dyn Any + Send + Sync>> {
+        service.downcast()
+    }
+}
+
+/// Holds services in an [`Rc<T>`].
+pub struct RcPointer;
+
+impl SvcPointer for RcPointer {
+    type Svc<T: ?Sized + 'static> = Rc<T>;
+    type DynSvc = Rc<dyn Any>;
+
+    fn new<T: 'static>(value: T) -> Self::Svc<T> {
+        Rc::new(value)
+    }
+}
+
+impl<T: Any> PointerService<RcPointer> for T {
+    fn into_dyn(service: Rc<T>) -> Rc<dyn Any> {
+        service
+    }
+
+    fn downcast(service: Rc<dyn Any>) -> Result<Rc<T>, Rc<dyn Any>> {
+        service.downcast()
+    }
+}
+
+/// The pointer type used by [`Svc<T>`], which is determined by the feature
+/// flags passed to this crate.
+#[cfg(feature = "arc")]
+pub type DefaultPointer = ArcPointer;
+
+/// The pointer type used by [`Svc<T>`], which is determined by the feature
+/// flags passed to this crate.
+#[cfg(feature = "rc")]
+pub type DefaultPointer = RcPointer;
+
+/// A provider which returns a constant, predetermined value held by the
+/// pointer type `P`. This works the same way as a
+/// [`ConstantProvider`](crate::ConstantProvider), but is generic over the
+/// pointer type. It can only be registered with an injector if `P` is the
+/// [`DefaultPointer`]. See [`SvcPointer`] for more information.
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{
+///     DefaultPointer, Injector, PointerConstantProvider, RcPointer, Svc,
+/// };
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(PointerConstantProvider::<DefaultPointer, _>::new(8i32));
+///
+/// let injector = builder.build();
+/// let value: Svc<i32> = injector.get().unwrap();
+/// assert_eq!(8, *value);
+///
+/// // Other pointer types can still be used outside of an injector
+/// let local = PointerConstantProvider::<RcPointer, _>::new(8i32);
+/// assert_eq!(8, *local.get());
+/// ```
+pub struct PointerConstantProvider<P: SvcPointer, R: 'static> {
+    result: P::Svc<R>,
+}
+
+impl<P: SvcPointer, R: 'static> PointerConstantProvider<P, R> {
+    /// Creates a new [`PointerConstantProvider`] using a predetermined
+    /// value.
+    #[must_use]
+    pub fn new(value: R) -> Self {
+        PointerConstantProvider {
+            result: P::new(value),
+        }
+    }
+
+    /// Gets a pointer to the value.
+    #[must_use]
+    pub fn get(&self) -> P::Svc<R> {
+        self.result.clone()
+    }
+}
+
+impl<R: Service> TypedProvider for PointerConstantProvider<DefaultPointer, R>
+where
+    PointerConstantProvider<DefaultPointer, R>: Service,
+{
+    type Result = R;
+
+    fn provide_typed(
+        &mut self,
+        _injector: &Injector,
+        _request_info: &RequestInfo,
+    ) -> InjectResult<Svc<Self::Result>> {
+        Ok(self.get())
+    }
+
+    fn strong_count_typed(&self) -> Option<usize> {
+        Some(Svc::strong_count(&self.result))
+    }
+
+    fn lifetime_typed(&self) -> Lifetime {
+        Lifetime::Constant
+    }
+}
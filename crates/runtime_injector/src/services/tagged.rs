@@ -0,0 +1,107 @@
+use crate::{
+    InjectResult, Injector, Lifetime, RequestInfo, ServiceInfo, Svc,
+    TypedProvider,
+};
+use std::{collections::BTreeSet, time::Duration};
+
+/// Provides a service with one or more tags. The service can still be
+/// requested normally, but it can also be requested along with the other
+/// services which share a tag. See [`TypedProvider::with_tag()`] for more
+/// information.
+pub struct TaggedProvider<P>
+where
+    P: TypedProvider,
+{
+    inner: P,
+    tags: BTreeSet<String>,
+}
+
+impl<P> TaggedProvider<P>
+where
+    P: TypedProvider,
+{
+    pub(crate) fn new(inner: P, tag: String) -> Self {
+        let mut tags = BTreeSet::new();
+        tags.insert(tag);
+        TaggedProvider { inner, tags }
+    }
+
+    /// Adds another tag to this provider.
+    #[must_use]
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.insert(tag.into());
+        self
+    }
+}
+
+impl<P> TypedProvider for TaggedProvider<P>
+where
+    P: TypedProvider,
+{
+    type Result = P::Result;
+
+    #[inline]
+    fn provide_typed(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Svc<Self::Result>> {
+        self.inner.provide_typed(injector, request_info)
+    }
+
+    #[inline]
+    fn provide_owned_typed(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Box<Self::Result>> {
+        self.inner.provide_owned_typed(injector, request_info)
+    }
+
+    #[inline]
+    fn strong_count_typed(&self) -> Option<usize> {
+        self.inner.strong_count_typed()
+    }
+
+    #[inline]
+    fn name_typed(&self) -> Option<&str> {
+        self.inner.name_typed()
+    }
+
+    fn tags_typed(&self) -> Vec<&str> {
+        let mut tags = self.inner.tags_typed();
+        tags.extend(self.tags.iter().map(String::as_str));
+        tags.sort_unstable();
+        tags.dedup();
+        tags
+    }
+
+    #[inline]
+    fn priority_typed(&self) -> i32 {
+        self.inner.priority_typed()
+    }
+
+    #[inline]
+    fn build_time_typed(&self) -> Option<Duration> {
+        self.inner.build_time_typed()
+    }
+
+    #[inline]
+    fn dependencies_typed(&self) -> Vec<ServiceInfo> {
+        self.inner.dependencies_typed()
+    }
+
+    #[inline]
+    fn is_available_typed(
+        &self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> bool {
+        self.inner.is_available_typed(injector, request_info)
+    }
+
+    #[inline]
+    fn lifetime_typed(&self) -> Lifetime {
+        self.inner.lifetime_typed()
+    }
+}
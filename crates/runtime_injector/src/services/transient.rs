@@ -1,6 +1,6 @@
 use crate::{
-    InjectResult, Injector, RequestInfo, Service, ServiceFactory, Svc,
-    TypedProvider,
+    InjectResult, Injector, Lifetime, RequestInfo, Service, ServiceFactory,
+    ServiceInfo, Svc, TypedProvider,
 };
 use std::marker::PhantomData;
 
@@ -33,7 +33,7 @@ where
 
 impl<D, R, F> TypedProvider for TransientProvider<D, R, F>
 where
-    D: Service,
+    D: 'static,
     R: Service,
     F: ServiceFactory<D, Result = R>,
 {
@@ -56,6 +56,14 @@ where
         let result = self.factory.invoke(injector, request_info)?;
         Ok(Box::new(result))
     }
+
+    fn dependencies_typed(&self) -> Vec<ServiceInfo> {
+        self.factory.dependencies()
+    }
+
+    fn lifetime_typed(&self) -> Lifetime {
+        Lifetime::Transient
+    }
 }
 
 /// Defines a conversion into a transient provider. This trait is automatically
@@ -0,0 +1,149 @@
+use crate::{
+    DynSvc, InjectResult, Injector, Lifetime, OwnedDynSvc, Provider,
+    ProviderMap, RequestInfo, ServiceInfo, Svc,
+};
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+/// Tracks which providers have activated a service. Each injector which
+/// shares providers holds a pointer to the same tracker. With the "tracing"
+/// feature enabled, unused providers are reported once the last of those
+/// injectors is dropped.
+#[derive(Default)]
+pub(crate) struct UsageTracker {
+    providers: Vec<(ServiceInfo, Svc<AtomicBool>)>,
+}
+
+impl UsageTracker {
+    /// Wraps each provider in a provider map so that its usage is tracked.
+    pub fn track(&mut self, provider_map: &mut ProviderMap) {
+        for providers in provider_map.values_mut().flatten() {
+            *providers = std::mem::take(providers)
+                .into_iter()
+                .map(|inner| {
+                    let used = Svc::new(AtomicBool::new(false));
+                    self.providers.push((inner.result(), used.clone()));
+                    Box::new(UsageTrackingProvider { inner, used })
+                        as Box<dyn Provider>
+                })
+                .collect();
+        }
+    }
+
+    /// Gets the services which have a provider that has never activated
+    /// them, sorted by name.
+    pub fn unused(&self) -> Vec<ServiceInfo> {
+        let mut unused: Vec<_> = self
+            .providers
+            .iter()
+            .filter(|(_, used)| !used.load(Ordering::Relaxed))
+            .map(|&(service_info, _)| service_info)
+            .collect();
+        unused.sort_by_key(ServiceInfo::name);
+        unused.dedup();
+        unused
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl Drop for UsageTracker {
+    fn drop(&mut self) {
+        for service_info in self.unused() {
+            tracing::warn!(
+                service = service_info.name(),
+                "registered provider was never used"
+            );
+        }
+    }
+}
+
+/// Marks another provider as used once it activates a service.
+struct UsageTrackingProvider {
+    inner: Box<dyn Provider>,
+    used: Svc<AtomicBool>,
+}
+
+impl UsageTrackingProvider {
+    fn mark<T>(&self, result: InjectResult<T>) -> InjectResult<T> {
+        if result.is_ok() {
+            self.used.store(true, Ordering::Relaxed);
+        }
+
+        result
+    }
+}
+
+impl Provider for UsageTrackingProvider {
+    fn result(&self) -> ServiceInfo {
+        self.inner.result()
+    }
+
+    fn provide(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<DynSvc> {
+        let result = self.inner.provide(injector, request_info);
+        self.mark(result)
+    }
+
+    fn provide_owned(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<OwnedDynSvc> {
+        let result = self.inner.provide_owned(injector, request_info);
+        self.mark(result)
+    }
+
+    fn provide_all(
+        &mut self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> InjectResult<Vec<DynSvc>> {
+        let result = self.inner.provide_all(injector, request_info);
+        self.mark(result)
+    }
+
+    fn strong_count(&self) -> Option<usize> {
+        self.inner.strong_count()
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.inner.name()
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        self.inner.tags()
+    }
+
+    fn priority(&self) -> i32 {
+        self.inner.priority()
+    }
+
+    fn build_time(&self) -> Option<Duration> {
+        self.inner.build_time()
+    }
+
+    fn dependencies(&self) -> Vec<ServiceInfo> {
+        self.inner.dependencies()
+    }
+
+    fn is_available(
+        &self,
+        injector: &Injector,
+        request_info: &RequestInfo,
+    ) -> bool {
+        self.inner.is_available(injector, request_info)
+    }
+
+    fn lifetime(&self) -> Lifetime {
+        self.inner.lifetime()
+    }
+
+    fn implementation(&self) -> ServiceInfo {
+        self.inner.implementation()
+    }
+}
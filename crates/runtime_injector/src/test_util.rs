@@ -0,0 +1,319 @@
+use crate::{
+    InjectError, Injector, InjectorBuilder, Interface, InterfaceFor,
+    IntoSingleton, Module, Provider, Request, Service, ServiceFactory,
+    ServiceInfo, Services, Svc, TypedProvider,
+};
+use std::any::type_name;
+
+/// Fluent assertions over the services provided by an [`Injector`]. This is
+/// intended to reduce the boilerplate needed to test how an injector was
+/// configured. Each assertion panics with a descriptive message if it fails,
+/// and returns the same [`InjectorAssert`] so assertions can be chained.
+///
+/// This requires the "test-util" feature.
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{
+///     interface, Injector, InjectorAssert, IntoSingleton, Service, Svc,
+///     TypedProvider,
+/// };
+///
+/// trait Foo: Service {}
+/// interface!(dyn Foo = [Bar]);
+///
+/// #[derive(Default)]
+/// struct Bar;
+/// impl Foo for Bar {}
+///
+/// struct Baz;
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(Bar::default.singleton().with_interface::<dyn Foo>());
+///
+/// let injector = builder.build();
+/// InjectorAssert::new(&injector)
+///     .resolves::<Svc<dyn Foo>>()
+///     .resolves_to::<dyn Foo, Bar>()
+///     .count::<dyn Foo>(1)
+///     .missing::<Svc<Baz>>();
+/// ```
+pub struct InjectorAssert<'a> {
+    injector: &'a Injector,
+}
+
+// Assertions are only called for their side effects
+#[allow(clippy::missing_panics_doc, clippy::must_use_candidate)]
+impl<'a> InjectorAssert<'a> {
+    /// Creates assertions over the given injector.
+    #[must_use]
+    pub fn new(injector: &'a Injector) -> Self {
+        InjectorAssert { injector }
+    }
+
+    /// Asserts that a request succeeds.
+    #[track_caller]
+    pub fn resolves<R: Request>(&self) -> &Self {
+        if let Err(error) = self.injector.get::<R>() {
+            panic!(
+                "expected {} to resolve, but it failed: {error}",
+                type_name::<R>()
+            );
+        }
+
+        self
+    }
+
+    /// Asserts that exactly one implementation of an interface is provided,
+    /// and that its concrete type is `T`.
+    #[track_caller]
+    pub fn resolves_to<I, T>(&self) -> &Self
+    where
+        I: ?Sized + Interface,
+        T: Service,
+    {
+        let mut services: Services<I> = match self.injector.get() {
+            Ok(services) => services,
+            Err(error) => panic!(
+                "expected {} to resolve to {}, but it failed: {error}",
+                type_name::<I>(),
+                type_name::<T>()
+            ),
+        };
+
+        let mut results = services.get_all_dyn();
+        let service = match results.len() {
+            1 => results.remove(0),
+            count => panic!(
+                "expected {} to resolve to {}, but it has {count} implementations",
+                type_name::<I>(),
+                type_name::<T>()
+            ),
+        };
+
+        match service {
+            Ok(service) if service.is::<T>() => {}
+            Ok(_) => panic!(
+                "expected {} to resolve to {}, but it resolved to another type",
+                type_name::<I>(),
+                type_name::<T>()
+            ),
+            Err(error) => panic!(
+                "expected {} to resolve to {}, but it failed: {error}",
+                type_name::<I>(),
+                type_name::<T>()
+            ),
+        }
+
+        self
+    }
+
+    /// Asserts that an interface has the given number of implementations.
+    /// Implementations whose conditions were not met are not counted.
+    #[track_caller]
+    pub fn count<I: ?Sized + Interface>(&self, expected: usize) -> &Self {
+        let count = match self.injector.get::<Vec<Svc<I>>>() {
+            Ok(services) => services.len(),
+            Err(InjectError::MissingProvider { service_info })
+                if service_info == ServiceInfo::of::<I>() =>
+            {
+                0
+            }
+            Err(error) => panic!(
+                "expected {expected} implementations of {}, but the request failed: {error}",
+                type_name::<I>()
+            ),
+        };
+
+        assert_eq!(
+            expected,
+            count,
+            "expected {expected} implementations of {}, but found {count}",
+            type_name::<I>()
+        );
+        self
+    }
+
+    /// Asserts that a request fails because a provider is missing, either
+    /// for the requested service or for one of its dependencies.
+    #[track_caller]
+    pub fn missing<R: Request>(&self) -> &Self {
+        match self.injector.get::<R>() {
+            Err(
+                InjectError::MissingProvider { .. }
+                | InjectError::MissingDependency { .. },
+            ) => {}
+            Err(error) => panic!(
+                "expected {} to be missing a provider, but it failed with another error: {error}",
+                type_name::<R>()
+            ),
+            Ok(_) => panic!(
+                "expected {} to be missing a provider, but it resolved",
+                type_name::<R>()
+            ),
+        }
+
+        self
+    }
+}
+
+/// Asserts that two service pointers point to the same instance of a service.
+/// This is useful for verifying that a service is shared, such as when it is
+/// provided as a singleton.
+///
+/// This requires the "test-util" feature.
+///
+/// ## Panics
+///
+/// Panics if the pointers point to different instances of the service.
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{
+///     assert_same_instance, Injector, IntoSingleton, Svc, TypedProvider,
+/// };
+///
+/// #[derive(Default)]
+/// struct Cache;
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(Cache::default.singleton());
+///
+/// let injector = builder.build();
+/// let cache1: Svc<Cache> = injector.get().unwrap();
+/// let cache2: Svc<Cache> = injector.get().unwrap();
+/// assert_same_instance(&cache1, &cache2);
+/// ```
+#[track_caller]
+pub fn assert_same_instance<T: ?Sized + Service>(
+    left: &Svc<T>,
+    right: &Svc<T>,
+) {
+    assert!(
+        Svc::ptr_eq(left, right),
+        "expected both pointers to the same instance of {}, but they point to different instances",
+        ServiceInfo::of::<T>().name()
+    );
+}
+
+/// An [`InjectorBuilder`] for tests which makes replacing a dependency with a
+/// mock implementation a one-liner. Mocks replace all other providers for
+/// their service, even ones which are added afterwards, so a real module can
+/// be registered while only some of its services are mocked.
+///
+/// This requires the "test-util" feature.
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{
+///     interface, Injector, IntoSingleton, MockInjectorBuilder, Module,
+///     Service, Svc, TypedProvider,
+/// };
+///
+/// trait UserDb: Service {
+///     fn user_name(&self, id: u32) -> String;
+/// }
+/// interface!(dyn UserDb = [PostgresUserDb, MockUserDb]);
+///
+/// #[derive(Default)]
+/// struct PostgresUserDb;
+/// impl UserDb for PostgresUserDb {
+///     fn user_name(&self, _id: u32) -> String {
+///         unimplemented!("requires a database")
+///     }
+/// }
+///
+/// #[derive(Default)]
+/// struct MockUserDb;
+/// impl UserDb for MockUserDb {
+///     fn user_name(&self, id: u32) -> String {
+///         format!("user{id}")
+///     }
+/// }
+///
+/// let mut module = Module::default();
+/// module.provide(
+///     PostgresUserDb::default
+///         .singleton()
+///         .with_interface::<dyn UserDb>(),
+/// );
+///
+/// let mut builder = MockInjectorBuilder::new();
+/// builder.add_module(module).mock::<dyn UserDb, _>(MockUserDb::default);
+///
+/// let injector = builder.build();
+/// let user_db: Svc<dyn UserDb> = injector.get().unwrap();
+/// assert_eq!("user1", user_db.user_name(1));
+/// ```
+#[derive(Default)]
+pub struct MockInjectorBuilder {
+    builder: InjectorBuilder,
+    mocks: Vec<RegisterMock>,
+}
+
+type RegisterMock = Box<dyn FnOnce(&mut InjectorBuilder)>;
+
+impl MockInjectorBuilder {
+    /// Creates a new, empty builder.
+    #[must_use]
+    pub fn new() -> Self {
+        MockInjectorBuilder::default()
+    }
+
+    /// Registers a mock implementation of a service. Each time the mock is
+    /// requested, the same instance is returned, so tests can request the
+    /// mock to inspect it. Any other providers for the service are replaced
+    /// when the injector is built.
+    pub fn mock<I, F>(&mut self, factory: F) -> &mut Self
+    where
+        F: ServiceFactory<()>,
+        I: ?Sized + InterfaceFor<F::Result>,
+    {
+        let provider = factory.singleton().with_interface::<I>();
+        self.mocks.push(Box::new(move |builder| {
+            builder.override_provider(provider);
+        }));
+        self
+    }
+
+    /// Registers a provider. See [`InjectorBuilder::provide()`].
+    pub fn provide<P: Provider>(&mut self, provider: P) -> &mut Self {
+        self.builder.provide(provider);
+        self
+    }
+
+    /// Adds a module. See [`InjectorBuilder::add_module()`].
+    pub fn add_module(&mut self, module: Module) -> &mut Self {
+        self.builder.add_module(module);
+        self
+    }
+
+    /// Gets the underlying builder. Any providers registered with it for a
+    /// mocked service are replaced by the mock.
+    pub fn builder_mut(&mut self) -> &mut InjectorBuilder {
+        &mut self.builder
+    }
+
+    /// Builds the injector, replacing the providers of every mocked service
+    /// with its mock.
+    #[must_use]
+    pub fn build(mut self) -> Injector {
+        for mock in self.mocks {
+            mock(&mut self.builder);
+        }
+
+        self.builder.build()
+    }
+}
+
+impl From<InjectorBuilder> for MockInjectorBuilder {
+    fn from(builder: InjectorBuilder) -> Self {
+        MockInjectorBuilder {
+            builder,
+            mocks: Vec::new(),
+        }
+    }
+}
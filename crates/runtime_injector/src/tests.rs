@@ -1,11 +1,23 @@
-#![allow(clippy::blacklisted_name)]
+#![allow(clippy::disallowed_names)]
 
 use crate::{
-    constant, interface, InjectError, InjectResult, Injector, IntoSingleton,
-    IntoTransient, RequestInfo, Service, ServiceInfo, Services, Svc,
-    TypedProvider,
+    constant, constant_cloneable, constant_mut, interface, once, one_of,
+    provide_generic, Arg, Count, DynSvc, Inject, InjectError, InjectResult,
+    Injector, IntoFallible, IntoMemoized, IntoPooled, IntoScoped,
+    IntoSingleton, IntoTransient, IsCancelled, Lazy, Lifetime, Locked,
+    ManyProvider, Module, Overrides, Owned, PoolExhausted, Pooled, Provider,
+    ProviderRegistry, RefCount, Request, RequestInfo, RequestParameter,
+    Service, ServiceInfo, Services, Svc, SvcLock, SvcMut, TypedProvider,
+    WeakSvc, WithArg, WithCondition,
+};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fmt::{Display, Formatter},
+    marker::PhantomData,
+    sync::Mutex,
 };
-use std::sync::Mutex;
 
 #[derive(Default)]
 struct Svc1(pub i32);
@@ -41,6 +53,7 @@ fn can_make_svc1() {
 }
 
 #[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
 fn cant_make_svc1_when_no_provider() {
     let injector = Injector::builder().build();
     let svc: InjectResult<Svc<Svc1>> = injector.get();
@@ -70,6 +83,7 @@ fn can_make_svc3() {
 }
 
 #[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
 fn cant_make_svc3_when_no_provider_for_dependency() {
     let mut builder = Injector::builder();
     builder.provide(Svc2::new.transient());
@@ -229,8 +243,11 @@ fn multi_injection() {
 }
 
 #[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
 fn injector_returns_error_on_cycles() {
+    #[allow(dead_code)]
     struct Foo(Svc<Bar>);
+    #[allow(dead_code)]
     struct Bar(Svc<Foo>);
 
     let mut builder = Injector::builder();
@@ -273,3 +290,2758 @@ fn request_info_has_correct_path() {
         foo.0 .0.service_path()
     );
 }
+
+#[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
+fn error_provider_is_not_treated_as_missing() {
+    let mut builder = Injector::builder();
+    builder.provide_error::<Svc1, _>(|| "Svc1 is disabled".into());
+
+    let injector = builder.build();
+    match injector.get::<Option<Svc<Svc1>>>() {
+        Err(InjectError::ActivationFailed {
+            service_info,
+            inner,
+        }) if service_info == ServiceInfo::of::<Svc1>() => {
+            assert_eq!("Svc1 is disabled", inner.to_string());
+        }
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("the service should not be activated"),
+    }
+}
+
+#[test]
+fn also_as_shares_singleton_between_registrations() {
+    trait Foo: Service {}
+    impl Foo for Svc1 {}
+    interface!(dyn Foo = [Svc1]);
+
+    type Counter = Mutex<i32>;
+
+    fn make_svc1(counter: Svc<Counter>) -> Svc1 {
+        let mut counter = counter.lock().unwrap();
+        *counter += 1;
+        Svc1(*counter)
+    }
+
+    let mut builder = Injector::builder();
+    builder.provide(constant(Mutex::new(0)));
+    builder.provide(make_svc1.singleton().also_as::<dyn Foo>());
+
+    let injector = builder.build();
+    let svc1: Svc<Svc1> = injector.get().unwrap();
+    let foos: Vec<Svc<dyn Foo>> = injector.get().unwrap();
+    let counter: Svc<Counter> = injector.get().unwrap();
+
+    assert_eq!(1, foos.len());
+    assert_eq!(1, svc1.0);
+    assert_eq!(1, *counter.lock().unwrap());
+}
+
+#[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
+fn with_interfaces_shares_singleton_between_interfaces() {
+    trait Foo: Service {}
+    trait Bar: Service {}
+    impl Foo for Svc1 {}
+    impl Bar for Svc1 {}
+    interface!(dyn Foo = [Svc1], dyn Bar = [Svc1]);
+
+    let mut builder = Injector::builder();
+    builder.provide(
+        Svc1::default
+            .singleton()
+            .with_interfaces::<dyn Foo>()
+            .also_as::<dyn Bar>(),
+    );
+
+    let injector = builder.build();
+    let foo: Svc<dyn Foo> = injector.get().unwrap();
+    let bar: Svc<dyn Bar> = injector.get().unwrap();
+    assert_eq!(
+        Svc::as_ptr(&foo).cast::<()>(),
+        Svc::as_ptr(&bar).cast::<()>()
+    );
+    assert_eq!(3, injector.get::<RefCount<dyn Foo>>().unwrap().get());
+
+    // The concrete type isn't registered
+    match injector.get::<Svc<Svc1>>() {
+        Err(InjectError::MissingProvider { service_info })
+            if service_info == ServiceInfo::of::<Svc1>() => {}
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("Svc1 should not be registered"),
+    }
+}
+
+#[test]
+fn locked_services_share_state() {
+    struct Counter(i32);
+
+    let mut builder = Injector::builder();
+    builder.provide((|| SvcLock::new(Counter(0))).singleton());
+
+    let injector = builder.build();
+    let counter1: Locked<Counter> = injector.get().unwrap();
+    let counter2: Locked<Counter> = injector.get().unwrap();
+    counter1.lock().0 += 1;
+    counter2.lock().0 += 1;
+
+    assert_eq!(2, counter1.lock().0);
+}
+
+#[test]
+fn overrides_apply_to_nested_dependencies() {
+    trait Foo: Service {
+        fn value(&self) -> i32;
+    }
+
+    impl Foo for Svc1 {
+        fn value(&self) -> i32 {
+            self.0
+        }
+    }
+
+    interface!(dyn Foo = [Svc1]);
+
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.transient());
+    builder.provide(Svc2::new.transient());
+    builder.provide(Svc3::new.transient());
+    builder.provide(Svc1::default.transient().with_interface::<dyn Foo>());
+
+    let injector = builder.build();
+    let mut overrides = Overrides::new();
+    overrides.insert(Svc::new(Svc1(42)));
+    overrides.insert_as::<dyn Foo, _>(Svc::new(Svc1(7)));
+
+    let svc3: Svc<Svc3> =
+        injector.get_with_overrides(overrides.clone()).unwrap();
+    assert_eq!(42, svc3.dep1.0);
+    assert_eq!(42, svc3.dep2.dep1.0);
+
+    let foo: Svc<dyn Foo> = injector.get_with_overrides(overrides).unwrap();
+    assert_eq!(7, foo.value());
+
+    let svc3: Svc<Svc3> = injector.get().unwrap();
+    assert_eq!(0, svc3.dep1.0);
+}
+
+#[test]
+fn disabled_layers_are_not_built() {
+    let mut builder = Injector::builder();
+    builder.provide(constant(Svc1(1)));
+    builder.layer("experimental", |layer| {
+        layer.provide(constant(Svc1(2)));
+    });
+    builder.layer("extra", |layer| {
+        layer.provide(constant(Svc1(3)));
+    });
+
+    assert!(builder.disable_layer("experimental"));
+    assert!(!builder.disable_layer("missing"));
+    assert_eq!(Some(false), builder.is_layer_enabled("experimental"));
+
+    let injector = builder.build();
+    let values: Vec<i32> = injector
+        .get::<Services<Svc1>>()
+        .unwrap()
+        .get_all()
+        .map(|svc| svc.unwrap().0)
+        .collect();
+    assert_eq!(vec![1, 3], values);
+}
+
+#[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
+fn one_of_prefers_first_registered_variant() {
+    one_of! {
+        #[allow(dead_code)]
+        enum Either {
+            First(Svc1),
+            Second(Svc2),
+        }
+    }
+
+    let mut builder = Injector::builder();
+    builder.provide(Svc2::new.singleton());
+    builder.provide(constant(Svc1(4)));
+
+    let injector = builder.build();
+    match injector.get::<Either>().unwrap() {
+        Either::First(svc1) => assert_eq!(4, svc1.0),
+        Either::Second(_) => unreachable!("first variant should take priority"),
+    }
+
+    let injector = Injector::builder().build();
+    match injector.get::<Either>() {
+        Err(InjectError::MissingProvider { service_info })
+            if service_info == ServiceInfo::of::<Either>() => {}
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("no variant should be available"),
+    }
+}
+
+#[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
+fn ref_count_tracks_singleton_references() {
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.singleton());
+    builder.provide(Svc2::new.transient());
+
+    let injector = builder.build();
+    let svc2: Svc<Svc2> = injector.get().unwrap();
+    let count: RefCount<Svc1> = injector.get().unwrap();
+    assert_eq!(2, count.get());
+
+    drop(svc2);
+    let count: RefCount<Svc1> = injector.get().unwrap();
+    assert_eq!(1, count.get());
+
+    match injector.get::<RefCount<Svc2>>() {
+        Err(InjectError::RefCountNotSupported { service_info })
+            if service_info == ServiceInfo::of::<Svc2>() => {}
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("transient services have no reference count"),
+    }
+}
+
+#[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
+fn once_provider_fails_after_being_consumed() {
+    let mut builder = Injector::builder();
+    builder.provide(once(Svc1(3)));
+
+    let injector = builder.build();
+    let svc1: Box<Svc1> = injector.get().unwrap();
+    assert_eq!(3, svc1.0);
+
+    for _ in 0..2 {
+        match injector.get::<Box<Svc1>>() {
+            Err(InjectError::AlreadyConsumed { service_info })
+                if service_info == ServiceInfo::of::<Svc1>() => {}
+            Err(error) => Err(error).unwrap(),
+            Ok(_) => unreachable!("value should have been consumed"),
+        }
+    }
+
+    match injector.get::<Svc<Svc1>>() {
+        Err(InjectError::AlreadyConsumed { .. }) => {}
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("value should have been consumed"),
+    }
+}
+
+#[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
+fn named_services_are_sorted_and_unique() {
+    let mut builder = Injector::builder();
+    builder.provide(constant(Svc1(2)).with_name("b"));
+    builder.provide(constant(Svc1(1)).with_name("a"));
+    builder.provide(constant(Svc1(0)));
+
+    let injector = builder.build();
+    let named: BTreeMap<String, Svc<Svc1>> = injector.get().unwrap();
+    let values: Vec<_> = named
+        .iter()
+        .map(|(name, svc)| (name.as_str(), svc.0))
+        .collect();
+    assert_eq!(vec![("a", 1), ("b", 2)], values);
+
+    let mut builder = Injector::builder();
+    builder.provide(constant(Svc1(1)).with_name("a"));
+    builder.provide(constant(Svc1(2)).with_name("a"));
+
+    let injector = builder.build();
+    match injector.get::<BTreeMap<String, Svc<Svc1>>>() {
+        Err(InjectError::DuplicateName { name, .. }) if name == "a" => {}
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("duplicate names should be an error"),
+    }
+}
+
+#[test]
+fn request_middleware_runs_once_per_top_level_request() {
+    let top_level = Svc::new(Mutex::new(Vec::new()));
+    let nested = Svc::new(Mutex::new(0));
+
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.transient());
+    builder.provide(Svc2::new.transient());
+    builder.provide(Svc3::new.transient());
+    {
+        let top_level = top_level.clone();
+        builder.add_request_middleware(move |info, next| {
+            top_level.lock().unwrap().push("before");
+            let result = next(info);
+            top_level.lock().unwrap().push("after");
+            result
+        });
+    }
+    {
+        let nested = nested.clone();
+        builder.add_nested_request_middleware(move |info, next| {
+            *nested.lock().unwrap() += 1;
+            next(info)
+        });
+    }
+    builder.add_request_middleware(|info, next| {
+        if info.get_parameter("deny").is_some() {
+            Err(InjectError::MissingProvider {
+                service_info: ServiceInfo::of::<Svc3>(),
+            })
+        } else {
+            next(info)
+        }
+    });
+
+    let injector = builder.build();
+    let _svc3: Svc<Svc3> = injector.get().unwrap();
+    assert_eq!(vec!["before", "after"], *top_level.lock().unwrap());
+    assert!(*nested.lock().unwrap() > 1);
+
+    let mut request_info = RequestInfo::new();
+    request_info.insert_parameter("deny", ());
+    assert!(injector.get_with::<Svc<Svc3>>(&request_info).is_err());
+    assert_eq!(4, top_level.lock().unwrap().len());
+}
+
+#[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
+fn middleware_suppressing_errors_does_not_repeat_request() {
+    let calls = Svc::new(Mutex::new(0));
+
+    let mut builder = Injector::builder();
+    {
+        let calls = calls.clone();
+        builder.provide(
+            (move || -> Result<Svc1, std::fmt::Error> {
+                *calls.lock().unwrap() += 1;
+                Err(std::fmt::Error)
+            })
+            .fallible()
+            .singleton(),
+        );
+    }
+    builder.add_request_middleware(|info, next| {
+        let _ = next(info);
+        Ok(())
+    });
+
+    let injector = builder.build();
+    match injector.get::<Svc<Svc1>>() {
+        Err(InjectError::SuppressedByMiddleware) => {}
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("the request should have failed"),
+    }
+    assert_eq!(1, *calls.lock().unwrap());
+}
+
+#[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
+fn many_provider_yields_each_instance() {
+    let calls = Svc::new(Mutex::new(0));
+
+    let mut builder = Injector::builder();
+    {
+        let calls = calls.clone();
+        builder.provide_many(move |_: &Injector, _: &RequestInfo| {
+            *calls.lock().unwrap() += 1;
+            Ok(vec![Svc1(1), Svc1(2)])
+        });
+    }
+    builder.provide(constant(Svc1(3)));
+
+    let injector = builder.build();
+    let values: Vec<i32> = injector
+        .get::<Vec<Svc<Svc1>>>()
+        .unwrap()
+        .iter()
+        .map(|svc| svc.0)
+        .collect();
+    assert_eq!(vec![1, 2, 3], values);
+
+    let _: Vec<Svc<Svc1>> = injector.get().unwrap();
+    assert_eq!(2, *calls.lock().unwrap());
+
+    let mut builder = Injector::builder();
+    builder.provide(ManyProvider::singleton(
+        |_: &Injector, _: &RequestInfo| Ok(vec![Svc1(1), Svc1(2)]),
+    ));
+
+    let injector = builder.build();
+    match injector.get::<Svc<Svc1>>() {
+        Err(InjectError::MultipleProviders { providers: 2, .. }) => {}
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("multiple instances should be an error"),
+    }
+
+    let first: Vec<Svc<Svc1>> = injector.get().unwrap();
+    let second: Vec<Svc<Svc1>> = injector.get().unwrap();
+    assert!(Svc::ptr_eq(&first[0], &second[0]));
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn injector_assert_checks_resolution() {
+    use crate::InjectorAssert;
+
+    trait Foo: Service {}
+    impl Foo for Svc1 {}
+    interface!(dyn Foo = [Svc1]);
+
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.singleton().with_interface::<dyn Foo>());
+    builder.provide(Svc2::new.transient());
+
+    let injector = builder.build();
+    InjectorAssert::new(&injector)
+        .resolves::<Svc<dyn Foo>>()
+        .resolves_to::<dyn Foo, Svc1>()
+        .count::<dyn Foo>(1)
+        .count::<Svc3>(0)
+        .missing::<Svc<Svc2>>();
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+#[should_panic(expected = "but it resolved")]
+fn injector_assert_panics_on_unexpected_resolution() {
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.singleton());
+
+    let injector = builder.build();
+    crate::InjectorAssert::new(&injector).missing::<Svc<Svc1>>();
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn mock_injector_builder_replaces_module_providers() {
+    use crate::MockInjectorBuilder;
+
+    trait Foo: Service {
+        fn value(&self) -> i32;
+    }
+    impl Foo for Svc1 {
+        fn value(&self) -> i32 {
+            self.0
+        }
+    }
+    interface!(dyn Foo = [Svc1]);
+
+    let mut module = Module::default();
+    module.provide(Svc1::default.singleton().with_interface::<dyn Foo>());
+    module.provide(Svc2::new.transient());
+
+    let mut builder = MockInjectorBuilder::new();
+    builder.mock::<dyn Foo, _>(|| Svc1(5)).add_module(module);
+    builder.mock::<Svc1, _>(|| Svc1(6));
+
+    let injector = builder.build();
+    let foos: Vec<Svc<dyn Foo>> = injector.get().unwrap();
+    assert_eq!(1, foos.len());
+    assert_eq!(5, foos[0].value());
+
+    // Mocks are shared so they can be inspected
+    let svc2: Svc<Svc2> = injector.get().unwrap();
+    let svc1: Svc<Svc1> = injector.get().unwrap();
+    assert!(Svc::ptr_eq(&svc1, &svc2.dep1));
+    assert_eq!(6, svc1.0);
+}
+
+#[test]
+fn scoped_services_share_parent_singletons() {
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.singleton());
+    builder.provide(Svc2::new.scoped());
+
+    let injector = builder.build();
+    let scope1 = injector.create_scope().unwrap();
+    let scope2 = injector.create_scope().unwrap();
+
+    let first: Svc<Svc2> = scope1.get().unwrap();
+    let second: Svc<Svc2> = scope1.get().unwrap();
+    let other: Svc<Svc2> = scope2.get().unwrap();
+    assert!(Svc::ptr_eq(&first, &second));
+    assert!(!Svc::ptr_eq(&first, &other));
+
+    let svc1: Svc<Svc1> = injector.get().unwrap();
+    assert!(Svc::ptr_eq(&svc1, &first.dep1));
+    assert!(Svc::ptr_eq(&svc1, &other.dep1));
+}
+
+#[test]
+#[cfg(feature = "arc")]
+fn per_thread_services_are_distinct_per_thread() {
+    use crate::IntoThreadLocal;
+
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.per_thread());
+
+    let injector = Svc::new(builder.build());
+    let first: Svc<Svc1> = injector.get().unwrap();
+    let second: Svc<Svc1> = injector.get().unwrap();
+    assert!(Svc::ptr_eq(&first, &second));
+
+    let other: Svc<Svc1> = {
+        let injector = injector.clone();
+        std::thread::spawn(move || injector.get().unwrap())
+            .join()
+            .unwrap()
+    };
+    assert!(!Svc::ptr_eq(&first, &other));
+}
+
+#[test]
+#[cfg(feature = "arc")]
+fn per_thread_services_are_dropped_when_thread_exits() {
+    use crate::IntoThreadLocal;
+
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.per_thread());
+
+    let injector = Svc::new(builder.build());
+    let weak = {
+        let injector = injector.clone();
+        std::thread::spawn(move || {
+            let svc1: Svc<Svc1> = injector.get().unwrap();
+            Svc::downgrade(&svc1)
+        })
+        .join()
+        .unwrap()
+    };
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn per_thread_services_are_dropped_with_injector() {
+    use crate::IntoThreadLocal;
+
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.per_thread());
+
+    let injector = builder.build();
+    let svc1: Svc<Svc1> = injector.get().unwrap();
+    let weak = Svc::downgrade(&svc1);
+    drop(svc1);
+    assert!(weak.upgrade().is_some());
+
+    drop(injector);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn param_conditions_require_matching_parameter() {
+    let called = Svc::new(Mutex::new(0));
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.transient().with_condition_param::<bool, _>(
+        "feature",
+        {
+            let called = called.clone();
+            move |value| {
+                *called.lock().unwrap() += 1;
+                *value
+            }
+        },
+    ));
+
+    let injector = builder.build();
+    let missing: Option<Box<Svc1>> = injector.get().unwrap();
+    assert!(missing.is_none());
+
+    let mut request_info = RequestInfo::new();
+    request_info.insert_parameter("feature", 1i32);
+    let wrong_type: Option<Box<Svc1>> =
+        injector.get_with(&request_info).unwrap();
+    assert!(wrong_type.is_none());
+    assert_eq!(0, *called.lock().unwrap());
+
+    let mut request_info = RequestInfo::new();
+    request_info.insert_parameter("feature", false);
+    let disabled: Option<Box<Svc1>> = injector.get_with(&request_info).unwrap();
+    assert!(disabled.is_none());
+
+    let mut request_info = RequestInfo::new();
+    request_info.insert_parameter("feature", true);
+    let enabled: Option<Box<Svc1>> = injector.get_with(&request_info).unwrap();
+    assert!(enabled.is_some());
+    assert_eq!(2, *called.lock().unwrap());
+}
+
+#[test]
+fn disposers_run_in_reverse_order_after_last_injector_drops() {
+    let disposed = Svc::new(Mutex::new(Vec::new()));
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.singleton());
+    builder.provide(Svc2::new.singleton());
+    builder.provide(Svc3::new.transient());
+    builder.on_dispose({
+        let disposed = disposed.clone();
+        move |_: Svc<Svc1>| disposed.lock().unwrap().push("svc1")
+    });
+    builder.on_dispose({
+        let disposed = disposed.clone();
+        move |_: Svc<Svc2>| disposed.lock().unwrap().push("svc2")
+    });
+    builder.on_dispose({
+        let disposed = disposed.clone();
+        move |_: Svc<Svc3>| disposed.lock().unwrap().push("svc3")
+    });
+
+    let injector = builder.build();
+    let scope = injector.create_scope().unwrap();
+    let _svc3: Svc<Svc3> = scope.get().unwrap();
+    let _svc2: Svc<Svc2> = injector.get().unwrap();
+
+    drop(injector);
+    assert!(disposed.lock().unwrap().is_empty());
+
+    drop(scope);
+    assert_eq!(vec!["svc2", "svc1"], *disposed.lock().unwrap());
+}
+
+#[test]
+fn registrations_are_reported_without_activation() {
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.singleton());
+    builder.provide(Svc1::default.transient());
+    builder.layer("extra", |module| module.provide(Svc2::new.singleton()));
+    assert!(builder.contains::<Svc1>());
+    assert!(builder.contains::<Svc2>());
+    assert!(!builder.contains::<Svc3>());
+
+    builder.disable_layer("extra");
+    assert!(!builder.contains::<Svc2>());
+    builder.enable_layer("extra");
+
+    let injector = builder.build();
+    let mut expected =
+        vec![ServiceInfo::of::<Svc1>(), ServiceInfo::of::<Svc2>()];
+    expected.sort_by_key(ServiceInfo::name);
+    assert_eq!(expected, injector.registered_services());
+    assert_eq!(2, injector.provider_count(ServiceInfo::of::<Svc1>()));
+    assert_eq!(0, injector.provider_count(ServiceInfo::of::<Svc3>()));
+    assert!(!injector.is_interface(ServiceInfo::of::<Svc1>()));
+}
+
+#[test]
+fn mapped_providers_preserve_inner_lifetime() {
+    struct Derived(i32);
+
+    let mut builder = Injector::builder();
+    builder.provide(
+        Svc1::default
+            .singleton()
+            .map_result(|svc1: Svc<Svc1>, _| Ok(Derived(svc1.0 + 1))),
+    );
+    builder.provide(
+        (|| Svc1(10))
+            .transient()
+            .map_result(|svc1: Svc<Svc1>, _| Ok(svc1.0.to_string())),
+    );
+
+    let injector = builder.build();
+    let first: Svc<Derived> = injector.get().unwrap();
+    let second: Svc<Derived> = injector.get().unwrap();
+    assert_eq!(1, first.0);
+    assert!(Svc::ptr_eq(&first, &second));
+
+    let first: Svc<String> = injector.get().unwrap();
+    let second: Svc<String> = injector.get().unwrap();
+    assert_eq!("10", *first);
+    assert!(!Svc::ptr_eq(&first, &second));
+
+    // The original services are not registered on their own
+    assert!(injector.get::<Svc<Svc1>>().is_err());
+}
+
+#[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
+fn provide_fallible_preserves_original_errors() {
+    #[derive(Debug)]
+    struct CustomError;
+
+    impl Error for CustomError {}
+
+    impl Display for CustomError {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(f, "custom error")
+        }
+    }
+
+    let mut builder = Injector::builder();
+    builder.provide_fallible(|| -> Result<Svc1, _> { Err(CustomError) });
+    builder.provide_fallible(
+        || -> Result<Svc2, Box<dyn Error + Send + Sync>> {
+            Err("boxed error".into())
+        },
+    );
+
+    let injector = builder.build();
+    match injector.get::<Svc<Svc1>>() {
+        Err(InjectError::ActivationFailed { inner, .. }) => {
+            assert!(inner.downcast_ref::<CustomError>().is_some());
+        }
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("activation should have failed"),
+    }
+    match injector.get::<Svc<Svc2>>() {
+        Err(InjectError::ActivationFailed { inner, .. }) => {
+            assert_eq!("boxed error", inner.to_string());
+        }
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("activation should have failed"),
+    }
+}
+
+#[test]
+fn tagged_services_are_grouped_by_tag() {
+    let mut builder = Injector::builder();
+    builder.provide(
+        (|| Svc1(1))
+            .singleton()
+            .with_tag("startup")
+            .with_tag("shutdown"),
+    );
+    builder.provide((|| Svc1(2)).singleton().with_tag("startup"));
+    builder.provide((|| Svc1(3)).singleton());
+
+    let injector = builder.build();
+    let startup: Vec<Svc<Svc1>> = injector.get_tagged("startup").unwrap();
+    let startup: Vec<_> = startup.iter().map(|svc1| svc1.0).collect();
+    assert_eq!(vec![1, 2], startup);
+
+    let shutdown: Vec<Svc<Svc1>> = injector.get_tagged("shutdown").unwrap();
+    assert_eq!(1, shutdown.len());
+    assert!(injector.get_tagged::<Svc1>("other").unwrap().is_empty());
+    assert!(injector.get_tagged::<Svc2>("startup").unwrap().is_empty());
+}
+
+#[test]
+fn describe_reports_wrapped_provider_lifetimes() {
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.transient().with_condition(|_, _| false));
+    builder.provide(Svc2::new.scoped().with_name("svc2"));
+    builder.provide(constant(Svc3 {
+        dep1: Svc::new(Svc1(1)),
+        dep2: Svc::new(Svc2 {
+            dep1: Svc::new(Svc1(2)),
+        }),
+    }));
+
+    let injector = builder.build();
+    let lifetimes: Vec<_> = injector
+        .describe()
+        .into_iter()
+        .map(|description| (description.service_info, description.lifetime))
+        .collect();
+    let mut expected = vec![
+        (ServiceInfo::of::<Svc1>(), Lifetime::Transient),
+        (ServiceInfo::of::<Svc2>(), Lifetime::Scoped),
+        (ServiceInfo::of::<Svc3>(), Lifetime::Constant),
+    ];
+    expected.sort_by_key(|(service_info, _)| service_info.name());
+    assert_eq!(expected, lifetimes);
+}
+
+#[test]
+fn module_overrides_replace_conflicting_providers() {
+    let mut base = Module::default();
+    base.provide(Svc1::default.singleton());
+    base.provide(constant(1u32));
+    base.provide_named("named", constant(2u32));
+
+    let mut overrides = Module::default();
+    overrides.provide(constant(3u32));
+    overrides.provide(constant(4u32));
+    overrides.provide_named("other", constant(5u32));
+
+    let mut builder = Injector::builder();
+    builder.add_module(base);
+    builder.add_module_override(overrides);
+
+    let injector = builder.build();
+    let _: Svc<Svc1> = injector.get().unwrap();
+    let values: Vec<Svc<u32>> = injector.get().unwrap();
+    let values: Vec<u32> = values.into_iter().map(|value| *value).collect();
+    assert_eq!(vec![3, 4], values);
+    let named: Svc<u32> = injector.get_named("named").unwrap();
+    assert_eq!(2, *named);
+    let other: Svc<u32> = injector.get_named("other").unwrap();
+    assert_eq!(5, *other);
+}
+
+#[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
+fn requests_deeper_than_max_depth_fail() {
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.transient());
+    builder.provide(Svc2::new.transient());
+    builder.provide(Svc3::new.transient());
+    builder.provide((|info: RequestInfo| info.depth()).transient());
+    builder.set_max_depth(1);
+
+    let injector = builder.build();
+    let _: Svc<Svc2> = injector.get().unwrap();
+    // The request path seen by a factory includes the service it creates
+    let depth: Svc<usize> = injector.get().unwrap();
+    assert_eq!(1, *depth);
+    match injector.get::<Svc<Svc3>>() {
+        Err(InjectError::MaxDepthExceeded {
+            service_info,
+            depth,
+        }) => {
+            assert_eq!(ServiceInfo::of::<Svc1>(), service_info);
+            assert_eq!(2, depth);
+        }
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("Svc1 should be requested too deeply"),
+    }
+}
+
+#[test]
+fn mutable_constants_are_shared() {
+    struct Counter(i32);
+
+    struct Incrementer(SvcMut<Counter>);
+
+    let mut builder = Injector::builder();
+    builder.provide(constant_mut(Counter(0)));
+    builder.provide(Incrementer.transient());
+
+    let injector = builder.build();
+    for _ in 0..3 {
+        let incrementer: Svc<Incrementer> = injector.get().unwrap();
+        incrementer.0.with(|counter| counter.0 += 1);
+    }
+
+    let counter: SvcMut<Counter> = injector.get().unwrap();
+    assert_eq!(3, counter.with(|counter| counter.0));
+}
+
+#[test]
+fn dependency_errors_are_chained() {
+    #[allow(dead_code)]
+    struct Foo(Svc<Svc2>);
+
+    let mut builder = Injector::builder();
+    builder.provide(Foo.transient());
+    builder.provide(Svc2::new.transient());
+
+    let injector = builder.build();
+    let error = injector
+        .get::<Svc<Foo>>()
+        .err()
+        .expect("Svc1 has no provider");
+    match &error {
+        InjectError::MissingDependency {
+            service_info,
+            dependency_info,
+            inner: Some(inner),
+        } => {
+            assert_eq!(ServiceInfo::of::<Foo>(), *service_info);
+            assert_eq!(ServiceInfo::of::<Svc2>(), *dependency_info);
+            match inner.as_ref() {
+                InjectError::MissingDependency {
+                    service_info,
+                    dependency_info,
+                    inner: None,
+                } => {
+                    assert_eq!(ServiceInfo::of::<Svc2>(), *service_info);
+                    assert_eq!(ServiceInfo::of::<Svc1>(), *dependency_info);
+                }
+                inner => panic!("unexpected inner error: {:?}", inner),
+            }
+        }
+        error => panic!("unexpected error: {:?}", error),
+    }
+
+    let chain = format!("{error:#}");
+    assert!(chain.contains(&format!("{error}")));
+    assert!(chain.contains(ServiceInfo::of::<Svc1>().name()));
+    assert_eq!(
+        1,
+        chain.matches("an error occurred during injection").count()
+    );
+    assert!(error.source().unwrap().source().is_none());
+}
+
+#[test]
+fn with_name_providers_can_be_requested_by_name() {
+    trait Cache: Service {
+        fn url(&self) -> String;
+    }
+
+    struct RedisCache(Arg<String>);
+    impl Cache for RedisCache {
+        fn url(&self) -> String {
+            self.0.clone()
+        }
+    }
+
+    #[derive(Default)]
+    struct MemoryCache;
+    impl Cache for MemoryCache {
+        fn url(&self) -> String {
+            "memory".to_owned()
+        }
+    }
+
+    interface!(dyn Cache = [RedisCache, MemoryCache]);
+
+    let mut builder = Injector::builder();
+    builder.provide(
+        RedisCache
+            .singleton()
+            .with_interface::<dyn Cache>()
+            .with_name("redis"),
+    );
+    builder.provide(
+        MemoryCache::default
+            .singleton()
+            .with_name("memory")
+            .with_interface::<dyn Cache>(),
+    );
+    builder.with_arg::<RedisCache, String>("redis://localhost".to_owned());
+
+    let injector = builder.build();
+    let caches: Vec<Svc<dyn Cache>> = injector.get().unwrap();
+    assert_eq!(2, caches.len());
+    let redis: Svc<dyn Cache> = injector.get_named("redis").unwrap();
+    assert_eq!("redis://localhost", redis.url());
+    let memory: Svc<dyn Cache> = injector.get_named("memory").unwrap();
+    assert_eq!("memory", memory.url());
+    assert!(caches.iter().any(|cache| Svc::ptr_eq(cache, &redis)));
+    assert!(injector.get_named::<Svc<dyn Cache>>("disk").is_err());
+}
+
+#[test]
+fn unused_providers_exclude_activated_dependencies() {
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.singleton());
+    builder.provide(Svc2::new.transient());
+    builder.provide(Svc3::new.transient());
+    builder.provide_named("named", constant(1u32));
+
+    let injector = builder.build();
+    assert!(injector.unused_providers().is_empty());
+
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.singleton());
+    builder.provide(Svc2::new.transient());
+    builder.provide(Svc3::new.transient());
+    builder.provide_named("named", constant(1u32));
+    builder.track_unused();
+
+    let injector = builder.build();
+    let mut expected = vec![
+        ServiceInfo::of::<Svc1>(),
+        ServiceInfo::of::<Svc2>(),
+        ServiceInfo::of::<Svc3>(),
+        ServiceInfo::of::<u32>(),
+    ];
+    expected.sort_by_key(ServiceInfo::name);
+    assert_eq!(expected, injector.unused_providers());
+
+    let scope = injector.create_scope().unwrap();
+    let _: Svc<Svc2> = scope.get().unwrap();
+    let _: Svc<u32> = injector.get_named("named").unwrap();
+    assert_eq!(vec![ServiceInfo::of::<Svc3>()], injector.unused_providers());
+}
+
+#[test]
+fn count_does_not_activate_providers() {
+    let mut builder = Injector::builder();
+    builder.provide(
+        (|| -> Svc1 { unreachable!("Svc1 should not be activated") })
+            .transient(),
+    );
+    builder.provide((|| Svc1(1)).transient());
+
+    let injector = builder.build();
+    let count: Count<Svc1> = injector.get().unwrap();
+    assert_eq!(2, count.get());
+    let count: Count<Svc2> = injector.get().unwrap();
+    assert_eq!(0, count.get());
+
+    let mut overrides = Overrides::new();
+    overrides.insert(Svc::new(Svc1(2)));
+    let count: Count<Svc1> = injector.get_with_overrides(overrides).unwrap();
+    assert_eq!(1, count.get());
+}
+
+#[test]
+fn child_injectors_isolate_parent_dependencies() {
+    trait Greeting: Service {
+        fn text(&self) -> String;
+    }
+
+    struct Hello;
+    impl Greeting for Hello {
+        fn text(&self) -> String {
+            "hello".to_owned()
+        }
+    }
+
+    struct Howdy;
+    impl Greeting for Howdy {
+        fn text(&self) -> String {
+            "howdy".to_owned()
+        }
+    }
+
+    interface!(dyn Greeting = [Hello, Howdy]);
+
+    struct Greeter(Svc<dyn Greeting>);
+    struct PluginGreeter(Svc<dyn Greeting>, Svc<Greeter>);
+
+    let mut builder = Injector::builder();
+    builder.provide(constant(Hello).with_interface::<dyn Greeting>());
+    builder.provide(Greeter.singleton());
+    builder.provide_named("named", constant(Svc1(1)));
+    let parent = builder.build();
+
+    let mut builder = parent.child().unwrap();
+    builder.provide(constant(Howdy).with_interface::<dyn Greeting>());
+    builder.provide(PluginGreeter.singleton());
+    builder.validate().unwrap();
+    let child = builder.build();
+
+    let plugin: Svc<PluginGreeter> = child.get().unwrap();
+    assert_eq!("howdy", plugin.0.text());
+    assert_eq!("hello", plugin.1 .0.text());
+    let greeter: Svc<Greeter> = parent.get().unwrap();
+    assert!(Svc::ptr_eq(&greeter, &plugin.1));
+    let greetings: Vec<Svc<dyn Greeting>> = child.get().unwrap();
+    assert_eq!(1, greetings.len());
+    let named: Svc<Svc1> = child.get_named("named").unwrap();
+    assert_eq!(1, named.0);
+
+    let scope = child.create_scope().unwrap();
+    let scoped_plugin: Svc<PluginGreeter> = scope.get().unwrap();
+    assert!(Svc::ptr_eq(&plugin, &scoped_plugin));
+    let sibling = parent.child().unwrap().build();
+    assert!(sibling.get::<Svc<PluginGreeter>>().is_err());
+}
+
+#[test]
+fn interface_collections_request_all_implementations() {
+    trait Foo: Service {
+        fn value(&self) -> i32;
+    }
+
+    impl Foo for Svc1 {
+        fn value(&self) -> i32 {
+            self.0
+        }
+    }
+
+    impl Foo for Svc2 {
+        fn value(&self) -> i32 {
+            self.dep1.0 + 1
+        }
+    }
+
+    interface!(dyn Foo = [Svc1, Svc2] with collection(Foos, InjectorFoos::foos));
+
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.singleton().with_interface::<dyn Foo>());
+    builder.provide(Svc1::default.singleton());
+    builder.provide(Svc2::new.transient().with_interface::<dyn Foo>());
+
+    let injector = builder.build();
+    let foos: Foos = injector.foos().unwrap();
+    let mut values: Vec<_> = foos.iter().map(|foo| foo.value()).collect();
+    values.sort_unstable();
+    assert_eq!(vec![0, 1], values);
+}
+
+#[test]
+fn fallback_providers_replace_failed_activations() {
+    #[derive(Debug)]
+    struct CustomError;
+
+    impl Error for CustomError {}
+
+    impl Display for CustomError {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(f, "custom error")
+        }
+    }
+
+    fn primary(fail: Svc<bool>) -> Result<Svc1, CustomError> {
+        if *fail {
+            Err(CustomError)
+        } else {
+            Ok(Svc1(1))
+        }
+    }
+
+    let build = |fail: bool| {
+        let fallback_calls = Svc::new(Mutex::new(0));
+        let calls = fallback_calls.clone();
+        let mut builder = Injector::builder();
+        builder.provide(
+            primary.fallible().singleton().or_else(
+                (move || {
+                    *calls.lock().unwrap() += 1;
+                    Svc1(2)
+                })
+                .singleton(),
+            ),
+        );
+        builder.provide(constant(fail));
+        (builder.build(), fallback_calls)
+    };
+
+    // The fallback supplies the value when the primary fails
+    let (injector, fallback_calls) = build(true);
+    let svc: Svc<Svc1> = injector.get().unwrap();
+    assert_eq!(2, svc.0);
+    assert_eq!(1, *fallback_calls.lock().unwrap());
+
+    // A successful primary is cached and the fallback is never attempted
+    let (injector, fallback_calls) = build(false);
+    let first: Svc<Svc1> = injector.get().unwrap();
+    let second: Svc<Svc1> = injector.get().unwrap();
+    assert_eq!(1, first.0);
+    assert!(Svc::ptr_eq(&first, &second));
+    assert_eq!(0, *fallback_calls.lock().unwrap());
+}
+
+#[test]
+fn generic_providers_are_registered_for_each_type() {
+    struct Repository<T>(PhantomData<fn() -> T>);
+
+    impl<T> Repository<T> {
+        fn new() -> Self {
+            Repository(PhantomData)
+        }
+    }
+
+    let mut module = Module::default();
+    provide_generic!(
+        module,
+        T in [Svc1, Svc2, Svc3] => Repository::<T>::new.singleton(),
+    );
+
+    let mut builder = Injector::builder();
+    builder.add_module(module);
+
+    let injector = builder.build();
+    let _: Svc<Repository<Svc1>> = injector.get().unwrap();
+    let _: Svc<Repository<Svc2>> = injector.get().unwrap();
+    let _: Svc<Repository<Svc3>> = injector.get().unwrap();
+    assert!(injector.get::<Svc<Repository<i32>>>().is_err());
+}
+
+#[test]
+fn resolve_hooks_report_successful_and_failed_activations() {
+    let resolved = Svc::new(Mutex::new(Vec::new()));
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.singleton());
+    builder.provide(Svc2::new.transient());
+    builder.provide(Svc3::new.transient().with_condition(|_, _| false));
+    builder.on_resolve({
+        let resolved = resolved.clone();
+        move |service_info: &ServiceInfo, _, result: &InjectResult<()>| {
+            resolved
+                .lock()
+                .unwrap()
+                .push((*service_info, result.is_ok()));
+        }
+    });
+
+    let injector = builder.build();
+    let _: Svc<Svc2> = injector.get().unwrap();
+    assert!(injector.get::<Svc<Svc3>>().is_err());
+
+    // Dependencies finish activating before the services that depend on them
+    assert_eq!(
+        vec![
+            (ServiceInfo::of::<Svc1>(), true),
+            (ServiceInfo::of::<Svc2>(), true),
+            (ServiceInfo::of::<Svc3>(), false),
+        ],
+        *resolved.lock().unwrap()
+    );
+}
+
+#[test]
+fn implementations_of_lists_concrete_types_without_activation() {
+    trait Foo: Service {}
+    impl Foo for Svc1 {}
+    impl Foo for Svc2 {}
+
+    interface!(dyn Foo = [Svc1, Svc2]);
+
+    let mut builder = Injector::builder();
+    builder.provide(
+        (|| -> Svc1 { unreachable!("provider should not be activated") })
+            .singleton()
+            .with_interface::<dyn Foo>(),
+    );
+    builder.provide(Svc2::new.transient().with_interface::<dyn Foo>());
+    builder.provide(Svc1::default.singleton());
+
+    let injector = builder.build();
+    assert_eq!(
+        vec![ServiceInfo::of::<Svc1>(), ServiceInfo::of::<Svc2>()],
+        injector.implementations_of::<dyn Foo>()
+    );
+    assert_eq!(
+        vec![ServiceInfo::of::<Svc1>()],
+        injector.implementations_of::<Svc1>()
+    );
+    assert!(injector.implementations_of::<Svc3>().is_empty());
+}
+
+#[test]
+fn request_params_only_apply_to_their_request() {
+    fn tenant(request_info: RequestInfo) -> String {
+        let tenant = request_info.get_parameter("tenant").unwrap();
+        tenant.downcast_ref::<String>().unwrap().clone()
+    }
+
+    let mut builder = Injector::builder();
+    builder.provide(tenant.transient());
+    builder
+        .root_info_mut()
+        .insert_parameter("tenant", "root".to_owned());
+
+    let injector = builder.build();
+    let value: Svc<String> = injector
+        .get_with_params(vec![(
+            "tenant".to_owned(),
+            Box::new("inline".to_owned()) as Box<dyn RequestParameter>,
+        )])
+        .unwrap();
+    assert_eq!("inline", *value);
+
+    let request_info = RequestInfo::builder()
+        .param("tenant", "built".to_owned())
+        .build();
+    let value: Svc<String> = injector.get_with(&request_info).unwrap();
+    assert_eq!("built", *value);
+
+    let value: Svc<String> = injector.get().unwrap();
+    assert_eq!("root", *value);
+}
+
+#[test]
+fn dyn_factories_provide_interfaces_directly() {
+    trait Foo: Service {
+        fn value(&self) -> i32;
+    }
+
+    impl Foo for Svc1 {
+        fn value(&self) -> i32 {
+            self.0
+        }
+    }
+
+    interface!(dyn Foo = []);
+
+    let mut builder = Injector::builder();
+    builder.provide(constant(3));
+    builder.provide_dyn(|value: Svc<i32>| -> Box<dyn Foo> {
+        Box::new(Svc1(*value))
+    });
+
+    let injector = builder.build();
+    let foo: Svc<dyn Foo> = injector.get().unwrap();
+    assert_eq!(3, foo.value());
+    let foo: Box<dyn Foo> = injector.get().unwrap();
+    assert_eq!(3, foo.value());
+    let foos: Vec<Svc<dyn Foo>> = injector.get().unwrap();
+    assert_eq!(1, foos.len());
+}
+
+#[test]
+fn count_available_skips_unmet_conditions() {
+    trait Foo: Service {}
+    impl Foo for Svc1 {}
+
+    interface!(dyn Foo = [Svc1]);
+
+    let mut builder = Injector::builder();
+    builder.provide(
+        Svc1::default
+            .singleton()
+            .with_condition(|_, _| false)
+            .with_interface::<dyn Foo>(),
+    );
+    builder.provide(
+        Svc1::default
+            .singleton()
+            .with_condition_param("enabled", |&enabled: &bool| enabled)
+            .with_interface::<dyn Foo>(),
+    );
+    builder.provide(Svc1::default.transient().with_interface::<dyn Foo>());
+    builder.provide_error::<dyn Foo, _>(|| "unavailable".into());
+
+    let injector = builder.build();
+    let foos: Services<dyn Foo> = injector.get().unwrap();
+    assert_eq!(4, foos.len());
+    assert_eq!(1, foos.count_available());
+    drop(foos);
+
+    let foos: Services<dyn Foo> = injector
+        .get_with(&RequestInfo::builder().param("enabled", true).build())
+        .unwrap();
+    assert_eq!(2, foos.count_available());
+}
+
+#[test]
+fn owned_requests_clone_registered_values() {
+    #[derive(Clone)]
+    struct Config(Vec<i32>);
+
+    struct Consumer {
+        config: Owned<Config>,
+        name: Cow<'static, str>,
+    }
+
+    let mut builder = Injector::builder();
+    builder.provide(constant(Config(vec![1, 2])));
+    builder.provide(constant(Cow::Borrowed("consumer")));
+    builder.provide((|config, name| Consumer { config, name }).transient());
+
+    let injector = builder.build();
+    let mut consumer: Box<Consumer> = injector.get().unwrap();
+    consumer.config.0.push(3);
+    assert_eq!(vec![1, 2, 3], consumer.config.0);
+    assert_eq!("consumer", consumer.name);
+
+    // The registered value is unchanged
+    let config: Svc<Config> = injector.get().unwrap();
+    assert_eq!(vec![1, 2], config.0);
+    assert!(injector.get::<Owned<String>>().is_err());
+}
+
+#[test]
+fn module_args_apply_to_their_own_providers() {
+    struct Db(Arg<i32>);
+
+    trait Foo: Service {
+        fn value(&self) -> i32;
+    }
+
+    impl Foo for Db {
+        fn value(&self) -> i32 {
+            *self.0
+        }
+    }
+
+    interface!(dyn Foo = [Db]);
+
+    let mut first = Module::default();
+    first.provide(Db.singleton().with_interface::<dyn Foo>());
+    first.with_arg::<Db, i32>(1);
+
+    let mut second = Module::default();
+    second.provide(Db.transient().with_interface::<dyn Foo>());
+    second.with_arg::<Db, i32>(2);
+
+    // Services outside of a module still see the module's args
+    let mut builder = Injector::builder();
+    builder.add_module(first);
+    builder.add_module(second);
+    builder.provide(Db.transient());
+
+    let injector = builder.build();
+    let foos: Vec<Svc<dyn Foo>> = injector.get().unwrap();
+    let values: Vec<_> = foos.iter().map(|foo| foo.value()).collect();
+    assert_eq!(vec![1, 2], values);
+    let db: Svc<Db> = injector.get().unwrap();
+    assert_eq!(2, *db.0);
+}
+
+#[test]
+fn build_hooks_rewrite_providers_from_modules() {
+    struct Spy {
+        inner: Box<dyn Provider>,
+        activations: Svc<Mutex<Vec<ServiceInfo>>>,
+    }
+
+    impl Provider for Spy {
+        fn result(&self) -> ServiceInfo {
+            self.inner.result()
+        }
+
+        fn provide(
+            &mut self,
+            injector: &Injector,
+            request_info: &RequestInfo,
+        ) -> InjectResult<DynSvc> {
+            self.activations.lock().unwrap().push(self.inner.result());
+            self.inner.provide(injector, request_info)
+        }
+
+        fn lifetime(&self) -> Lifetime {
+            self.inner.lifetime()
+        }
+    }
+
+    let activations = Svc::new(Mutex::new(Vec::new()));
+    let mut builder = Injector::builder();
+    builder.add_build_hook({
+        let activations = activations.clone();
+        move |registry: &mut ProviderRegistry<'_>| {
+            registry.rewrite(|inner| {
+                if inner.lifetime() == Lifetime::Singleton {
+                    Box::new(Spy {
+                        inner,
+                        activations: activations.clone(),
+                    })
+                } else {
+                    inner
+                }
+            });
+        }
+    });
+    builder.add_build_hook(|registry: &mut ProviderRegistry<'_>| {
+        assert!(registry.services().contains(&ServiceInfo::of::<Svc2>()));
+        assert_eq!(1, registry.remove(ServiceInfo::of::<Svc2>()).len());
+        registry.provide(Svc2::new.transient());
+    });
+
+    // Modules are added before hooks run, even when added after the hooks
+    let mut module = Module::default();
+    module.provide(Svc1::default.singleton());
+    module.provide(Svc2::new.singleton());
+    builder.add_module(module);
+
+    let injector = builder.build();
+    let _svc2: Svc<Svc2> = injector.get().unwrap();
+    assert_eq!(
+        vec![ServiceInfo::of::<Svc1>()],
+        *activations.lock().unwrap()
+    );
+}
+
+#[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
+fn get_dyn_requests_services_known_at_runtime() {
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.singleton());
+    builder.provide(Svc2::new.transient());
+
+    let injector = builder.build();
+    let svc2 = injector.get_dyn(ServiceInfo::of::<Svc2>()).unwrap();
+    let svc2 = svc2.downcast::<Svc2>().unwrap();
+    let svc1: Svc<Svc1> = injector.get().unwrap();
+    assert!(Svc::ptr_eq(&svc1, &svc2.dep1));
+
+    match injector.get_dyn(ServiceInfo::of::<Svc3>()) {
+        Err(InjectError::MissingProvider { service_info })
+            if service_info == ServiceInfo::of::<Svc3>() => {}
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("service should not be able to be activated"),
+    }
+}
+
+#[test]
+fn get_dyn_erases_decorated_services() {
+    trait Foo: Service {}
+    interface!(dyn Foo = [Svc1]);
+    impl Foo for Svc1 {}
+
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.singleton());
+    builder.provide(Svc1::default.singleton().with_interface::<dyn Foo>());
+    builder.decorate(|_: Svc<Svc1>, _, _| Ok(Svc::new(Svc1(5))));
+    builder.decorate(|foo: Svc<dyn Foo>, _, _| Ok(foo));
+
+    let injector = builder.build();
+    let svc1 = injector.get_dyn(ServiceInfo::of::<Svc1>()).unwrap();
+    let svc1 = svc1.downcast::<Svc1>().unwrap();
+    assert_eq!(5, svc1.0);
+
+    let svc1: Svc<Svc1> = injector.get().unwrap();
+    assert_eq!(5, svc1.0);
+    let _: Svc<dyn Foo> = injector.get().unwrap();
+}
+
+#[test]
+fn services_first_prefers_first_registered_implementation() {
+    trait Foo: Service {
+        fn name(&self) -> &'static str;
+    }
+
+    impl Foo for Svc1 {
+        fn name(&self) -> &'static str {
+            "svc1"
+        }
+    }
+
+    impl Foo for Svc2 {
+        fn name(&self) -> &'static str {
+            "svc2"
+        }
+    }
+
+    interface!(dyn Foo = [Svc1, Svc2]);
+
+    let mut builder = Injector::builder();
+    builder.provide(
+        Svc1::default
+            .singleton()
+            .with_condition(|_, _| false)
+            .with_interface::<dyn Foo>(),
+    );
+    builder.provide(Svc1::default.singleton());
+    builder.provide(Svc2::new.transient().with_interface::<dyn Foo>());
+    builder.provide(Svc1::default.transient().with_interface::<dyn Foo>());
+
+    let injector = builder.build();
+    let mut foos: Services<dyn Foo> = injector.get().unwrap();
+    let foo = foos.first().unwrap().unwrap();
+    assert_eq!("svc2", foo.name());
+    let foo = foos.first_owned().unwrap().unwrap();
+    assert_eq!("svc2", foo.name());
+}
+
+#[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
+fn cancelled_requests_stop_between_dependencies() {
+    struct Token(Mutex<Vec<ServiceInfo>>);
+
+    impl IsCancelled for Token {
+        fn is_cancelled(&self) -> bool {
+            // Cancel the request once the first dependency is activated
+            !self.0.lock().unwrap().is_empty()
+        }
+    }
+
+    let token = Svc::new(Token(Mutex::new(Vec::new())));
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.transient());
+    builder.provide(Svc2::new.transient());
+    builder.provide(Svc3::new.transient());
+    builder.on_resolve({
+        let token = token.clone();
+        move |service_info: &ServiceInfo, _, _: &InjectResult<()>| {
+            token.0.lock().unwrap().push(*service_info);
+        }
+    });
+
+    let injector = builder.build();
+    let request_info =
+        RequestInfo::builder().cancellation(token.clone()).build();
+    match injector.get_with::<Svc<Svc3>>(&request_info) {
+        Err(InjectError::Cancelled { service_info })
+            if service_info == ServiceInfo::of::<Svc2>() => {}
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("request should have been cancelled"),
+    }
+
+    // Requests without the token aren't cancelled
+    let _svc3: Svc<Svc3> = injector.get().unwrap();
+}
+
+#[test]
+fn sealed_injectors_block_new_scopes_and_children() {
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.scoped());
+    let injector = builder.build();
+    let scope = injector.create_scope().unwrap();
+    let child = injector.child().unwrap().build();
+    let svc1: Svc<Svc1> = scope.get().unwrap();
+
+    scope.seal();
+    assert!(injector.is_sealed());
+    assert!(!child.is_sealed());
+    assert!(matches!(injector.create_scope(), Err(InjectError::Sealed)));
+    assert!(matches!(injector.child(), Err(InjectError::Sealed)));
+    assert!(matches!(
+        scope.clone().create_scope(),
+        Err(InjectError::Sealed)
+    ));
+
+    // Existing scopes and children can still be used
+    let scoped: Svc<Svc1> = scope.get().unwrap();
+    assert!(Svc::ptr_eq(&svc1, &scoped));
+    let _scope = child.create_scope().unwrap();
+}
+
+#[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
+fn array_requests_require_exact_count() {
+    trait Foo: Service {}
+    impl Foo for Svc1 {}
+    impl Foo for Svc2 {}
+
+    interface!(dyn Foo = [Svc1, Svc2]);
+
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.singleton());
+    builder.provide(Svc1::default.transient().with_interface::<dyn Foo>());
+    builder.provide(Svc2::new.transient().with_interface::<dyn Foo>());
+
+    let injector = builder.build();
+    let [_foo1, _foo2]: [Svc<dyn Foo>; 2] = injector.get().unwrap();
+    let [_foo1, _foo2]: [Box<dyn Foo>; 2] = injector.get().unwrap();
+    let [_svc1]: [Svc<Svc1>; 1] = injector.get().unwrap();
+    match injector.get::<[Box<dyn Foo>; 3]>() {
+        Err(InjectError::WrongCount {
+            service_info,
+            expected: 3,
+            actual: 2,
+        }) if service_info == ServiceInfo::of::<dyn Foo>() => {}
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("request should have the wrong count"),
+    }
+}
+
+#[test]
+fn pooled_providers_reuse_checked_in_instances() {
+    let mut builder = Injector::builder();
+    let mut next = 0;
+    builder.provide(
+        (move || {
+            next += 1;
+            Svc1(next)
+        })
+        .pooled(2),
+    );
+
+    let injector = builder.build();
+    let first: Svc<Pooled<Svc1>> = injector.get().unwrap();
+    let second: Svc<Pooled<Svc1>> = injector.get().unwrap();
+    assert_eq!((1, 2), (first.0, second.0));
+
+    // The least recently used instance is reused first
+    drop(first);
+    drop(second);
+    let svc1: Svc<Pooled<Svc1>> = injector.get().unwrap();
+    assert_eq!(1, svc1.0);
+    drop(svc1);
+    let svc1: Svc<Pooled<Svc1>> = injector.get().unwrap();
+    assert_eq!(2, svc1.0);
+}
+
+#[test]
+fn pooled_providers_create_unpooled_instances_over_cap() {
+    let mut builder = Injector::builder();
+    builder.provide(
+        Svc1::default
+            .pooled(1)
+            .when_exhausted(PoolExhausted::CreateOverCap),
+    );
+
+    let injector = builder.build();
+    let pooled: Svc<Pooled<Svc1>> = injector.get().unwrap();
+    let over_cap: Svc<Pooled<Svc1>> = injector.get().unwrap();
+    assert!(Pooled::is_pooled(&pooled));
+    assert!(!Pooled::is_pooled(&over_cap));
+
+    // Instances created over the cap aren't checked in
+    drop(over_cap);
+    let svc1: Svc<Pooled<Svc1>> = injector.get().unwrap();
+    assert!(!Pooled::is_pooled(&svc1));
+}
+
+#[cfg(feature = "arc")]
+#[test]
+fn pooled_instances_are_checked_in_from_other_threads() {
+    let mut builder = Injector::builder();
+    builder
+        .provide(Svc1::default.pooled(1).when_exhausted(PoolExhausted::Error));
+
+    let injector = builder.build();
+    let svc1: Box<Pooled<Svc1>> = injector.get().unwrap();
+    assert!(injector.get::<Svc<Pooled<Svc1>>>().is_err());
+
+    std::thread::spawn(move || drop(svc1)).join().unwrap();
+    let _svc1: Svc<Pooled<Svc1>> = injector.get().unwrap();
+}
+
+#[test]
+fn short_names_trim_outer_module_path() {
+    mod user {
+        pub struct User;
+    }
+
+    struct Repository<T>(PhantomData<T>);
+
+    assert_eq!(
+        "Repository<runtime_injector::tests::short_names_trim_outer_module_path::user::User>",
+        ServiceInfo::of::<Repository<user::User>>().short_name()
+    );
+    assert_eq!(
+        "(i32, runtime_injector::tests::Svc1)",
+        ServiceInfo::of::<(i32, Svc1)>().short_name()
+    );
+    assert_eq!("Svc1", ServiceInfo::of::<Svc1>().short_name());
+
+    // Types that don't start with a path are left as they are
+    assert_eq!(
+        "(alloc::string::String, u8)",
+        ServiceInfo::of::<(String, u8)>().short_name()
+    );
+    assert_eq!(
+        "[alloc::string::String; 3]",
+        ServiceInfo::of::<[String; 3]>().short_name()
+    );
+    assert_eq!(
+        "&std::path::Path",
+        ServiceInfo::of::<&'static std::path::Path>().short_name()
+    );
+    assert_eq!(
+        "dyn Fn(runtime_injector::tests::Svc1) + core::marker::Send + core::marker::Sync",
+        ServiceInfo::of::<dyn Fn(Svc1) + Send + Sync>().short_name()
+    );
+
+    // Cycles are formatted with short names
+    let error = InjectError::CycleDetected {
+        service_info: ServiceInfo::of::<Svc1>(),
+        cycle: vec![ServiceInfo::of::<Svc1>(), ServiceInfo::of::<Svc2>()],
+    };
+    assert!(error.to_string().contains("Svc2 -> Svc1"));
+}
+
+#[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
+fn cloneable_constants_provide_owned_clones() {
+    let mut builder = Injector::builder();
+    builder.provide(constant_cloneable(String::from("foo")));
+    builder.provide(constant(5i32));
+
+    let injector = builder.build();
+    let shared: Svc<String> = injector.get().unwrap();
+    let mut owned: Box<String> = injector.get().unwrap();
+    owned.push_str("bar");
+    assert_eq!("foo", *shared);
+    assert_eq!("foobar", *owned);
+    assert_eq!(2, injector.get::<RefCount<String>>().unwrap().get());
+
+    // Constants which can't be cloned still can't provide owned services
+    match injector.get::<Box<i32>>() {
+        Err(InjectError::OwnedNotSupported { service_info })
+            if service_info == ServiceInfo::of::<i32>() => {}
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("constant should not provide owned services"),
+    }
+}
+
+#[test]
+fn extend_merges_other_builders() {
+    let mut library = Injector::builder();
+    library.provide(constant(Svc1(2)));
+    library.root_info_mut().insert_parameter("key", 2i32);
+    library.layer("extra", |layer| layer.provide(constant(Svc1(3))));
+    library.disable_layer("extra");
+    library.add_build_hook(|registry: &mut ProviderRegistry<'_>| {
+        registry.provide(Svc2::new.singleton());
+    });
+
+    let mut builder = Injector::builder();
+    builder.provide(constant(Svc1(1)));
+    builder.root_info_mut().insert_parameter("key", 1i32);
+    builder.layer("extra", |layer| layer.provide(constant(Svc1(4))));
+    builder.extend(library);
+
+    // The other builder's layer state and parameters are used
+    assert_eq!(Some(false), builder.is_layer_enabled("extra"));
+    let injector = builder.build();
+    let svc1s: Vec<Svc<Svc1>> = injector.get().unwrap();
+    let values: Vec<_> = svc1s.iter().map(|svc1| svc1.0).collect();
+    assert_eq!(vec![1, 2], values);
+
+    let request_info: RequestInfo = injector.get().unwrap();
+    let key = request_info.get_parameter("key").unwrap();
+    assert_eq!(Some(&2), key.downcast_ref::<i32>());
+    assert_eq!(1, injector.get::<Services<Svc2>>().unwrap().len());
+}
+
+#[test]
+fn lazy_clones_share_cached_value() {
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.transient());
+
+    let injector = builder.build();
+    let lazy: Lazy<Svc<Svc1>> = injector.get().unwrap();
+    let clone = lazy.clone();
+    assert!(lazy.get_cached().is_none());
+
+    let svc1 = clone.get().unwrap().clone();
+    assert!(Svc::ptr_eq(&svc1, lazy.get_cached().unwrap()));
+    assert!(Lazy::into_inner(clone).is_none());
+    assert!(Svc::ptr_eq(&svc1, &Lazy::into_inner(lazy).unwrap()));
+}
+
+#[cfg(feature = "unstable-pointer")]
+#[test]
+fn pointer_constants_work_with_either_pointer_type() {
+    use crate::{
+        ArcPointer, DefaultPointer, PointerConstantProvider, PointerService,
+        RcPointer,
+    };
+
+    let shared = PointerConstantProvider::<ArcPointer, _>::new(Svc1(1));
+    let local = PointerConstantProvider::<RcPointer, _>::new(Svc1(2));
+    assert_eq!(1, shared.get().0);
+    assert_eq!(2, local.get().0);
+
+    // Erased pointers downcast back into the service
+    let local = <Svc1 as PointerService<RcPointer>>::into_dyn(local.get());
+    let local = <Svc1 as PointerService<RcPointer>>::downcast(local)
+        .unwrap_or_else(|_| unreachable!("pointer should hold a Svc1"));
+    assert_eq!(2, local.0);
+
+    let mut builder = Injector::builder();
+    builder.provide(PointerConstantProvider::<DefaultPointer, _>::new(Svc1(3)));
+    builder.provide(Svc2::new.transient());
+
+    let injector = builder.build();
+    let svc2: Svc<Svc2> = injector.get().unwrap();
+    assert_eq!(3, svc2.dep1.0);
+}
+
+#[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
+fn services_try_all_stops_at_first_error() {
+    let activated = Svc::new(Mutex::new(Vec::new()));
+    let mut builder = Injector::builder();
+    builder.provide(constant(Svc1(1)));
+    builder.provide(
+        (|| -> Result<Svc1, std::fmt::Error> { Err(std::fmt::Error) })
+            .fallible()
+            .transient(),
+    );
+    builder.provide({
+        let activated = activated.clone();
+        (move || {
+            activated.lock().unwrap().push(3);
+            Svc1(3)
+        })
+        .transient()
+    });
+
+    let injector = builder.build();
+    let mut services: Services<Svc1> = injector.get().unwrap();
+    match services.try_all() {
+        Err(InjectError::ActivationFailed { service_info, .. })
+            if service_info == ServiceInfo::of::<Svc1>() => {}
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("the second implementation should fail"),
+    }
+    assert!(activated.lock().unwrap().is_empty());
+
+    // Constants can't provide owned services
+    match services.try_all_owned() {
+        Err(InjectError::OwnedNotSupported { service_info })
+            if service_info == ServiceInfo::of::<Svc1>() => {}
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("the constant should fail"),
+    }
+}
+
+#[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
+fn when_registered_checks_for_other_providers() {
+    let mut builder = Injector::builder();
+    builder.provide(Svc2::new.transient().when_registered::<Svc1>());
+    builder.provide(Svc3::new.transient().when_registered::<Svc2>());
+
+    let injector = builder.build();
+    let svc2: Option<Svc<Svc2>> = injector.get().unwrap();
+    assert!(svc2.is_none());
+
+    // Svc3's condition is met even though Svc2's isn't
+    match injector.get::<Svc<Svc3>>() {
+        Err(InjectError::MissingDependency {
+            dependency_info, ..
+        }) if dependency_info == ServiceInfo::of::<Svc1>() => {}
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("Svc1 is not registered"),
+    }
+
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.singleton());
+    builder.provide(Svc2::new.transient().when_registered::<Svc1>());
+
+    let injector = builder.build();
+    let svc2: Option<Svc<Svc2>> = injector.get().unwrap();
+    assert!(svc2.is_some());
+}
+
+#[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
+fn get_traced_returns_path_to_failed_service() {
+    #[allow(dead_code)]
+    struct Svc4(Svc<Svc3>);
+
+    let mut builder = Injector::builder();
+    builder.provide(Svc2::new.transient());
+    builder.provide(Svc3::new.transient());
+    builder.provide(Svc4.transient());
+
+    let injector = builder.build();
+    let (result, path) = injector.get_traced::<Svc<Svc4>>();
+    match result {
+        Err(InjectError::MissingDependency { service_info, .. })
+            if service_info == ServiceInfo::of::<Svc4>() => {}
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("Svc1 is not registered"),
+    }
+    assert_eq!(
+        vec![
+            ServiceInfo::of::<Svc4>(),
+            ServiceInfo::of::<Svc3>(),
+            ServiceInfo::of::<Svc1>(),
+        ],
+        path
+    );
+
+    // Successful requests are traced too
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.singleton());
+    builder.provide(Svc2::new.transient());
+
+    let injector = builder.build();
+    let (result, path) = injector.get_traced::<Svc<Svc2>>();
+    result.unwrap();
+    assert_eq!(
+        vec![ServiceInfo::of::<Svc2>(), ServiceInfo::of::<Svc1>()],
+        path
+    );
+}
+
+#[test]
+fn provide_default_registers_when_dropped() {
+    trait Foo: Service {}
+    impl Foo for Svc1 {}
+    interface!(dyn Foo = [Svc1]);
+
+    #[derive(Default)]
+    struct Transient;
+
+    let mut module = Module::default();
+    module.provide_default::<Svc1>().with_interface::<dyn Foo>();
+
+    let mut builder = Injector::builder();
+    builder.add_module(module);
+    builder.provide_default::<Svc1>();
+    builder.provide_default_transient::<Transient>();
+
+    let injector = builder.build();
+    let foo: Svc<dyn Foo> = injector.get().unwrap();
+    let svc1: Svc<Svc1> = injector.get().unwrap();
+    assert!(Svc::ptr_eq(&svc1, &injector.get().unwrap()));
+    assert_ne!(
+        Svc::as_ptr(&foo).cast::<()>(),
+        Svc::as_ptr(&svc1).cast::<()>()
+    );
+
+    let first: Svc<Transient> = injector.get().unwrap();
+    let second: Svc<Transient> = injector.get().unwrap();
+    assert!(!Svc::ptr_eq(&first, &second));
+}
+
+#[test]
+fn provider_metadata_does_not_activate_services() {
+    let activated = Svc::new(Mutex::new(0));
+    let mut builder = Injector::builder();
+    builder.provide({
+        let activated = activated.clone();
+        (move || {
+            *activated.lock().unwrap() += 1;
+            Svc1(1)
+        })
+        .singleton()
+        .with_name("first")
+        .with_tag("a")
+        .with_tag("b")
+    });
+    builder.provide(constant(Svc1(2)));
+    builder.provide_named("third", Svc1::default.transient());
+
+    let injector = builder.build();
+    let metadata: Vec<_> = injector
+        .provider_metadata::<Svc1>()
+        .into_iter()
+        .map(|meta| (meta.name, meta.tags, meta.lifetime))
+        .collect();
+    assert_eq!(
+        vec![
+            (
+                Some("first".to_owned()),
+                vec!["a".to_owned(), "b".to_owned()],
+                Lifetime::Singleton
+            ),
+            (None, Vec::new(), Lifetime::Constant),
+            (Some("third".to_owned()), Vec::new(), Lifetime::Transient),
+        ],
+        metadata
+    );
+    assert_eq!(0, *activated.lock().unwrap());
+    assert!(injector.provider_metadata::<Svc2>().is_empty());
+}
+
+#[test]
+fn result_requests_capture_dependency_errors() {
+    struct Logger;
+    struct Service1 {
+        has_logger: bool,
+    }
+
+    impl Service1 {
+        fn new(
+            logger: InjectResult<Svc<Logger>>,
+            svc1: InjectResult<Box<Svc1>>,
+        ) -> Self {
+            match svc1 {
+                Err(InjectError::OwnedNotSupported { service_info })
+                    if service_info == ServiceInfo::of::<Svc1>() => {}
+                Err(error) => panic!("unexpected error: {}", error),
+                Ok(_) => unreachable!("constants can't provide owned services"),
+            }
+
+            match logger {
+                Err(InjectError::ActivationFailed { service_info, .. })
+                    if service_info == ServiceInfo::of::<Logger>() =>
+                {
+                    Service1 { has_logger: false }
+                }
+                Err(error) => panic!("unexpected error: {}", error),
+                Ok(_) => Service1 { has_logger: true },
+            }
+        }
+    }
+
+    let mut builder = Injector::builder();
+    builder.provide(
+        (|| -> Result<Logger, std::fmt::Error> { Err(std::fmt::Error) })
+            .fallible()
+            .singleton(),
+    );
+    builder.provide(constant(Svc1(1)));
+    builder.provide(Service1::new.transient());
+
+    let injector = builder.build();
+    let service: Svc<Service1> = injector.get().unwrap();
+    assert!(!service.has_logger);
+}
+
+#[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
+fn map_err_only_maps_errors() {
+    let mut builder = Injector::builder();
+    builder.provide(
+        (|| -> Result<Svc1, std::fmt::Error> { Err(std::fmt::Error) })
+            .fallible()
+            .transient()
+            .map_err(|_| InjectError::InternalError("mapped".to_owned())),
+    );
+    builder.provide(Svc2::new.singleton());
+    builder.provide(Svc3::new.transient().map_err(|error| {
+        InjectError::ActivationFailed {
+            service_info: ServiceInfo::of::<Svc3>(),
+            inner: Box::new(error),
+        }
+    }));
+
+    let injector = builder.build();
+    match injector.get::<Svc<Svc1>>() {
+        Err(InjectError::InternalError(message)) if message == "mapped" => {}
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("Svc1 should fail to activate"),
+    }
+    match injector.get::<Svc<Svc3>>() {
+        Err(InjectError::ActivationFailed {
+            service_info,
+            inner,
+        }) if service_info == ServiceInfo::of::<Svc3>() => {
+            assert!(inner.downcast_ref::<InjectError>().is_some());
+        }
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("Svc3's dependencies should fail"),
+    }
+
+    let mut builder = Injector::builder();
+    builder.provide(
+        Svc1::default
+            .singleton()
+            .map_err(|_| unreachable!("Svc1 should not fail")),
+    );
+
+    let injector = builder.build();
+    let _: Svc<Svc1> = injector.get().unwrap();
+}
+
+#[test]
+fn provide_iter_registers_each_provider() {
+    let providers: Vec<Box<dyn Provider>> = vec![
+        Box::new(Svc1::default.singleton()),
+        Box::new(Svc2::new.transient()),
+        Box::new(Svc2::new.singleton()),
+    ];
+
+    let mut builder = Injector::builder();
+    builder.provide_iter(providers);
+
+    let injector = builder.build();
+    let dependencies: Vec<Svc<Svc1>> = injector.get().unwrap();
+    assert_eq!(1, dependencies.len());
+    let services: Vec<Svc<Svc2>> = injector.get().unwrap();
+    assert_eq!(2, services.len());
+    assert!(Svc::ptr_eq(&services[0].dep1, &services[1].dep1));
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+#[should_panic(expected = "point to different instances")]
+fn assert_same_instance_fails_for_transients() {
+    use crate::assert_same_instance;
+
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.transient());
+
+    let injector = builder.build();
+    let svc1: Svc<Svc1> = injector.get().unwrap();
+    assert_same_instance(&svc1, &svc1.clone());
+    assert_same_instance(&svc1, &injector.get().unwrap());
+}
+
+#[test]
+fn gated_by_checks_enabled_features() {
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.singleton().gated_by("svc1"));
+
+    let injector = builder.build();
+    let svc1: Option<Svc<Svc1>> = injector.get().unwrap();
+    assert!(svc1.is_none());
+
+    let request_info = RequestInfo::builder().feature("svc1").build();
+    let _: Svc<Svc1> = injector.get_with(&request_info).unwrap();
+
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.singleton().gated_by("svc1"));
+    builder.root_info_mut().features_mut().enable("svc1");
+
+    let injector = builder.build();
+    let _: Svc<Svc1> = injector.get().unwrap();
+}
+
+#[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
+fn memoized_by_arg_reuses_instances_per_arg() {
+    struct Tenant(Arg<u32>);
+
+    let mut builder = Injector::builder();
+    builder.provide(Tenant.memoized_by_arg::<u32>());
+
+    let injector = builder.build();
+    match injector.get::<Svc<Tenant>>() {
+        Err(InjectError::ActivationFailed { service_info, .. })
+            if service_info == ServiceInfo::of::<Arg<u32>>() => {}
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("the arg is missing"),
+    }
+
+    let tenants: Vec<Svc<Tenant>> = [1, 2, 1, 2, 3]
+        .iter()
+        .map(|&id| {
+            let mut request_info = RequestInfo::new();
+            request_info.with_arg::<Tenant, u32>(id);
+            injector.get_with(&request_info).unwrap()
+        })
+        .collect();
+    assert!(Svc::ptr_eq(&tenants[0], &tenants[2]));
+    assert!(Svc::ptr_eq(&tenants[1], &tenants[3]));
+    assert!(!Svc::ptr_eq(&tenants[0], &tenants[1]));
+    assert_eq!(3, *tenants[4].0);
+}
+
+#[test]
+fn request_info_exposes_parent_and_root_services() {
+    struct Requesters {
+        parent: Option<ServiceInfo>,
+        root: Option<ServiceInfo>,
+    }
+
+    impl Request for Requesters {
+        fn request(
+            _injector: &Injector,
+            info: &RequestInfo,
+        ) -> InjectResult<Self> {
+            Ok(Requesters {
+                parent: info.parent_service(),
+                root: info.root_service(),
+            })
+        }
+    }
+
+    struct Leaf(Requesters);
+    struct Root(Svc<Leaf>);
+
+    let mut builder = Injector::builder();
+    builder.provide(Root.transient());
+    builder.provide(Leaf.transient());
+
+    let injector = builder.build();
+    let root: Svc<Root> = injector.get().unwrap();
+    assert_eq!(Some(ServiceInfo::of::<Leaf>()), root.0 .0.parent);
+    assert_eq!(Some(ServiceInfo::of::<Root>()), root.0 .0.root);
+
+    let leaf: Svc<Leaf> = injector.get().unwrap();
+    assert_eq!(Some(ServiceInfo::of::<Leaf>()), leaf.0.parent);
+    assert_eq!(Some(ServiceInfo::of::<Leaf>()), leaf.0.root);
+
+    let requesters: Requesters = injector.get().unwrap();
+    assert_eq!(None, requesters.parent);
+    assert_eq!(None, requesters.root);
+}
+
+#[test]
+fn inject_error_predicates_match_variants() {
+    let mut builder = Injector::builder();
+    builder.provide(Svc2::new.transient());
+    builder.provide(Svc1::default.singleton().with_condition(|_, _| false));
+
+    let injector = builder.build();
+    let error = injector.get::<Svc<Svc3>>().err().unwrap();
+    assert!(error.is_missing());
+    assert!(error.is_missing_provider());
+    assert!(!error.is_cycle());
+    assert!(error.as_activation_source().is_none());
+
+    let error = injector.get::<Svc<Svc2>>().err().unwrap();
+    assert!(error.is_missing());
+    assert!(!error.is_missing_provider());
+    assert!(!error.is_activation_failed());
+    assert!(!error.is_conditions_not_met());
+}
+
+#[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
+fn with_priority_selects_highest_priority_provider() {
+    let mut builder = Injector::builder();
+    builder.provide(constant(Svc1(1)));
+    builder.provide(constant(Svc1(2)).with_priority(5));
+    builder.provide(constant(Svc1(3)).with_priority(-1));
+
+    let injector = builder.build();
+    let svc1: Svc<Svc1> = injector.get().unwrap();
+    assert_eq!(2, svc1.0);
+    let svc1 = injector.get_dyn(ServiceInfo::of::<Svc1>()).unwrap();
+    assert_eq!(2, svc1.downcast::<Svc1>().unwrap().0);
+
+    let values: Vec<_> = injector
+        .get::<Vec<Svc<Svc1>>>()
+        .unwrap()
+        .iter()
+        .map(|svc1| svc1.0)
+        .collect();
+    assert_eq!(vec![2, 1, 3], values);
+
+    let mut builder = Injector::builder();
+    builder.provide(constant(Svc1(1)).with_priority(5));
+    builder.provide(constant(Svc1(2)));
+    builder.provide(constant(Svc1(3)).with_priority(5));
+
+    let injector = builder.build();
+    match injector.get::<Svc<Svc1>>() {
+        Err(InjectError::MultipleProviders {
+            service_info,
+            providers: 2,
+        }) if service_info == ServiceInfo::of::<Svc1>() => {}
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("two providers share the highest priority"),
+    }
+
+    let scope = injector.create_scope().unwrap();
+    let values: Vec<_> = scope
+        .get::<Vec<Svc<Svc1>>>()
+        .unwrap()
+        .iter()
+        .map(|svc1| svc1.0)
+        .collect();
+    assert_eq!(vec![1, 3, 2], values);
+}
+
+#[test]
+fn define_module_includes_other_modules() {
+    let mut base = Module::default();
+    base.provide(constant(Svc1(1)));
+    base.insert_parameter("key", 1i32);
+
+    let mut other = Module::default();
+    other.provide(constant(Svc1(2)));
+    other.insert_parameter("key", 2i32);
+
+    let module = crate::define_module! {
+        include = [base, other],
+        services = [constant(Svc1(3))],
+    };
+
+    let mut builder = Injector::builder();
+    builder.add_module(module);
+
+    // Providers are appended and later parameters override earlier ones
+    let injector = builder.build();
+    let svc1s: Vec<Svc<Svc1>> = injector.get().unwrap();
+    let values: Vec<_> = svc1s.iter().map(|svc1| svc1.0).collect();
+    assert_eq!(vec![1, 2, 3], values);
+
+    let request_info: RequestInfo = injector.get().unwrap();
+    let key = request_info.get_parameter("key").unwrap();
+    assert_eq!(Some(&2), key.downcast_ref::<i32>());
+}
+
+#[test]
+fn provide_null_is_overridden_by_other_implementations() {
+    trait Sink: Service {
+        fn value(&self) -> i32;
+    }
+
+    interface!(dyn Sink = [Svc1]);
+
+    impl Sink for Svc1 {
+        fn value(&self) -> i32 {
+            self.0
+        }
+    }
+
+    let mut builder = Injector::builder();
+    builder.provide_null::<dyn Sink, _>(Svc1(0));
+    let injector = builder.build();
+    assert_eq!(0, injector.get::<Svc<dyn Sink>>().unwrap().value());
+
+    let mut builder = Injector::builder();
+    builder.provide_null::<dyn Sink, _>(Svc1(0));
+    builder.provide(constant(Svc1(1)).with_interface::<dyn Sink>());
+    let injector = builder.build();
+    assert_eq!(1, injector.get::<Svc<dyn Sink>>().unwrap().value());
+
+    // The null implementation is still included with all implementations
+    let sinks: Vec<Svc<dyn Sink>> = injector.get().unwrap();
+    let values: Vec<_> = sinks.iter().map(|sink| sink.value()).collect();
+    assert_eq!(vec![1, 0], values);
+}
+
+#[test]
+fn singleton_build_times_are_opt_in() {
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.singleton());
+    builder.provide(Svc2::new.singleton());
+    builder.provide(Svc3::new.transient());
+    let injector = builder.build();
+    let _svc3: Svc<Svc3> = injector.get().unwrap();
+    assert!(injector.singleton_build_times().is_empty());
+
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.singleton());
+    builder.provide(Svc2::new.singleton());
+    builder.provide(Svc3::new.transient());
+    builder.time_singletons();
+    let injector = builder.build();
+    let _svc1: Svc<Svc1> = injector.get().unwrap();
+
+    // Only activated singletons are timed, including from scopes
+    let build_times = injector.singleton_build_times();
+    assert_eq!(1, build_times.len());
+    assert!(build_times.contains_key(&ServiceInfo::of::<Svc1>()));
+
+    let scope = injector.create_scope().unwrap();
+    let _svc3: Svc<Svc3> = scope.get().unwrap();
+    let build_times = injector.singleton_build_times();
+    assert_eq!(2, build_times.len());
+    assert!(build_times.contains_key(&ServiceInfo::of::<Svc2>()));
+}
+
+#[test]
+fn inspect_sees_each_provided_service() {
+    let created = Svc::new(Mutex::new(Vec::new()));
+    let mut builder = Injector::builder();
+    builder.provide({
+        let created = created.clone();
+        Svc1::default
+            .singleton()
+            .inspect(move |svc1, request_info| {
+                created
+                    .lock()
+                    .unwrap()
+                    .push((svc1.0, request_info.service_path().len()));
+            })
+    });
+    builder.provide(Svc2::new.transient());
+
+    let injector = builder.build();
+    let _svc1: Svc<Svc1> = injector.get().unwrap();
+    let _svc2: Svc<Svc2> = injector.get().unwrap();
+
+    // The singleton is inspected for each request, including as a dependency
+    assert_eq!(vec![(0, 0), (0, 1)], *created.lock().unwrap());
+}
+
+#[test]
+fn inject_creates_values_from_several_requests() {
+    struct Handler {
+        svc2: Svc<Svc2>,
+        svc3: Option<Svc<Svc3>>,
+        path_len: usize,
+    }
+
+    impl Inject for Handler {
+        fn inject(
+            injector: &Injector,
+            info: &RequestInfo,
+        ) -> InjectResult<Self> {
+            Ok(Handler {
+                svc2: Request::request(injector, info)?,
+                svc3: Request::request(injector, info)?,
+                path_len: info.service_path().len(),
+            })
+        }
+    }
+
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.singleton());
+    builder.provide(Svc2::new.singleton());
+
+    let injector = builder.build();
+    let handler: Handler = injector.inject().unwrap();
+    assert!(handler.svc3.is_none());
+    assert_eq!(0, handler.path_len);
+
+    // Requests are injected the same way they are requested
+    let svc2: Svc<Svc2> = injector.inject().unwrap();
+    assert!(Svc::ptr_eq(&handler.svc2, &svc2));
+    assert!(injector.inject::<Svc<Svc3>>().is_err());
+}
+
+#[test]
+fn btree_map_keys_implementations_by_type() {
+    trait Foo: Service {}
+    interface!(dyn Foo = [Svc1, Svc2]);
+    impl Foo for Svc1 {}
+    impl Foo for Svc2 {}
+
+    let mut builder = Injector::builder();
+    builder.provide(Svc2::new.singleton().with_interface::<dyn Foo>());
+    builder.provide(Svc1::default.singleton().with_interface::<dyn Foo>());
+    builder.provide(Svc1::default.singleton());
+
+    let injector = builder.build();
+    let impls: BTreeMap<ServiceInfo, Svc<dyn Foo>> = injector.get().unwrap();
+    let keys: Vec<_> = impls.keys().copied().collect();
+    assert_eq!(
+        vec![ServiceInfo::of::<Svc1>(), ServiceInfo::of::<Svc2>()],
+        keys
+    );
+    assert!(ServiceInfo::of::<Svc1>() < ServiceInfo::of::<Svc2>());
+}
+
+#[test]
+fn lazy_releases_injector_once_cached() {
+    struct Holder {
+        lazy: Lazy<Svc<Svc1>>,
+        dropped: Svc<Mutex<bool>>,
+    }
+
+    impl Drop for Holder {
+        fn drop(&mut self) {
+            *self.dropped.lock().unwrap() = true;
+        }
+    }
+
+    let mut builder = Injector::builder();
+    builder.provide(constant(Mutex::new(false)));
+    builder.provide(Svc1::default.singleton());
+    builder.provide((|lazy, dropped| Holder { lazy, dropped }).singleton());
+
+    let injector = builder.build();
+    let dropped: Svc<Mutex<bool>> = injector.get().unwrap();
+    let holder: Svc<Holder> = injector.get().unwrap();
+    holder.lazy.get().unwrap();
+    drop(holder);
+    drop(injector);
+
+    // The singleton no longer keeps the injector alive through its lazy
+    assert!(*dropped.lock().unwrap());
+}
+
+#[test]
+fn deferred_weak_svc_releases_injector() {
+    struct EventBus {
+        registry: WeakSvc<Registry>,
+        dropped: Svc<Mutex<bool>>,
+    }
+
+    impl Drop for EventBus {
+        fn drop(&mut self) {
+            *self.dropped.lock().unwrap() = true;
+        }
+    }
+
+    struct Registry(Svc<EventBus>);
+
+    let mut builder = Injector::builder();
+    builder.provide(constant(Mutex::new(false)));
+    builder.provide(
+        (|registry, dropped| EventBus { registry, dropped }).singleton(),
+    );
+    builder.provide(Registry.singleton());
+
+    let injector = builder.build();
+    let dropped: Svc<Mutex<bool>> = injector.get().unwrap();
+    let bus: Svc<EventBus> = injector.get().unwrap();
+    let registry = bus.registry.try_upgrade().unwrap().unwrap();
+    assert!(Svc::ptr_eq(&bus, &registry.0));
+
+    // Once resolved, the cycle no longer keeps the injector alive
+    drop((bus, registry, injector));
+    assert!(*dropped.lock().unwrap());
+}
+
+#[test]
+fn child_scopes_own_parent_scoped_services() {
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.scoped());
+    let parent = builder.build();
+    let parent_svc1: Svc<Svc1> = parent.get().unwrap();
+
+    let child = parent.child().unwrap().build();
+    let scope1 = child.create_scope().unwrap();
+    let scope2 = child.create_scope().unwrap();
+    let svc1_a: Svc<Svc1> = scope1.get().unwrap();
+    let svc1_b: Svc<Svc1> = scope1.get().unwrap();
+    let svc1_c: Svc<Svc1> = scope2.get().unwrap();
+
+    assert!(Svc::ptr_eq(&svc1_a, &svc1_b));
+    assert!(!Svc::ptr_eq(&svc1_a, &svc1_c));
+    assert!(!Svc::ptr_eq(&svc1_a, &parent_svc1));
+    assert!(!Svc::ptr_eq(&svc1_c, &parent_svc1));
+}
+
+#[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
+fn weak_svc_reports_cycles_outside_request_path() {
+    #[allow(dead_code)]
+    struct Foo(Svc<Bar>);
+    #[allow(dead_code)]
+    struct Bar(Svc<Foo>);
+
+    let mut builder = Injector::builder();
+    builder.provide(Foo.singleton());
+    builder.provide(Bar.singleton());
+
+    // Nothing on the request path is part of the cycle, so it isn't deferred
+    let injector = builder.build();
+    match injector.get::<WeakSvc<Foo>>() {
+        Err(InjectError::CycleDetected { .. }) => {}
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("the cycle should have been reported"),
+    }
+}
+
+#[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
+fn weak_svc_requires_stored_instance() {
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.singleton());
+    builder.provide(Svc2::new.transient());
+
+    let injector = builder.build();
+    let weak: WeakSvc<Svc1> = injector.get().unwrap();
+    let svc1: Svc<Svc1> = injector.get().unwrap();
+    assert!(Svc::ptr_eq(&svc1, &weak.upgrade().unwrap()));
+
+    match injector.get::<WeakSvc<Svc2>>() {
+        Err(InjectError::WeakNotSupported { service_info })
+            if service_info == ServiceInfo::of::<Svc2>() => {}
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("transient services can't be weakly referenced"),
+    }
+}
+
+#[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
+fn named_providers_are_only_used_by_name() {
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.singleton());
+    builder.provide_named("a", (|| Svc1(1)).singleton());
+    builder.provide_named("b", (|| Svc1(2)).transient());
+    builder.provide_named("b", Svc2::new.transient());
+
+    let injector = builder.build();
+    let svc1: Svc<Svc1> = injector.get().unwrap();
+    assert_eq!(0, svc1.0);
+    let svc1: Svc<Svc1> = injector.get_named("a").unwrap();
+    assert_eq!(1, svc1.0);
+
+    // Dependencies of named services are resolved normally
+    let svc2: Svc<Svc2> = injector.get_named("b").unwrap();
+    assert_eq!(0, svc2.dep1.0);
+
+    match injector.get_named::<Svc<Svc2>>("a") {
+        Err(InjectError::MissingProvider { service_info })
+            if service_info == ServiceInfo::of::<Svc2>() => {}
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("no provider is registered under this name"),
+    }
+}
+
+#[test]
+fn validate_reports_each_missing_dependency() {
+    let mut builder = Injector::builder();
+    builder.provide(Svc3::new.singleton());
+    builder.provide(
+        (|_: Option<Svc<Svc1>>, _: Services<Svc1>| Svc1(0)).transient(),
+    );
+
+    let errors = builder.validate().unwrap_err();
+    let missing: Vec<_> = errors
+        .iter()
+        .map(|error| match error {
+            InjectError::MissingDependency {
+                service_info,
+                dependency_info,
+                ..
+            } => (*service_info, *dependency_info),
+            error => unreachable!("unexpected error: {}", error),
+        })
+        .collect();
+    assert_eq!(
+        vec![(ServiceInfo::of::<Svc3>(), ServiceInfo::of::<Svc2>())],
+        missing
+    );
+
+    builder.provide(Svc2::new.transient());
+    assert!(builder.validate().is_ok());
+}
+
+#[test]
+fn export_dot_links_interfaces_to_implementations() {
+    trait Foo: Service {}
+    impl Foo for Svc2 {}
+    interface!(dyn Foo = [Svc2]);
+
+    let mut builder = Injector::builder();
+    builder.provide(constant(Svc1(0)));
+    builder.provide(Svc2::new.singleton().with_interface::<dyn Foo>());
+
+    let injector = builder.build();
+    let dot = injector.export_dot();
+    let edge = |from: ServiceInfo, to: ServiceInfo| {
+        format!("{:?} -> {:?};", from.name(), to.name())
+    };
+    assert!(dot.contains(&edge(
+        ServiceInfo::of::<dyn Foo>(),
+        ServiceInfo::of::<Svc2>()
+    )));
+    assert!(dot
+        .contains(&edge(ServiceInfo::of::<Svc2>(), ServiceInfo::of::<Svc1>())));
+    assert!(dot.contains(&format!(
+        "{:?}",
+        format!("{}\n(singleton)", ServiceInfo::of::<Svc2>().name())
+    )));
+    assert!(dot.contains(&format!(
+        "{:?}",
+        format!("{}\n(constant)", ServiceInfo::of::<Svc1>().name())
+    )));
+}
+
+#[test]
+fn override_provider_replaces_aliases() {
+    trait Foo: Service {}
+    impl Foo for Svc1 {}
+    interface!(dyn Foo = [Svc1]);
+
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.singleton());
+    builder.provide(Svc1::default.singleton().with_interface::<dyn Foo>());
+    builder.provide(Svc1::default.transient().with_interface::<dyn Foo>());
+
+    let replaced =
+        builder.override_provider(constant(Svc1(1)).also_as::<dyn Foo>());
+    assert_eq!(3, replaced);
+
+    let injector = builder.build();
+    let svc1: Svc<Svc1> = injector.get().unwrap();
+    let foos: Services<dyn Foo> = injector.get().unwrap();
+    assert_eq!(1, svc1.0);
+    assert_eq!(1, foos.len());
+}
+
+#[test]
+fn decorators_compose_and_run_once_for_singletons() {
+    let calls = Svc::new(Mutex::new(0));
+
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.singleton());
+    builder.provide(Svc2::new.transient());
+    builder.decorate(|svc1: Svc<Svc1>, _: &_, _: &_| {
+        Ok(Svc::new(Svc1(svc1.0 + 1)))
+    });
+    builder.decorate(|svc1: Svc<Svc1>, _: &_, _: &_| {
+        Ok(Svc::new(Svc1(svc1.0 * 10)))
+    });
+    builder.decorate({
+        let calls = calls.clone();
+        move |svc2: Svc<Svc2>, _: &_, _: &_| {
+            *calls.lock().unwrap() += 1;
+            Ok(svc2)
+        }
+    });
+
+    let injector = builder.build();
+    let first: Svc<Svc1> = injector.get().unwrap();
+    let all: Vec<Svc<Svc1>> = injector.get().unwrap();
+    assert_eq!(10, first.0);
+    assert!(Svc::ptr_eq(&first, &all[0]));
+
+    let svc2: Svc<Svc2> = injector.get().unwrap();
+    let mut services: Services<Svc2> = injector.get().unwrap();
+    assert_eq!(1, services.get_all().count());
+    assert!(Svc::ptr_eq(&first, &svc2.dep1));
+    assert_eq!(2, *calls.lock().unwrap());
+}
+
+#[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
+fn named_maps_include_named_registrations() {
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.transient());
+    builder.provide((|| Svc1(1)).transient().with_name("a"));
+    builder.provide_named("b", (|| Svc1(2)).transient());
+
+    let injector = builder.build();
+    let svcs: HashMap<String, Box<Svc1>> = injector.get().unwrap();
+    assert_eq!(2, svcs.len());
+    assert_eq!(1, svcs["a"].0);
+    assert_eq!(2, svcs["b"].0);
+
+    let mut builder = Injector::builder();
+    builder.provide((|| Svc1(1)).transient().with_name("a"));
+    builder.provide_named("a", (|| Svc1(2)).transient());
+
+    let injector = builder.build();
+    match injector.get::<HashMap<String, Svc<Svc1>>>() {
+        Err(InjectError::DuplicateName { name, .. }) if name == "a" => {}
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("the name was used twice"),
+    }
+}
+
+#[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
+fn get_or_only_defaults_missing_providers() {
+    let mut builder = Injector::builder();
+    builder.provide(Svc1::default.transient());
+    builder.provide(Svc1::default.transient());
+
+    let injector = builder.build();
+    let svc2: Svc<Svc2> = injector
+        .get_or(|| Svc::new(Svc2::new(Svc::new(Svc1(1)))))
+        .unwrap();
+    assert_eq!(1, svc2.dep1.0);
+
+    match injector.get_or::<Svc<Svc1>>(|| unreachable!("not missing")) {
+        Err(InjectError::MultipleProviders { providers: 2, .. }) => {}
+        Err(error) => Err(error).unwrap(),
+        Ok(_) => unreachable!("there are multiple providers"),
+    }
+}
@@ -13,6 +13,8 @@
 
 pub use runtime_injector::*;
 
+mod middleware;
 mod service;
 
+pub use middleware::*;
 pub use service::*;
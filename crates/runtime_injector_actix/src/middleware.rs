@@ -0,0 +1,200 @@
+use crate::{request_scope, InjectedConfig};
+use actix_web::{
+    dev::{
+        BodySize, MessageBody, ResponseBody, Service, ServiceRequest,
+        ServiceResponse, Transform,
+    },
+    error::ErrorInternalServerError,
+    web::Bytes,
+};
+use futures_util::future::{ok, FutureExt, LocalBoxFuture, Ready};
+use runtime_injector::Injector;
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Middleware which creates the scope for each HTTP request before the
+/// request is handled, and keeps it alive until the response has finished
+/// streaming. Without this middleware, the scope for a request is created
+/// the first time a service is injected into a handler for it, and it is
+/// dropped along with the request, which may happen before a streaming
+/// response body has been fully sent. See [`Injected<R>`] for more
+/// information about request scopes.
+///
+/// Scoped services are dropped once the scope is dropped, which happens
+/// after the response body has been sent or dropped. This means that scoped
+/// services can safely be used by a streaming response body.
+///
+/// Middleware in actix-web runs in the reverse order it was registered in,
+/// so the scope is available to the handler and to any middleware which was
+/// registered with [`App::wrap()`] before this middleware. Middleware which
+/// was registered after it runs first, and can't use the scope. The scope
+/// outlives every response body produced by middleware registered before
+/// it.
+///
+/// If the scope can't be created, then the request fails without reaching
+/// the handler. Errors are handled the same way as by [`Injected<R>`], and
+/// can be configured by adding an [`InjectedConfig`] to the app data.
+///
+/// [`Injected<R>`]: crate::Injected
+/// [`App::wrap()`]: actix_web::App::wrap
+///
+/// ## Example
+///
+/// ```
+/// use actix_web::{test, web, App, HttpResponse};
+/// use runtime_injector_actix::{
+///     Injected, Injector, InjectorScope, IntoScoped, Svc,
+/// };
+/// use std::sync::{
+///     atomic::{AtomicUsize, Ordering},
+///     Arc,
+/// };
+///
+/// struct RequestContext(Arc<AtomicUsize>);
+///
+/// impl Drop for RequestContext {
+///     fn drop(&mut self) {
+///         self.0.fetch_add(1, Ordering::Relaxed);
+///     }
+/// }
+///
+/// actix_web::rt::System::new("example").block_on(async {
+///     let dropped = Arc::new(AtomicUsize::new(0));
+///     let mut builder = Injector::builder();
+///     builder.provide({
+///         let dropped = dropped.clone();
+///         (move || RequestContext(dropped.clone())).scoped()
+///     });
+///
+///     let injector = builder.build();
+///     let mut app = test::init_service(
+///         App::new().app_data(injector).wrap(InjectorScope).route(
+///             "/",
+///             web::get().to(|_: Injected<Svc<RequestContext>>| async {
+///                 HttpResponse::Ok().body("done")
+///             }),
+///         ),
+///     )
+///     .await;
+///
+///     let request = test::TestRequest::get().uri("/").to_request();
+///     let response = test::call_service(&mut app, request).await;
+///     assert_eq!(0, dropped.load(Ordering::Relaxed));
+///
+///     // The scope is dropped once the response has been sent
+///     let body = test::read_body(response).await;
+///     assert_eq!("done", body);
+///     assert_eq!(1, dropped.load(Ordering::Relaxed));
+/// });
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct InjectorScope;
+
+impl<S, B> Transform<S> for InjectorScope
+where
+    S: Service<
+        Request = ServiceRequest,
+        Response = ServiceResponse<B>,
+        Error = actix_web::Error,
+    >,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<ScopedBody<B>>;
+    type Error = actix_web::Error;
+    type Transform = InjectorScopeMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(InjectorScopeMiddleware { service })
+    }
+}
+
+/// The service created by the [`InjectorScope`] middleware.
+pub struct InjectorScopeMiddleware<S> {
+    service: S,
+}
+
+impl<S, B> Service for InjectorScopeMiddleware<S>
+where
+    S: Service<
+        Request = ServiceRequest,
+        Response = ServiceResponse<B>,
+        Error = actix_web::Error,
+    >,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<ScopedBody<B>>;
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, actix_web::Result<Self::Response>>;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: ServiceRequest) -> Self::Future {
+        let scope = if let Some(injector) = req.app_data::<Injector>() {
+            request_scope(&req, injector)
+        } else {
+            let error =
+                ErrorInternalServerError("no injector is present in app_data");
+            let (req, _payload) = req.into_parts();
+            return ok(ServiceResponse::from_err(error, req)).boxed_local();
+        };
+
+        let scope = match scope {
+            Ok(scope) => scope,
+            Err(error) => {
+                let (req, _payload) = req.into_parts();
+                let error = match req.app_data::<InjectedConfig>() {
+                    Some(config) => config.handle_error(error, &req),
+                    None => ErrorInternalServerError(error),
+                };
+                return ok(ServiceResponse::from_err(error, req)).boxed_local();
+            }
+        };
+
+        self.service
+            .call(req)
+            .map(move |response| {
+                response.map(|response| {
+                    response.map_body(move |_head, body| {
+                        ResponseBody::Body(ScopedBody {
+                            body: Box::pin(body),
+                            _scope: scope,
+                        })
+                    })
+                })
+            })
+            .boxed_local()
+    }
+}
+
+/// A response body which keeps the scope for its request alive until it has
+/// been dropped. See [`InjectorScope`] for more information.
+pub struct ScopedBody<B> {
+    body: Pin<Box<ResponseBody<B>>>,
+    _scope: Injector,
+}
+
+impl<B: MessageBody> MessageBody for ScopedBody<B> {
+    fn size(&self) -> BodySize {
+        self.body.size()
+    }
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<actix_web::Result<Bytes>>> {
+        self.body.as_mut().poll_next(cx)
+    }
+}
@@ -1,14 +1,26 @@
 use actix_web::{
-    dev::Payload, error::ErrorInternalServerError, FromRequest, HttpRequest,
+    dev::Payload, error::ErrorInternalServerError, FromRequest, HttpMessage,
+    HttpRequest,
 };
 use futures_util::future::{err, ok, Ready};
-use runtime_injector::{Injector, Request};
-use std::ops::Deref;
+use runtime_injector::{InjectError, InjectResult, Injector, Request};
+use std::{ops::Deref, sync::Arc};
 
 /// An injected request. Any request to the [`Injector`] can be injected by
 /// wrapping it in this type and providing it as a parameter to your request
 /// handler.
 ///
+/// The root injector must be added to the app data. Each HTTP request gets
+/// its own scope, created from the root injector the first time a service is
+/// injected into a handler for that request. Every injected request made for
+/// the same HTTP request shares that scope, so scoped services are shared
+/// within a request but not between requests. See
+/// [`Injector::create_scope()`] for more information.
+///
+/// If the request to the injector fails, then the handler responds with a
+/// `500 Internal Server Error` by default. This can be configured by adding an
+/// [`InjectedConfig`] to the app data.
+///
 /// ## Example
 ///
 /// ```no_run
@@ -59,7 +71,7 @@ impl<R: Request> Deref for Injected<R> {
 impl<R: Request> FromRequest for Injected<R> {
     type Error = actix_web::Error;
     type Future = Ready<actix_web::Result<Self>>;
-    type Config = ();
+    type Config = InjectedConfig;
 
     fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
         let injector: &Injector = match req.app_data() {
@@ -71,11 +83,85 @@ impl<R: Request> FromRequest for Injected<R> {
             }
         };
 
-        let inner = match injector.get() {
-            Ok(inner) => inner,
-            Err(error) => return err(ErrorInternalServerError(error)),
-        };
+        let inner =
+            match request_scope(req, injector).and_then(|scope| scope.get()) {
+                Ok(inner) => inner,
+                Err(error) => {
+                    let error = match req.app_data::<Self::Config>() {
+                        Some(config) => config.handle_error(error, req),
+                        None => ErrorInternalServerError(error),
+                    };
+                    return err(error);
+                }
+            };
 
         ok(Injected(inner))
     }
 }
+
+/// The scope created for an HTTP request.
+struct RequestScope(Injector);
+
+/// Gets the scope for an HTTP request, creating it if needed.
+pub(crate) fn request_scope<M: HttpMessage>(
+    req: &M,
+    injector: &Injector,
+) -> InjectResult<Injector> {
+    if let Some(RequestScope(scope)) = req.extensions().get() {
+        return Ok(scope.clone());
+    }
+
+    let scope = injector.create_scope()?;
+    req.extensions_mut().insert(RequestScope(scope.clone()));
+    Ok(scope)
+}
+
+type ErrorHandler =
+    Arc<dyn Fn(InjectError, &HttpRequest) -> actix_web::Error + Send + Sync>;
+
+/// Configuration for the [`Injected<R>`] extractor. Add this to the app data
+/// to configure how failed requests to the injector are handled.
+///
+/// ## Example
+///
+/// ```no_run
+/// use actix_web::{error::ErrorServiceUnavailable, App};
+/// use runtime_injector_actix::{InjectedConfig, Injector};
+///
+/// let injector = Injector::builder().build();
+/// let app = App::new().app_data(injector).app_data(
+///     InjectedConfig::default()
+///         .error_handler(|error, _| ErrorServiceUnavailable(error)),
+/// );
+/// ```
+#[derive(Clone, Default)]
+pub struct InjectedConfig {
+    error_handler: Option<ErrorHandler>,
+}
+
+impl InjectedConfig {
+    /// Sets the handler which converts errors returned by the injector into
+    /// errors for actix-web to respond with.
+    #[must_use]
+    pub fn error_handler<F>(mut self, error_handler: F) -> Self
+    where
+        F: Fn(InjectError, &HttpRequest) -> actix_web::Error
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.error_handler = Some(Arc::new(error_handler));
+        self
+    }
+
+    pub(crate) fn handle_error(
+        &self,
+        error: InjectError,
+        req: &HttpRequest,
+    ) -> actix_web::Error {
+        match &self.error_handler {
+            Some(error_handler) => error_handler(error, req),
+            None => ErrorInternalServerError(error),
+        }
+    }
+}
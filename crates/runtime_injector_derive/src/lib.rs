@@ -0,0 +1,239 @@
+//! Derive macros for [runtime_injector](https://docs.rs/runtime_injector).
+//!
+//! These are re-exported by runtime_injector when its "derive" feature is
+//! enabled, so most applications won't need to depend on this crate
+//! directly.
+
+#![forbid(unsafe_code)]
+#![deny(clippy::all, clippy::pedantic)]
+#![warn(missing_docs)]
+#![allow(clippy::module_name_repetitions, clippy::doc_markdown)]
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+    parse_macro_input, spanned::Spanned, Attribute, Data, DeriveInput, Field,
+    Fields, Type,
+};
+
+/// Generates a constructor for a struct which requests each of its fields
+/// from the injector.
+///
+/// The generated associated function `injector()` returns a service factory
+/// which can be registered like any other constructor, for example with
+/// `Foo::injector().singleton()`. Each field's type must be a valid
+/// [`Request`](https://docs.rs/runtime_injector/*/runtime_injector/trait.Request.html).
+///
+/// ## Attributes
+///
+/// * `#[inject(singleton)]`, `#[inject(transient)]` - On the struct, also
+///   generates an associated function `provider()` which returns the
+///   factory wrapped in a provider with that lifetime.
+/// * `#[inject(arg)]` - On a field, requests the field as an
+///   [`Arg<T>`](https://docs.rs/runtime_injector/*/runtime_injector/struct.Arg.html).
+///   If the field's type is not already an `Arg<T>`, the value is unwrapped
+///   before being stored in the field.
+///
+/// ## Example
+///
+/// ```
+/// use runtime_injector::{Injector, IntoSingleton, Svc, WithArg};
+/// use runtime_injector_derive::Inject;
+///
+/// #[derive(Default)]
+/// struct Database;
+///
+/// #[derive(Inject)]
+/// #[inject(singleton)]
+/// struct Server {
+///     database: Svc<Database>,
+///     #[inject(arg)]
+///     port: u16,
+/// }
+///
+/// let mut builder = Injector::builder();
+/// builder.provide(Database::default.singleton());
+/// builder.provide(Server::provider());
+/// builder.with_arg::<Server, u16>(8080);
+///
+/// let injector = builder.build();
+/// let server: Svc<Server> = injector.get().unwrap();
+/// assert_eq!(8080, server.port);
+/// ```
+#[proc_macro_derive(Inject, attributes(inject))]
+pub fn derive_inject(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_inject(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// The lifetime requested for a derived provider.
+enum Lifetime {
+    Singleton,
+    Transient,
+}
+
+fn expand_inject(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        Data::Enum(data) => {
+            return Err(syn::Error::new(
+                data.enum_token.span(),
+                "Inject can only be derived for structs",
+            ))
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new(
+                data.union_token.span(),
+                "Inject can only be derived for structs",
+            ))
+        }
+    };
+
+    let lifetime = parse_lifetime(&input.attrs)?;
+
+    let mut request_types = Vec::new();
+    let mut bindings = Vec::new();
+    let mut values = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let binding = format_ident!("__dependency{}", index);
+        let (request_type, value) = if parse_is_arg(field)? {
+            if is_arg_type(&field.ty) {
+                (field.ty.clone(), quote!(#binding))
+            } else {
+                let inner = &field.ty;
+                (
+                    syn::parse_quote!(::runtime_injector::Arg<#inner>),
+                    quote!(::runtime_injector::Arg::into_inner(#binding)),
+                )
+            }
+        } else {
+            (field.ty.clone(), quote!(#binding))
+        };
+
+        request_types.push(request_type);
+        bindings.push(binding);
+        values.push(value);
+    }
+
+    // Checking each request type separately points the compiler error at the
+    // field which can't be requested rather than at the whole factory
+    let assertions = fields.iter().zip(&request_types).map(|(field, ty)| {
+        quote_spanned! {field.ty.span()=>
+            assert_request::<#ty>();
+        }
+    });
+
+    let construct = match fields {
+        Fields::Named(_) => {
+            let names = fields.iter().map(|field| &field.ident);
+            quote!(Self { #(#names: #values),* })
+        }
+        Fields::Unnamed(_) => quote!(Self(#(#values),*)),
+        Fields::Unit => quote!(Self),
+    };
+
+    let factory_type = quote!(fn(#(#request_types),*) -> Self);
+    let provider = lifetime.map(|lifetime| {
+        let (provider_type, method) = match lifetime {
+            Lifetime::Singleton => (
+                quote!(::runtime_injector::SingletonProvider),
+                quote!(::runtime_injector::IntoSingleton::singleton),
+            ),
+            Lifetime::Transient => (
+                quote!(::runtime_injector::TransientProvider),
+                quote!(::runtime_injector::IntoTransient::transient),
+            ),
+        };
+
+        quote! {
+            /// Creates a provider for this service using the constructor
+            /// generated by `#[derive(Inject)]`.
+            #[allow(clippy::type_complexity)]
+            pub fn provider() -> #provider_type<
+                (#(#request_types,)*),
+                Self,
+                #factory_type,
+            > {
+                #method(Self::injector())
+            }
+        }
+    });
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) =
+        input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Creates a service factory which requests each field of this
+            /// type from the injector.
+            #[allow(clippy::type_complexity)]
+            pub fn injector() -> #factory_type {
+                fn assert_request<R: ::runtime_injector::Request>() {}
+                #(#assertions)*
+
+                |#(#bindings),*| #construct
+            }
+
+            #provider
+        }
+    })
+}
+
+fn parse_lifetime(attrs: &[Attribute]) -> syn::Result<Option<Lifetime>> {
+    let mut lifetime = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("inject")) {
+        attr.parse_nested_meta(|meta| {
+            let parsed = if meta.path.is_ident("singleton") {
+                Lifetime::Singleton
+            } else if meta.path.is_ident("transient") {
+                Lifetime::Transient
+            } else {
+                return Err(meta.error(
+                    "expected `singleton` or `transient` on the struct",
+                ));
+            };
+
+            if lifetime.replace(parsed).is_some() {
+                return Err(meta.error("lifetime is already specified"));
+            }
+
+            Ok(())
+        })?;
+    }
+
+    Ok(lifetime)
+}
+
+fn parse_is_arg(field: &Field) -> syn::Result<bool> {
+    let mut is_arg = false;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("inject"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("arg") {
+                is_arg = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `arg` on the field"))
+            }
+        })?;
+    }
+
+    Ok(is_arg)
+}
+
+fn is_arg_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Arg"),
+        _ => false,
+    }
+}
@@ -0,0 +1,54 @@
+use runtime_injector::{
+    Arg, Injector, IntoSingleton, IntoTransient, Svc, WithArg,
+};
+use runtime_injector_derive::Inject;
+
+#[derive(Default)]
+struct Config;
+
+#[derive(Inject)]
+#[inject(singleton)]
+struct Server {
+    config: Svc<Config>,
+    #[inject(arg)]
+    port: Arg<u16>,
+    #[inject(arg)]
+    host: String,
+}
+
+#[derive(Inject)]
+#[inject(transient)]
+struct Handler(Svc<Server>);
+
+#[derive(Inject)]
+struct Marker;
+
+#[test]
+fn derived_providers_request_each_field() {
+    let mut builder = Injector::builder();
+    builder.provide(Config::default.singleton());
+    builder.provide(Server::provider());
+    builder.provide(Handler::provider());
+    builder.with_arg::<Server, u16>(8080);
+    builder.with_arg::<Server, String>("localhost".to_string());
+
+    let injector = builder.build();
+    let handler1: Svc<Handler> = injector.get().unwrap();
+    let handler2: Svc<Handler> = injector.get().unwrap();
+    assert!(!Svc::ptr_eq(&handler1, &handler2));
+    assert!(Svc::ptr_eq(&handler1.0, &handler2.0));
+    assert_eq!(8080, *handler1.0.port);
+    assert_eq!("localhost", handler1.0.host);
+
+    let config: Svc<Config> = injector.get().unwrap();
+    assert!(Svc::ptr_eq(&config, &handler1.0.config));
+}
+
+#[test]
+fn derived_factories_can_be_registered_manually() {
+    let mut builder = Injector::builder();
+    builder.provide(Marker::injector().transient());
+
+    let injector = builder.build();
+    let _marker: Svc<Marker> = injector.get().unwrap();
+}